@@ -42,6 +42,28 @@ except ImportError:
 
 PORT = int(os.environ.get("PORT", 8080))
 
+# Full CLI / LaTeX output in the logs (may contain CV and job offer text)
+LOG_FULL_BODIES = os.environ.get("LOG_FULL_BODIES", "").strip().lower() in ("1", "true", "yes", "on")
+MAX_LOGGED_CHARS = 500
+
+# Screenshot formats accepted by /extract-image
+IMAGE_SUFFIXES = {"image/png": ".png", "image/jpeg": ".jpg", "image/webp": ".webp", "image/gif": ".gif"}
+NO_JOB_POSTING = "NO_JOB_POSTING"
+
+
+def redact(text, keep_tail=False):
+    """Shorten tool output before logging it, unless LOG_FULL_BODIES is set."""
+    if LOG_FULL_BODIES or len(text) <= MAX_LOGGED_CHARS:
+        return text
+    hidden = len(text) - MAX_LOGGED_CHARS
+    if keep_tail:
+        return f"[{hidden} chars hidden, set LOG_FULL_BODIES=true to show]...{text[-MAX_LOGGED_CHARS:]}"
+    return f"{text[:MAX_LOGGED_CHARS]}...[{hidden} chars hidden, set LOG_FULL_BODIES=true to show]"
+
+
+class ClaudeTimeout(Exception):
+    """The claude CLI did not answer in time (HTTP 504, retryable by the bot)."""
+
 class ClaudeHandler(BaseHTTPRequestHandler):
     def _set_headers(self, status=200, content_type="application/json"):
         self.send_response(status)
@@ -87,10 +109,14 @@ class ClaudeHandler(BaseHTTPRequestHandler):
                 result = self.handle_match_skills(data)
             elif path == "/salary-analysis":
                 result = self.handle_salary_analysis(data)
+            elif path == "/analyze":
+                result = self.handle_analyze(data)
             elif path == "/generate-cv":
                 result = self.handle_generate_cv(data)
             elif path == "/extract-pdf":
                 result = self.handle_extract_pdf(data)
+            elif path == "/extract-image":
+                result = self.handle_extract_image(data)
             elif path == "/generate-pdf":
                 result = self.handle_generate_pdf(data)
             else:
@@ -99,26 +125,34 @@ class ClaudeHandler(BaseHTTPRequestHandler):
 
             self._send_json(result)
 
+        except ClaudeTimeout as e:
+            self._send_error(str(e), 504)
+        except ValueError as e:
+            # Missing field or undecodable input: the request must be fixed, not retried
+            self._send_error(str(e), 400)
         except Exception as e:
             traceback.print_exc()
             self._send_error(str(e), 500)
 
-    def run_claude(self, prompt: str, timeout: int = 120) -> str:
-        """Execute claude -p with the given prompt."""
+    def run_claude(self, prompt: str, timeout: int = 120, allowed_tools=None) -> str:
+        """Execute claude -p with the given prompt (allowed_tools: e.g. ["Read"] for local files)."""
+        command = ["claude", "-p", prompt]
+        if allowed_tools:
+            command += ["--allowedTools", ",".join(allowed_tools)]
         try:
             result = subprocess.run(
-                ["claude", "-p", prompt],
+                command,
                 capture_output=True,
                 text=True,
                 timeout=timeout
             )
 
             if result.returncode != 0 and result.stderr:
-                print(f"Claude stderr: {result.stderr}")
+                print(f"Claude stderr: {redact(result.stderr)}")
 
             return result.stdout.strip()
         except subprocess.TimeoutExpired:
-            raise Exception(f"Claude timeout after {timeout}s")
+            raise ClaudeTimeout(f"Claude timeout after {timeout}s")
         except Exception as e:
             raise Exception(f"Claude error: {e}")
 
@@ -148,7 +182,7 @@ class ClaudeHandler(BaseHTTPRequestHandler):
     "benefits": ["avantage1"],
     "salary_range": null,
     "summary": "résumé en 2-3 phrases"
-}}
+}}{self.language_instruction(data)}
 
 Offre:
 {job_description}'''
@@ -160,6 +194,7 @@ Offre:
         """Match CV skills with job requirements."""
         job_description = data.get("job_description", "")
         cv_content = data.get("cv_content", "CV non fourni")
+        skills_section = self.declared_skills_section(data)
 
         prompt = f'''Compare ce CV avec cette offre et retourne UNIQUEMENT un JSON valide:
 {{
@@ -171,7 +206,7 @@ Offre:
 }}
 
 CV:
-{cv_content}
+{cv_content}{skills_section}{self.seniority_instruction(data)}{self.language_instruction(data)}
 
 Offre:
 {job_description}'''
@@ -179,6 +214,64 @@ Offre:
         response = self.run_claude(prompt)
         return self.extract_json(response)
 
+    @staticmethod
+    def declared_skills_section(data: dict) -> str:
+        """Skills from the structured CV profile, used as matching hints."""
+        skills = [s for s in data.get("cv_skills") or [] if isinstance(s, str) and s.strip()]
+        if not skills:
+            return ""
+        return "\n\nCompétences déclarées (profil du CV, à privilégier pour le matching):\n" + ", ".join(skills)
+
+    SENIORITY_LEVELS = {
+        "junior": "junior (0 à 2 ans d'expérience)",
+        "mid": "confirmé (3 à 5 ans d'expérience)",
+        "senior": "senior (plus de 5 ans d'expérience)",
+        "lead": "lead (encadrement technique d'une équipe)",
+    }
+
+    @classmethod
+    def seniority_instruction(cls, data: dict) -> str:
+        """Seniority level targeted by the candidate, so the score isn't skewed by title wording."""
+        level = cls.SENIORITY_LEVELS.get(data.get("seniority") or "")
+        if not level:
+            return ""
+        return (
+            f"\n\nLe candidat vise un poste de niveau {level}. Évalue le match_score pour ce niveau : "
+            "ne pénalise pas un simple écart d'intitulé (ex. « Senior » dans le titre) si les compétences "
+            "attendues sont couvertes, et signale dans les recommandations un réel décalage de niveau."
+        )
+
+    ANONYMIZED_MARKERS = ("[NOM]", "[EMAIL_", "[TÉLÉPHONE_")
+
+    @classmethod
+    def placeholders_instruction(cls, cv_content: str) -> str:
+        """CV anonymized by the bot: markers are restored locally, so they must survive verbatim."""
+        if not any(marker in cv_content for marker in cls.ANONYMIZED_MARKERS):
+            return ""
+        return (
+            "\n\nCe CV est anonymisé : recopie les marqueurs [NOM], [EMAIL_n] et [TÉLÉPHONE_n] tels quels "
+            '(ex. "firstname": "[NOM]", "lastname": "", "email": "[EMAIL_1]"), sans les traduire '
+            "ni inventer de coordonnées."
+        )
+
+    LANGUAGE_NAMES = {"fr": "français", "en": "anglais", "es": "espagnol", "de": "allemand"}
+
+    @classmethod
+    def language_instruction(cls, data: dict) -> str:
+        """Output language (detected from the job posting by the bot); keys stay unchanged."""
+        name = cls.LANGUAGE_NAMES.get(data.get("language") or "")
+        if not name:
+            return ""
+        return f"\n\nRédige toutes les valeurs textuelles du JSON en {name} (les clés restent inchangées)."
+
+    @staticmethod
+    def currency_instruction(data: dict) -> str:
+        """Currency chosen by the user for the salary amounts (ISO code, e.g. USD)."""
+        currency = (data.get("currency") or "").strip().upper()
+        if len(currency) != 3 or not currency.isalpha():
+            return ""
+        return f'\n\nExprime tous les montants en {currency} et renseigne "currency": "{currency}".'
+
     def handle_salary_analysis(self, data: dict) -> dict:
         """Analyze salary for a job."""
         job_description = data.get("job_description", "")
@@ -199,11 +292,65 @@ Offre:
 Offre:
 {job_description}
 
-Localisation: {location}'''
+Localisation: {location}{self.currency_instruction(data)}{self.language_instruction(data)}'''
 
         response = self.run_claude(prompt)
         return self.extract_json(response)
 
+    def handle_analyze(self, data: dict) -> dict:
+        """Job synthesis + skills matching + salary analysis in a single Claude call."""
+        job_description = data.get("job_description", "")
+        if not job_description:
+            raise ValueError("Missing 'job_description' field")
+        cv_content = data.get("cv_content", "CV non fourni")
+        experience_notes = data.get("experience_notes")
+        notes_section = f"\n\nNotes d'expérience du candidat:\n{experience_notes}" if experience_notes else ""
+        notes_section += self.declared_skills_section(data)
+        notes_section += self.seniority_instruction(data)
+        notes_section += self.currency_instruction(data)
+        notes_section += self.language_instruction(data)
+
+        prompt = f'''Analyse cette offre d'emploi et ce CV, puis retourne UNIQUEMENT un JSON valide:
+{{
+    "synthesis": {{
+        "title": "titre du poste",
+        "company": "nom de l'entreprise ou Non spécifié",
+        "location": "lieu ou Non spécifié",
+        "contract_type": "CDI/CDD/etc ou Non spécifié",
+        "key_requirements": ["compétence1", "compétence2"],
+        "responsibilities": ["responsabilité1"],
+        "benefits": ["avantage1"],
+        "salary_range": null,
+        "summary": "résumé en 2-3 phrases"
+    }},
+    "skills_match": {{
+        "match_score": 75,
+        "matched_skills": [{{"skill": "Python", "cv_level": "3 ans", "required": "2 ans", "match": true}}],
+        "missing_skills": [{{"skill": "Kubernetes", "importance": "nice-to-have"}}],
+        "highlights": ["point fort 1"],
+        "recommendations": ["recommandation 1"]
+    }},
+    "salary_analysis": {{
+        "offered_min": null,
+        "offered_max": null,
+        "market_low": 45000,
+        "market_median": 55000,
+        "market_high": 70000,
+        "currency": "EUR",
+        "analysis": "Analyse du positionnement salarial (selon la localisation de l'offre, France par défaut)",
+        "negotiation_tips": ["conseil 1"]
+    }}
+}}
+
+CV:
+{cv_content}{notes_section}
+
+Offre:
+{job_description}'''
+
+        response = self.run_claude(prompt, timeout=180)
+        return self.extract_json(response)
+
     def handle_generate_cv(self, data: dict) -> dict:
         """Generate a tailored CV in LaTeX ModernCV format."""
         cv_content = data.get("cv_content", "")
@@ -300,7 +447,7 @@ Localisation: {location}'''
         prompt = f'''Analyse ce CV et génère une version adaptée au poste visé.
 
 CV SOURCE:
-{cv_content}
+{cv_content}{self.placeholders_instruction(cv_content)}
 
 POSTE VISÉ: {job_title}
 ENTREPRISE: {company}
@@ -658,6 +805,34 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
 
         return "\n".join(lines)
 
+    def handle_extract_image(self, data: dict) -> dict:
+        """Transcribe the text of a job posting screenshot (OCR by Claude)."""
+        image_base64 = data.get("image_base64", "")
+        if not image_base64:
+            raise ValueError("Missing 'image_base64' field")
+        mime_type = data.get("mime_type", "")
+        suffix = IMAGE_SUFFIXES.get(mime_type)
+        if not suffix:
+            raise ValueError(f"Unsupported image type: {mime_type or 'unknown'}")
+
+        image_bytes = base64.b64decode(image_base64)
+        with tempfile.NamedTemporaryFile(suffix=suffix, delete=False) as tmp:
+            tmp.write(image_bytes)
+            tmp_path = tmp.name
+
+        try:
+            prompt = f"""Read the image file at {tmp_path}. It should be a screenshot of a job posting.
+Transcribe all the text of the posting exactly as written, in its original language,
+keeping paragraphs and bullet lists. Output only the transcribed text, without any comment.
+If the image does not contain a job posting, output exactly: {NO_JOB_POSTING}"""
+            text = self.run_claude(prompt, timeout=180, allowed_tools=["Read"])
+        finally:
+            os.unlink(tmp_path)
+
+        if not text or text.strip() == NO_JOB_POSTING:
+            return {"success": False, "error": "No job posting text found in the image", "text": ""}
+        return {"success": True, "text": text.strip()}
+
     def handle_extract_pdf(self, data: dict) -> dict:
         """Extract text from a PDF file."""
         pdf_base64 = data.get("pdf_base64", "")
@@ -732,6 +907,8 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         try:
             # Parse CV content into sections
             sections = self._parse_cv_sections(cv_content, name, job_title)
+            # Optional heading overrides, used to lay out non-CV documents (job synthesis)
+            sections['section_titles'] = data.get("section_titles") or {}
             print(f"Parsed sections: name={sections['name']}, title={sections['title']}")
             print(f"  - profil: {len(sections['profil'])} lines")
             print(f"  - competences: {len(sections['competences'])} lines")
@@ -1048,7 +1225,8 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
                 with open(pdf_file, 'rb') as f:
                     return f.read()
             else:
-                print(f"LaTeX compilation failed. Log:\n{result.stdout}\n{result.stderr}")
+                # The end of the log holds the error, but also the offending CV lines
+                print(f"LaTeX compilation failed. Log:\n{redact(result.stdout, keep_tail=True)}\n{redact(result.stderr)}")
                 return None
 
         except subprocess.TimeoutExpired:
@@ -1192,12 +1370,13 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         story.append(HRFlowable(width="100%", thickness=2, color=MAIN_COLOR, spaceAfter=10))
 
         has_content = False
+        section_titles = sections.get('section_titles') or {}
 
         # === PROFIL ===
         profil = sections.get('profil', [])
         if profil:
             has_content = True
-            story.append(Paragraph("Profil", cv_styles['Section']))
+            story.append(Paragraph(self._escape(section_titles.get('profil', "Profil")), cv_styles['Section']))
             story.append(HRFlowable(width="100%", thickness=0.5, color=MAIN_COLOR, spaceAfter=6))
             profil_text = ' '.join(profil)
             story.append(Paragraph(self._escape_with_bold(profil_text), cv_styles['Body']))
@@ -1206,7 +1385,7 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         competences = sections.get('competences', [])
         if competences:
             has_content = True
-            story.append(Paragraph("Compétences clés", cv_styles['Section']))
+            story.append(Paragraph(self._escape(section_titles.get('competences', "Compétences clés")), cv_styles['Section']))
             story.append(HRFlowable(width="100%", thickness=0.5, color=MAIN_COLOR, spaceAfter=6))
 
             # Create a table for skills
@@ -1242,7 +1421,7 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         experience = sections.get('experience', [])
         if experience:
             has_content = True
-            story.append(Paragraph("Expérience professionnelle", cv_styles['Section']))
+            story.append(Paragraph(self._escape(section_titles.get('experience', "Expérience professionnelle")), cv_styles['Section']))
             story.append(HRFlowable(width="100%", thickness=0.5, color=MAIN_COLOR, spaceAfter=6))
 
             current_entry = None
@@ -1274,7 +1453,7 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         formation = sections.get('formation', [])
         if formation:
             has_content = True
-            story.append(Paragraph("Formation", cv_styles['Section']))
+            story.append(Paragraph(self._escape(section_titles.get('formation', "Formation")), cv_styles['Section']))
             story.append(HRFlowable(width="100%", thickness=0.5, color=MAIN_COLOR, spaceAfter=6))
 
             for line in formation:
@@ -1308,7 +1487,7 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
         interets = sections.get('interets', [])
         if interets:
             has_content = True
-            story.append(Paragraph("Centres d'intérêt", cv_styles['Section']))
+            story.append(Paragraph(self._escape(section_titles.get('interets', "Centres d'intérêt")), cv_styles['Section']))
             story.append(HRFlowable(width="100%", thickness=0.5, color=MAIN_COLOR, spaceAfter=6))
             interets_text = ', '.join(interets)
             story.append(Paragraph(self._escape(interets_text), cv_styles['Body']))
@@ -1395,14 +1574,17 @@ NE PAS INVENTER d'expériences, mais reformuler honnêtement celles existantes p
                 except json.JSONDecodeError:
                     pass
 
-        # Find any JSON object
+        # Find the first JSON object (raw_decode ignores braces inside strings and trailing text)
+        decoder = json.JSONDecoder()
         start = response.find("{")
-        end = response.rfind("}") + 1
-        if start >= 0 and end > start:
+        while start >= 0:
             try:
-                return json.loads(response[start:end])
+                obj, _ = decoder.raw_decode(response, start)
+                if isinstance(obj, dict):
+                    return obj
             except json.JSONDecodeError:
                 pass
+            start = response.find("{", start + 1)
 
         # Return raw response wrapped
         return {"raw_response": response}
@@ -1422,8 +1604,10 @@ def main():
     print(f"  POST /synthesize       - Job synthesis")
     print(f"  POST /match-skills     - Skills matching")
     print(f"  POST /salary-analysis  - Salary analysis")
+    print(f"  POST /analyze          - Synthesis + skills + salary in one call")
     print(f"  POST /generate-cv      - CV generation")
     print(f"  POST /extract-pdf      - PDF text extraction")
+    print(f"  POST /extract-image    - Job posting screenshot transcription")
     print(f"  POST /generate-pdf     - PDF generation from CV content")
 
     try:
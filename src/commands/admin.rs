@@ -1,11 +1,41 @@
 use async_trait::async_trait;
 use serenity::all::{
-    ButtonStyle, CommandInteraction, CommandOptionType, Context, CreateActionRow, CreateButton,
-    CreateCommand, CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseMessage,
-    Permissions,
+    ButtonStyle, CommandInteraction, CommandOptionType, CommandType, Context, CreateActionRow,
+    CreateAttachment, CreateButton, CreateCommand, CreateCommandOption, CreateInteractionResponse,
+    CreateInteractionResponseMessage, EditInteractionResponse, Permissions, UserId,
 };
-
-use super::{CommandError, SlashCommand, get_database};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::{CommandError, SlashCommand, TOGGLE_COMMAND_NAME, defer_response, get_claude_client, get_config, get_database};
+use super::cv::rematch_application;
+use super::jobs::refresh_thread_name;
+use crate::CommandRegistryKey;
+use crate::db::{Database, JobApplication};
+use crate::services::{backup, ClaudeClient};
+
+const MAX_BACKUPS_DISPLAYED: usize = 20;
+const DEFAULT_BACKFILL_LIMIT: i64 = 10;
+const MAX_BACKFILL_LIMIT: i64 = 20;
+/// Pause entre deux appels de matching de /backfill (ne pas saturer le serveur Claude)
+const BACKFILL_DELAY: Duration = Duration::from_secs(3);
+
+/// Un seul /backfill à la fois, même lancé par deux administrateurs
+static BACKFILL_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Dernière candidature traitée par /backfill : l'exécution suivante reprend après elle,
+/// pour que les candidatures en échec ne bloquent pas les suivantes
+static BACKFILL_CURSOR: AtomicI64 = AtomicI64::new(0);
+
+/// Libère BACKFILL_RUNNING à la fin de /backfill, y compris en cas de panique
+struct BackfillGuard;
+
+impl Drop for BackfillGuard {
+    fn drop(&mut self) {
+        BACKFILL_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
 
 fn safe_truncate(s: &str, max_bytes: usize) -> &str {
     if s.len() <= max_bytes {
@@ -46,6 +76,10 @@ impl SlashCommand for ListCvsCommand {
         "List all stored CVs (admin only)"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -54,7 +88,7 @@ impl SlashCommand for ListCvsCommand {
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
         if !has_admin_permission(interaction) {
-            return send_response(ctx, interaction, "❌ You need administrator permissions.").await;
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ You need administrator permissions.").await;
         }
 
         let db = get_database(ctx).await?;
@@ -62,7 +96,7 @@ impl SlashCommand for ListCvsCommand {
             .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
 
         if cvs.is_empty() {
-            return send_response(ctx, interaction, "📋 **All stored CVs:**\n• No CVs in database.").await;
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "📋 **All stored CVs:**\n• No CVs in database.").await;
         }
 
         let mut lines = vec!["📋 **All stored CVs:**".to_string()];
@@ -73,7 +107,7 @@ impl SlashCommand for ListCvsCommand {
             ));
         }
         let response = lines.join("\n");
-        send_response(ctx, interaction, safe_truncate(&response, 1900)).await
+        send_response(ctx, interaction, self.is_ephemeral(interaction), safe_truncate(&response, 1900)).await
     }
 }
 
@@ -105,6 +139,10 @@ impl SlashCommand for GetCvCommand {
         "Retrieve a specific CV by user (admin only)"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -117,7 +155,7 @@ impl SlashCommand for GetCvCommand {
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
         if !has_admin_permission(interaction) {
-            return send_response(ctx, interaction, "❌ You need administrator permissions.").await;
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ You need administrator permissions.").await;
         }
 
         let target_user_id = interaction
@@ -128,28 +166,60 @@ impl SlashCommand for GetCvCommand {
             .and_then(|opt| opt.value.as_user_id())
             .ok_or_else(|| CommandError::MissingParameter("user".to_string()))?;
 
-        let db = get_database(ctx).await?;
-        let cv = db.get_active_cv(target_user_id.get() as i64).await
-            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+        send_user_cv(ctx, interaction, target_user_id, self.is_ephemeral(interaction)).await
+    }
+}
 
-        match cv {
-            None => send_response(ctx, interaction, &format!("📄 No active CV for <@{}>.", target_user_id)).await,
-            Some(cv) => {
-                let preview = cv.extracted_text.as_deref()
-                    .filter(|t| !t.is_empty())
-                    .map(|t| safe_truncate(t, 500))
-                    .unwrap_or("(no extracted text)");
-                let response = format!(
-                    "📄 **CV for <@{}>**\n\
-                     • File: `{}`\n\
-                     • Size: {} bytes\n\
-                     • Uploaded: {}\n\
-                     • Preview:\n```\n{}\n```",
-                    target_user_id, cv.original_name, cv.file_size, cv.created_at, preview
-                );
-                send_response(ctx, interaction, safe_truncate(&response, 1900)).await
-            }
+// ============================================================================
+// Get CV - User context menu (Admin)
+// ============================================================================
+
+pub struct GetCvUserMenuCommand;
+
+impl GetCvUserMenuCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for GetCvUserMenuCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for GetCvUserMenuCommand {
+    fn name(&self) -> &'static str {
+        "Get CV"
+    }
+
+    fn description(&self) -> &'static str {
+        "Retrieve the CV of the selected member (admin only)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .kind(CommandType::User)
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        if !has_admin_permission(interaction) {
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ You need administrator permissions.").await;
         }
+
+        let target_user_id = interaction
+            .data
+            .target_id
+            .map(|target| target.to_user_id())
+            .ok_or_else(|| CommandError::MissingParameter("user".to_string()))?;
+
+        send_user_cv(ctx, interaction, target_user_id, self.is_ephemeral(interaction)).await
     }
 }
 
@@ -181,6 +251,10 @@ impl SlashCommand for ClearAllCvsCommand {
         "Delete all stored CVs (admin only)"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -189,7 +263,7 @@ impl SlashCommand for ClearAllCvsCommand {
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
         if !has_admin_permission(interaction) {
-            return send_response(ctx, interaction, "❌ You need administrator permissions.").await;
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ You need administrator permissions.").await;
         }
 
         let confirm_btn = CreateButton::new("clearallcvs_confirm")
@@ -201,6 +275,7 @@ impl SlashCommand for ClearAllCvsCommand {
         let row = CreateActionRow::Buttons(vec![confirm_btn, cancel_btn]);
 
         let msg = CreateInteractionResponseMessage::new()
+            .ephemeral(self.is_ephemeral(interaction))
             .content("⚠️ **Êtes-vous sûr de vouloir supprimer TOUS les CVs ?** Cette action est irréversible.")
             .components(vec![row]);
         interaction
@@ -210,6 +285,482 @@ impl SlashCommand for ClearAllCvsCommand {
     }
 }
 
+// ============================================================================
+// ExportUser Command (Admin) - Export JSON de toutes les données d'un utilisateur
+// ============================================================================
+
+pub struct ExportUserCommand;
+
+impl ExportUserCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ExportUserCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for ExportUserCommand {
+    fn name(&self) -> &'static str {
+        "exportuser"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export all data of a user as JSON (admin only)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "User to export")
+                    .required(true),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let edit = |content: String| EditInteractionResponse::new().content(content);
+
+        if !has_admin_permission(interaction) {
+            interaction.edit_response(&ctx.http, edit("❌ You need administrator permissions.".to_string())).await
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            return Ok(());
+        }
+
+        let target_user_id = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "user")
+            .and_then(|opt| opt.value.as_user_id())
+            .ok_or_else(|| CommandError::MissingParameter("user".to_string()))?;
+
+        let db = get_database(ctx).await?;
+        let export = db.export_user_data(target_user_id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+        let json = serde_json::to_vec_pretty(&export)
+            .map_err(|e| CommandError::Internal(format!("JSON error: {}", e)))?;
+
+        info!(
+            "Admin '{}' exported data of user {} ({} bytes)",
+            interaction.user.name, target_user_id, json.len()
+        );
+
+        let summary = format!(
+            "📦 **Export for <@{}>**\n\
+             • CVs: {}\n\
+             • Applications: {}\n\
+             • Status changes: {}\n\
+             • Attachments: {}\n\
+             • Reminders: {}",
+            target_user_id,
+            export.cvs.len(),
+            export.applications.len(),
+            export.status_history.len(),
+            export.attachments.len(),
+            export.reminders.len()
+        );
+        let attachment = CreateAttachment::bytes(json, format!("export_{}.json", target_user_id));
+        interaction
+            .edit_response(&ctx.http, edit(summary).new_attachment(attachment))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// RestoreBackup Command (Admin) - Remplace la base active par une sauvegarde
+// ============================================================================
+
+pub struct RestoreBackupCommand;
+
+impl RestoreBackupCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RestoreBackupCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for RestoreBackupCommand {
+    fn name(&self) -> &'static str {
+        "restorebackup"
+    }
+
+    fn description(&self) -> &'static str {
+        "List database backups or restore one (admin only)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "file", "Backup file to restore (omit to list backups)")
+                    .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "confirm", "Type `RESTORE <file>` to confirm")
+                    .required(false),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        if !has_admin_permission(interaction) {
+            return send_ephemeral(ctx, interaction, "❌ You need administrator permissions.").await;
+        }
+
+        let get_option = |name: &str| {
+            interaction
+                .data
+                .options
+                .iter()
+                .find(|opt| opt.name == name)
+                .and_then(|opt| opt.value.as_str())
+                .map(|v| v.trim().to_string())
+        };
+        let config = get_config(ctx).await?;
+        let dir = Path::new(&config.backup_dir);
+
+        let Some(file) = get_option("file") else {
+            let backups = backup::list_backups(dir).await
+                .map_err(|e| CommandError::Internal(format!("Backup error: {}", e)))?;
+            if backups.is_empty() {
+                let content = format!(
+                    "📦 No backup in `{}`. Set `BACKUP_INTERVAL_HOURS` to enable automatic backups.",
+                    config.backup_dir
+                );
+                return send_ephemeral(ctx, interaction, &content).await;
+            }
+
+            let mut lines = vec![format!("📦 **Database backups** (`{}`, newest first):", config.backup_dir)];
+            for file in backups.iter().take(MAX_BACKUPS_DISPLAYED) {
+                lines.push(format!("• `{}` — {} KB", file.name, file.size / 1024));
+            }
+            if backups.len() > MAX_BACKUPS_DISPLAYED {
+                lines.push(format!("… and {} older backup(s)", backups.len() - MAX_BACKUPS_DISPLAYED));
+            }
+            lines.push("Use `/restorebackup file:<name>` to restore one.".to_string());
+            return send_ephemeral(ctx, interaction, safe_truncate(&lines.join("\n"), 1900)).await;
+        };
+
+        // Action destructrice : phrase saisie, pas un simple bouton
+        let phrase = backup::restore_confirmation_phrase(&file);
+        if get_option("confirm").as_deref() != Some(phrase.as_str()) {
+            let mismatch = if get_option("confirm").is_some() {
+                "❌ The confirmation phrase does not match.\n"
+            } else {
+                ""
+            };
+            let content = format!(
+                "{}⚠️ **Restoring `{}` replaces the whole live database.** Everything written since \
+                 this backup is lost (the current database is backed up first).\n\
+                 To proceed, run the command again with `confirm:{}`",
+                mismatch, file, phrase
+            );
+            return send_ephemeral(ctx, interaction, &content).await;
+        }
+
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let db = get_database(ctx).await?;
+        warn!("Admin '{}' is restoring database backup {}", interaction.user.name, file);
+        let content = match backup::restore_backup(&db, dir, &file).await {
+            Ok(safety_name) => format!(
+                "✅ **Database restored from `{}`.**\nThe previous state was saved as `{}`.",
+                file, safety_name
+            ),
+            Err(e) => {
+                warn!("Failed to restore database backup {}: {}", file, e);
+                format!("❌ Restore failed: {}", e)
+            }
+        };
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Backfill Command (Admin) - Complète le matching des anciennes candidatures
+// ============================================================================
+
+pub struct BackfillCommand;
+
+impl BackfillCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BackfillCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for BackfillCommand {
+    fn name(&self) -> &'static str {
+        "backfill"
+    }
+
+    fn description(&self) -> &'static str {
+        "Re-run skills matching for applications without a match score (admin only)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "limit", "Applications to process in this run (default 10)")
+                    .required(false)
+                    .min_int_value(1)
+                    .max_int_value(MAX_BACKFILL_LIMIT as u64),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        if !has_admin_permission(interaction) {
+            return send_ephemeral(ctx, interaction, "❌ You need administrator permissions.").await;
+        }
+
+        let limit = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "limit")
+            .and_then(|opt| opt.value.as_i64())
+            .unwrap_or(DEFAULT_BACKFILL_LIMIT)
+            .clamp(1, MAX_BACKFILL_LIMIT);
+
+        if BACKFILL_RUNNING.swap(true, Ordering::SeqCst) {
+            return send_ephemeral(ctx, interaction, "⏳ A backfill is already running, try again when it is done.").await;
+        }
+        let _guard = BackfillGuard;
+        run_backfill(ctx, interaction, limit).await
+    }
+}
+
+async fn run_backfill(ctx: &Context, interaction: &CommandInteraction, limit: i64) -> Result<(), CommandError> {
+    interaction.defer_ephemeral(&ctx.http).await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    let edit = |content: String| EditInteractionResponse::new().content(content);
+
+    let db = get_database(ctx).await?;
+    let claude_client = get_claude_client(ctx).await?;
+    let mut apps = db.list_applications_missing_analysis(BACKFILL_CURSOR.load(Ordering::SeqCst), limit).await
+        .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+    if apps.is_empty() && BACKFILL_CURSOR.swap(0, Ordering::SeqCst) > 0 {
+        // Fin de la liste atteinte : nouveau passage sur les candidatures en échec
+        apps = db.list_applications_missing_analysis(0, limit).await
+            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+    }
+    if let Some(last) = apps.last() {
+        BACKFILL_CURSOR.store(last.id, Ordering::SeqCst);
+    }
+
+    if apps.is_empty() {
+        interaction.edit_response(&ctx.http, edit("✅ No application is missing its match score.".to_string())).await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        return Ok(());
+    }
+
+    let mut lines = Vec::new();
+    let mut failed = 0;
+    for (index, app) in apps.iter().enumerate() {
+        if index > 0 {
+            tokio::time::sleep(BACKFILL_DELAY).await;
+        }
+        let progress = format!("⏳ Backfilling application {}/{}…", index + 1, apps.len());
+        if let Err(e) = interaction.edit_response(&ctx.http, edit(progress)).await {
+            warn!("Failed to update backfill progress: {}", e);
+        }
+
+        let label = format!(
+            "#{} {} — {}",
+            app.id,
+            app.company.as_deref().unwrap_or("N/A"),
+            app.job_title.as_deref().unwrap_or("N/A")
+        );
+        match backfill_application(&db, &claude_client, app).await {
+            Ok(score) => {
+                refresh_thread_name(ctx, &JobApplication { match_score: Some(score as i32), ..app.clone() }).await;
+                lines.push(format!("• {} : **{}%**", label, score));
+            }
+            Err(e) => {
+                warn!("Backfill failed for application {}: {}", app.id, e);
+                failed += 1;
+                lines.push(format!("• {} : ❌ {}", label, e));
+            }
+        }
+    }
+
+    let remaining = db.count_applications_missing_analysis().await
+        .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+    info!(
+        "Admin '{}' backfilled {} application(s) ({} failed, {} remaining)",
+        interaction.user.name, apps.len() - failed, failed, remaining
+    );
+
+    let mut content = format!(
+        "🔧 **Backfill done:** {} filled, {} failed, {} remaining\n{}",
+        apps.len() - failed, failed, remaining, lines.join("\n")
+    );
+    if remaining > 0 {
+        content.push_str("\nRun `/backfill` again to continue.");
+    }
+    interaction.edit_response(&ctx.http, edit(safe_truncate(&content, 1900).to_string())).await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    Ok(())
+}
+
+/// Relance le matching d'une candidature avec son CV (le CV associé, sinon le CV actif)
+async fn backfill_application(
+    db: &Database,
+    claude_client: &ClaudeClient,
+    app: &JobApplication,
+) -> Result<u32, String> {
+    let cv = match app.base_cv_id {
+        Some(cv_id) => db.get_cv_by_id(cv_id).await,
+        None => db.get_active_cv(app.user_id).await,
+    }
+    .map_err(|e| format!("DB error: {}", e))?
+    .ok_or("CV not found")?;
+    rematch_application(db, claude_client, app, &cv, true).await.map(|skills| skills.match_score)
+}
+
+// ============================================================================
+// ToggleCommand Command (Admin) - Active / désactive une commande sur le serveur
+// ============================================================================
+
+pub struct ToggleCommandCommand;
+
+impl ToggleCommandCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ToggleCommandCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for ToggleCommandCommand {
+    fn name(&self) -> &'static str {
+        TOGGLE_COMMAND_NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "Enable or disable a command on this server (admin only)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "name", "Command name, without the slash")
+                    .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "state", "New state")
+                    .required(true)
+                    .add_string_choice("on", "on")
+                    .add_string_choice("off", "off"),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        if !has_admin_permission(interaction) {
+            return send_ephemeral(ctx, interaction, "❌ You need administrator permissions.").await;
+        }
+        let Some(guild_id) = interaction.guild_id else {
+            return send_ephemeral(ctx, interaction, "❌ This command can only be used in a server.").await;
+        };
+
+        let option = |name: &str| {
+            interaction
+                .data
+                .options
+                .iter()
+                .find(|opt| opt.name == name)
+                .and_then(|opt| opt.value.as_str())
+                .unwrap_or_default()
+        };
+        let requested = option("name").trim().trim_start_matches('/').to_lowercase();
+        let enabled = option("state") == "on";
+
+        let registry = ctx.data.read().await.get::<CommandRegistryKey>().cloned()
+            .ok_or_else(|| CommandError::Internal("CommandRegistry not found".to_string()))?;
+        let Some(command_name) = registry.canonical_name(&requested) else {
+            return send_ephemeral(ctx, interaction, &format!("❌ Unknown command `/{}`.", requested)).await;
+        };
+        if command_name == TOGGLE_COMMAND_NAME {
+            return send_ephemeral(ctx, interaction, "❌ `/togglecommand` cannot be disabled.").await;
+        }
+
+        let db = get_database(ctx).await?;
+        let guild = guild_id.get() as i64;
+        db.set_command_enabled(guild, command_name, enabled).await
+            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+        let disabled = db.list_disabled_commands(guild).await
+            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+        info!(
+            "Admin '{}' turned /{} {} in guild {}",
+            interaction.user.name, command_name, if enabled { "on" } else { "off" }, guild_id
+        );
+
+        let mut content = if enabled {
+            format!("✅ `/{}` is now enabled on this server.", command_name)
+        } else {
+            format!("🚫 `/{}` is now disabled on this server (its aliases too).", command_name)
+        };
+        if disabled.is_empty() {
+            content.push_str("\nNo command is disabled.");
+        } else {
+            let list: Vec<String> = disabled.iter().map(|name| format!("`/{}`", name)).collect();
+            content.push_str(&format!("\nDisabled commands: {}", list.join(", ")));
+        }
+        send_ephemeral(ctx, interaction, safe_truncate(&content, 1900)).await
+    }
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -223,15 +774,60 @@ fn has_admin_permission(interaction: &CommandInteraction) -> bool {
         .unwrap_or(false)
 }
 
+/// Affiche le CV actif d'un utilisateur (partagé par /getcv et le menu contextuel)
+async fn send_user_cv(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    target_user_id: UserId,
+    ephemeral: bool,
+) -> Result<(), CommandError> {
+    let db = get_database(ctx).await?;
+    let cv = db.get_active_cv(target_user_id.get() as i64).await
+        .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+
+    match cv {
+        None => send_response(ctx, interaction, ephemeral, &format!("📄 No active CV for <@{}>.", target_user_id)).await,
+        Some(cv) => {
+            let preview = cv.extracted_text.as_deref()
+                .filter(|t| !t.is_empty())
+                .map(|t| safe_truncate(t, 500))
+                .unwrap_or("(no extracted text)");
+            let response = format!(
+                "📄 **CV for <@{}>**\n\
+                 • File: `{}`\n\
+                 • Size: {} bytes\n\
+                 • Uploaded: {}\n\
+                 • Preview:\n```\n{}\n```",
+                target_user_id, cv.original_name, cv.file_size, cv.created_at, preview
+            );
+            send_response(ctx, interaction, ephemeral, safe_truncate(&response, 1900)).await
+        }
+    }
+}
+
 async fn send_response(
     ctx: &Context,
     interaction: &CommandInteraction,
+    ephemeral: bool,
     content: &str,
 ) -> Result<(), CommandError> {
-    let msg = CreateInteractionResponseMessage::new().content(content);
+    let msg = CreateInteractionResponseMessage::new().content(content).ephemeral(ephemeral);
     
     interaction
         .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
         .await
         .map_err(|e| CommandError::ResponseFailed(e.to_string()))
 }
+
+async fn send_ephemeral(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    content: &str,
+) -> Result<(), CommandError> {
+    let msg = CreateInteractionResponseMessage::new().content(content).ephemeral(true);
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+}
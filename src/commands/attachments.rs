@@ -0,0 +1,349 @@
+use async_trait::async_trait;
+use serenity::all::{
+    Colour, CommandInteraction, CommandOptionType, Context, CreateAttachment, CreateCommand,
+    CreateCommandOption, CreateEmbed, EditInteractionResponse,
+};
+use std::path::PathBuf;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::{CommandError, SlashCommand, attachment_option, defer_response, get_database, private_option};
+use super::embeds::truncate_chars;
+use crate::db::Database;
+use crate::services::storage;
+
+const COLOR_ATTACHMENT: Colour = Colour::from_rgb(52, 73, 94);
+
+/// Taille maximale d'une pièce jointe (10 Mo)
+const MAX_ATTACHMENT_SIZE: u32 = 10 * 1024 * 1024;
+/// Taille totale maximale renvoyée en une fois par /attachments (limite Discord)
+const MAX_RESEND_TOTAL_SIZE: i64 = 8 * 1024 * 1024;
+/// Nombre maximal de fichiers par message Discord
+const MAX_RESEND_FILES: usize = 10;
+
+const ALLOWED_TYPES: &[&str] = &[
+    "application/pdf",
+    "text/plain",
+    "application/msword",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "message/rfc822",
+    "image/png",
+    "image/jpeg",
+];
+const ALLOWED_EXTENSIONS: &[&str] = &["pdf", "txt", "md", "doc", "docx", "eml", "msg", "png", "jpg", "jpeg"];
+
+// ============================================================================
+// Attach Command - Store a document with an application
+// ============================================================================
+
+pub struct AttachCommand;
+
+impl AttachCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AttachCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for AttachCommand {
+    fn name(&self) -> &'static str {
+        "attach"
+    }
+
+    fn description(&self) -> &'static str {
+        "Attach a document (recruiter email, offer letter...) to an application"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "application_id",
+                    "Application ID (from /status)",
+                )
+                .required(true)
+                .min_int_value(1),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Attachment,
+                    "file",
+                    "Document to attach (PDF, DOC, DOCX, TXT, EML, PNG, JPG — 10 MB max)",
+                )
+                .required(true),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let application_id = get_application_id(interaction)?;
+
+        let db = get_database(ctx).await?;
+        check_application_owner(&db, application_id, user_id).await?;
+
+        let attachment = attachment_option(interaction, "file")
+            .map_err(CommandError::InvalidInput)?
+            .ok_or_else(|| CommandError::MissingParameter("file".to_string()))?;
+
+        // Vérifier le type et la taille
+        let content_type = attachment.content_type.as_deref().unwrap_or("application/octet-stream");
+        let extension = PathBuf::from(&attachment.filename)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        if !ALLOWED_TYPES.iter().any(|t| content_type.contains(t)) && !ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(CommandError::InvalidInput(format!(
+                "Unsupported file type `{}`. Allowed: PDF, DOC, DOCX, TXT, EML, PNG, JPG",
+                content_type
+            )));
+        }
+        if attachment.size > MAX_ATTACHMENT_SIZE {
+            return Err(CommandError::InvalidInput("File is too large (max 10 MB)".to_string()));
+        }
+
+        info!("Downloading attachment {} for application {}", attachment.filename, application_id);
+        let file_bytes = attachment.download().await
+            .map_err(|e| CommandError::Internal(format!("Download failed: {}", e)))?;
+
+        // Stockage: data/attachments/{application_id}/{uuid}.{ext}
+        let storage_dir = PathBuf::from("data/attachments").join(application_id.to_string());
+        if let Err(e) = tokio::fs::create_dir_all(&storage_dir).await {
+            error!("Failed to create attachment dir: {}", e);
+            return Err(CommandError::Internal(format!("Storage error: {}", e)));
+        }
+        let stored_name = if extension.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            format!("{}.{}", Uuid::new_v4(), extension)
+        };
+        let file_path = storage_dir.join(stored_name);
+
+        if let Err(e) = storage::write_atomic(&file_path, &file_bytes).await {
+            error!("Failed to write attachment file: {}", e);
+            return Err(CommandError::Internal(format!("File write error: {}", e)));
+        }
+
+        let stored_id = db.add_attachment(
+            application_id,
+            user_id,
+            &attachment.filename,
+            file_path.to_string_lossy().as_ref(),
+            attachment.size as i64,
+            attachment.content_type.as_deref(),
+        ).await.map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        info!("Attachment {} saved for application {} at {:?}", stored_id, application_id, file_path);
+
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().content(format!(
+                    "📎 `{}` ajouté à la candidature **#{}** (pièce jointe #{}).\nUtilisez `/attachments application_id:{}` pour la retrouver.",
+                    attachment.filename, application_id, stored_id, application_id
+                )),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Attachments Command - List and re-download attached documents
+// ============================================================================
+
+pub struct AttachmentsCommand;
+
+impl AttachmentsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AttachmentsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for AttachmentsCommand {
+    fn name(&self) -> &'static str {
+        "attachments"
+    }
+
+    fn description(&self) -> &'static str {
+        "List and download the documents attached to an application"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "application_id",
+                    "Application ID (from /status)",
+                )
+                .required(true)
+                .min_int_value(1),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "attachment_id",
+                    "Only download this attachment",
+                )
+                .required(false)
+                .min_int_value(1),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let application_id = get_application_id(interaction)?;
+        let only_attachment = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "attachment_id")
+            .and_then(|opt| opt.value.as_i64());
+
+        let db = get_database(ctx).await?;
+        check_application_owner(&db, application_id, user_id).await?;
+
+        let mut attachments = db.list_attachments(application_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        if let Some(attachment_id) = only_attachment {
+            attachments.retain(|a| a.id == attachment_id);
+            if attachments.is_empty() {
+                return Err(CommandError::NotFound(format!(
+                    "Attachment #{} not found for application #{}",
+                    attachment_id, application_id
+                )));
+            }
+        }
+
+        if attachments.is_empty() {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(format!(
+                        "📎 Aucune pièce jointe pour la candidature **#{}**. Ajoutez-en avec `/attach`.",
+                        application_id
+                    )),
+                )
+                .await
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            return Ok(());
+        }
+
+        let lines: Vec<String> = attachments
+            .iter()
+            .map(|a| {
+                format!(
+                    "• `#{}` **{}** — {} ({})",
+                    a.id,
+                    a.filename,
+                    format_size(a.file_size.unwrap_or(0)),
+                    a.created_at.get(..10).unwrap_or(&a.created_at)
+                )
+            })
+            .collect();
+
+        // Renvoyer les fichiers si la limite Discord le permet
+        let total_size: i64 = attachments.iter().map(|a| a.file_size.unwrap_or(0)).sum();
+        let resend = attachments.len() <= MAX_RESEND_FILES && total_size <= MAX_RESEND_TOTAL_SIZE;
+
+        let mut files = Vec::new();
+        if resend {
+            for a in &attachments {
+                match tokio::fs::read(&a.path).await {
+                    Ok(bytes) => files.push(CreateAttachment::bytes(bytes, a.filename.clone())),
+                    Err(e) => warn!("Attachment file {} not readable: {}", a.path, e),
+                }
+            }
+        }
+
+        let footer = if resend {
+            "Fichiers joints ci-dessous".to_string()
+        } else {
+            "Trop de fichiers pour un seul message : utilisez l'option attachment_id".to_string()
+        };
+
+        let embed = CreateEmbed::new()
+            .title(format!("📎 Pièces jointes — candidature #{}", application_id))
+            .colour(COLOR_ATTACHMENT)
+            .description(truncate_chars(&lines.join("\n"), 4000))
+            .footer(serenity::all::CreateEmbedFooter::new(footer));
+
+        let mut response = EditInteractionResponse::new().embed(embed);
+        for file in files {
+            response = response.new_attachment(file);
+        }
+
+        interaction
+            .edit_response(&ctx.http, response)
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn get_application_id(interaction: &CommandInteraction) -> Result<i64, CommandError> {
+    interaction
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "application_id")
+        .and_then(|opt| opt.value.as_i64())
+        .ok_or_else(|| CommandError::MissingParameter("application_id".to_string()))
+}
+
+async fn check_application_owner(db: &Database, application_id: i64, user_id: i64) -> Result<(), CommandError> {
+    let owner = db.get_application_owner(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| CommandError::NotFound(format!("Application #{} not found", application_id)))?;
+    if owner != user_id {
+        return Err(CommandError::Unauthorized("This application does not belong to you".to_string()));
+    }
+    Ok(())
+}
+
+fn format_size(bytes: i64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} Mo", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} Ko", (bytes + 1023) / 1024)
+    }
+}
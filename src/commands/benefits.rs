@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use serenity::all::{Colour, CommandInteraction, Context, CreateCommand, CreateEmbed, EditInteractionResponse};
+use std::collections::HashMap;
+
+use super::{CommandError, SlashCommand, defer_response, get_database, private_option};
+use super::embeds::{MAX_FIELD_NAME_CHARS, field_value, truncate_chars};
+use crate::db::JobApplication;
+
+const COLOR_BENEFITS: Colour = Colour::from_rgb(26, 188, 156);
+/// Avantages listés dans le classement commun
+const MAX_COMMON_BENEFITS: usize = 10;
+/// Candidatures détaillées (un champ d'embed chacune, Discord en autorise 25)
+const MAX_APPLICATION_FIELDS: usize = 10;
+
+// ============================================================================
+// Benefits Command - Avantages mentionnés dans les offres, comparés entre candidatures
+// ============================================================================
+
+pub struct BenefitsCommand;
+
+impl BenefitsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for BenefitsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for BenefitsCommand {
+    fn name(&self) -> &'static str {
+        "benefits"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compare the benefits mentioned across your job applications"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let db = get_database(ctx).await?;
+        let apps: Vec<JobApplication> = db.list_applications(user_id, None, 500).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .into_iter()
+            .filter(|app| !app.benefits_parsed().is_empty())
+            .collect();
+
+        let embed = if apps.is_empty() {
+            CreateEmbed::new()
+                .title("🎁 Avantages des offres")
+                .colour(COLOR_BENEFITS)
+                .description(
+                    "Aucun avantage enregistré.\n\n\
+                     Les avantages sont extraits des offres analysées avec `/applyjob`.",
+                )
+        } else {
+            build_benefits_embed(&apps)
+        };
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Avantage commun à plusieurs offres
+#[derive(Debug, PartialEq)]
+struct BenefitSummary {
+    /// Libellé tel qu'il apparaît dans la première offre
+    label: String,
+    application_ids: Vec<i64>,
+}
+
+/// Regroupe les avantages identiques (casse et espaces ignorés) des couples
+/// (ID de candidature, avantages), les plus fréquents d'abord
+fn aggregate_benefits(apps: &[(i64, Vec<String>)]) -> Vec<BenefitSummary> {
+    let mut by_key: HashMap<String, BenefitSummary> = HashMap::new();
+    for (app_id, benefits) in apps {
+        for benefit in benefits {
+            let label = benefit.trim();
+            if label.is_empty() {
+                continue;
+            }
+            let summary = by_key.entry(label.to_lowercase()).or_insert_with(|| BenefitSummary {
+                label: label.to_string(),
+                application_ids: Vec::new(),
+            });
+            if !summary.application_ids.contains(app_id) {
+                summary.application_ids.push(*app_id);
+            }
+        }
+    }
+
+    let mut summaries: Vec<BenefitSummary> = by_key.into_values().collect();
+    summaries.sort_by(|a, b| {
+        b.application_ids
+            .len()
+            .cmp(&a.application_ids.len())
+            .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+    });
+    summaries
+}
+
+fn build_benefits_embed(apps: &[JobApplication]) -> CreateEmbed {
+    let benefits: Vec<(i64, Vec<String>)> = apps.iter().map(|app| (app.id, app.benefits_parsed())).collect();
+    let common = aggregate_benefits(&benefits)
+        .into_iter()
+        .take(MAX_COMMON_BENEFITS)
+        .map(|summary| {
+            let ids = summary
+                .application_ids
+                .iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("• **{}** — {} offre(s) ({})", summary.label, summary.application_ids.len(), ids)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("🎁 Avantages des offres ({} candidature(s))", apps.len()))
+        .colour(COLOR_BENEFITS)
+        .description(format!("**Les plus fréquents :**\n{}", common));
+
+    for app in apps.iter().take(MAX_APPLICATION_FIELDS) {
+        let name = format!(
+            "#{} {} — {}",
+            app.id,
+            app.company.as_deref().unwrap_or("N/A"),
+            app.job_title.as_deref().unwrap_or("N/A")
+        );
+        let benefits = app.benefits_parsed().join(", ");
+        embed = embed.field(truncate_chars(&name, MAX_FIELD_NAME_CHARS), field_value(&benefits), false);
+    }
+
+    if apps.len() > MAX_APPLICATION_FIELDS {
+        embed = embed.footer(serenity::all::CreateEmbedFooter::new(format!(
+            "{} candidature(s) les plus récentes affichées sur {}",
+            MAX_APPLICATION_FIELDS,
+            apps.len()
+        )));
+    }
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn benefits(id: i64, items: &[&str]) -> (i64, Vec<String>) {
+        (id, items.iter().map(|b| b.to_string()).collect())
+    }
+
+    #[test]
+    fn test_aggregate_benefits() {
+        let apps = vec![
+            benefits(1, &["Télétravail", "Tickets restaurant"]),
+            benefits(2, &["télétravail ", "RTT", "RTT"]),
+            benefits(3, &["Mutuelle", "Télétravail", ""]),
+        ];
+        let summaries = aggregate_benefits(&apps);
+        assert_eq!(
+            summaries[0],
+            BenefitSummary { label: "Télétravail".to_string(), application_ids: vec![1, 2, 3] }
+        );
+        // Égalité de fréquence : ordre alphabétique, un avantage répété ne compte qu'une fois par offre
+        let labels: Vec<&str> = summaries.iter().skip(1).map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Mutuelle", "RTT", "Tickets restaurant"]);
+        assert_eq!(summaries[2].application_ids, vec![2]);
+    }
+}
@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use serenity::all::{
+    Colour, CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    CreateEmbed, EditInteractionResponse,
+};
+use std::collections::HashSet;
+use tracing::info;
+
+use super::{CommandError, SlashCommand, defer_response, get_database, private_option};
+use super::embeds::truncate_chars;
+
+const COLOR_COMPARE: Colour = Colour::from_rgb(52, 152, 219);
+const MAX_DIFF_LINES: usize = 6;
+
+// ============================================================================
+// CompareCv Command - What changed between the base CV and the tailored one
+// ============================================================================
+
+pub struct CompareCvCommand;
+
+impl CompareCvCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CompareCvCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for CompareCvCommand {
+    fn name(&self) -> &'static str {
+        "comparecv"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compare the tailored CV of an application with your base CV"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "application_id",
+                    "Application ID (from /status)",
+                )
+                .required(true)
+                .min_int_value(1),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let application_id = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "application_id")
+            .and_then(|opt| opt.value.as_i64())
+            .ok_or_else(|| CommandError::MissingParameter("application_id".to_string()))?;
+
+        let db = get_database(ctx).await?;
+
+        let record = db.get_generated_cv(application_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .ok_or_else(|| CommandError::NotFound("Application not found".to_string()))?;
+
+        if record.user_id != user_id {
+            return Err(CommandError::Unauthorized("This application does not belong to you".to_string()));
+        }
+
+        let generated_text = record.generated_cv_text.as_deref().ok_or_else(|| {
+            CommandError::NotFound("No tailored CV was generated for this application".to_string())
+        })?;
+
+        let base_text = match record.base_cv_id {
+            Some(cv_id) => db.get_cv_by_id(cv_id).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+                .and_then(|cv| cv.extracted_text),
+            None => None,
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title(format!(
+                "🔍 CV adapté — {} ({})",
+                record.job_title.as_deref().unwrap_or("N/A"),
+                record.company.as_deref().unwrap_or("N/A")
+            ))
+            .colour(COLOR_COMPARE)
+            .footer(serenity::all::CreateEmbedFooter::new(format!("Candidature #{}", application_id)));
+
+        let adaptations = if record.adaptations.is_empty() {
+            "Aucune adaptation enregistrée".to_string()
+        } else {
+            record.adaptations.iter().map(|a| format!("• {}", a)).collect::<Vec<_>>().join("\n")
+        };
+        embed = embed.field("✨ Adaptations", truncate_chars(&adaptations, 1024), false);
+
+        match base_text.filter(|t| !t.trim().is_empty()) {
+            Some(base) => {
+                let diff = summarize_cv_diff(&base, generated_text);
+                embed = embed.field(
+                    "📊 Résumé des différences",
+                    format!(
+                        "➕ {} ligne(s) ajoutée(s)\n➖ {} ligne(s) retirée(s)\n🟰 {} ligne(s) conservée(s)",
+                        diff.added.len(),
+                        diff.removed.len(),
+                        diff.unchanged
+                    ),
+                    false,
+                );
+                if !diff.added.is_empty() || !diff.removed.is_empty() {
+                    embed = embed.field("📝 Extrait", format_diff_excerpt(&diff), false);
+                }
+            }
+            None => {
+                embed = embed.field(
+                    "📊 Résumé des différences",
+                    "⚠️ Texte du CV de base indisponible (CV supprimé ou non extrait).",
+                    false,
+                );
+            }
+        }
+
+        info!("User {} compared tailored CV of application {}", user_id, application_id);
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Diff helpers
+// ============================================================================
+
+/// Résumé ligne à ligne des différences entre deux versions d'un CV
+#[derive(Debug, Default, PartialEq)]
+pub struct CvDiffSummary {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+fn normalize_line(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Compare les lignes (non vides, espaces normalisés) du CV de base et du CV généré.
+/// L'ordre des lignes est ignoré : seul le contenu ajouté ou retiré compte.
+pub fn summarize_cv_diff(base: &str, generated: &str) -> CvDiffSummary {
+    let base_lines: Vec<String> = base.lines().map(normalize_line).filter(|l| !l.is_empty()).collect();
+    let generated_lines: Vec<String> = generated.lines().map(normalize_line).filter(|l| !l.is_empty()).collect();
+
+    let base_set: HashSet<&str> = base_lines.iter().map(|l| l.as_str()).collect();
+    let generated_set: HashSet<&str> = generated_lines.iter().map(|l| l.as_str()).collect();
+
+    let mut seen = HashSet::new();
+    let added = generated_lines
+        .iter()
+        .filter(|l| !base_set.contains(l.as_str()) && seen.insert(l.as_str()))
+        .cloned()
+        .collect();
+    let mut seen = HashSet::new();
+    let removed = base_lines
+        .iter()
+        .filter(|l| !generated_set.contains(l.as_str()) && seen.insert(l.as_str()))
+        .cloned()
+        .collect();
+    let unchanged = base_set.intersection(&generated_set).count();
+
+    CvDiffSummary { added, removed, unchanged }
+}
+
+fn format_diff_excerpt(diff: &CvDiffSummary) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in diff.removed.iter().take(MAX_DIFF_LINES / 2) {
+        lines.push(format!("- {}", truncate_chars(line, 80)));
+    }
+    for line in diff.added.iter().take(MAX_DIFF_LINES - lines.len()) {
+        lines.push(format!("+ {}", truncate_chars(line, 80)));
+    }
+    let body = truncate_chars(&lines.join("\n"), 1000).replace("```", "'''");
+    format!("```diff\n{}\n```", body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_cv_diff() {
+        let base = "Jean Dupont\nDéveloppeur Java\n\nCompétences: Java, SQL\n";
+        let generated = "Jean Dupont\nDéveloppeur Backend Rust\nCompétences:   Java, SQL\nRust, Tokio\n";
+        let diff = summarize_cv_diff(base, generated);
+        assert_eq!(diff.unchanged, 2);
+        assert_eq!(diff.removed, vec!["Développeur Java".to_string()]);
+        assert_eq!(
+            diff.added,
+            vec!["Développeur Backend Rust".to_string(), "Rust, Tokio".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_summarize_identical_cv() {
+        let diff = summarize_cv_diff("A\nB\n", "B\nA");
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.unchanged, 2);
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use serenity::all::{CommandInteraction, CommandType, Context, CreateCommand, EditInteractionResponse, Message};
+use tracing::info;
+
+use super::jobs::{ApplyJobRequest, run_apply_job_with_timeout};
+use super::{CommandError, SlashCommand, defer_response};
+
+/// Longueur minimale du texte d'un message pour lancer l'analyse
+const MIN_POSTING_LENGTH: usize = 50;
+
+// ============================================================================
+// AnalyzeMessage Command - Right-click → Apps → analyze a message as a job posting
+// ============================================================================
+
+pub struct AnalyzeMessageCommand;
+
+impl AnalyzeMessageCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AnalyzeMessageCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for AnalyzeMessageCommand {
+    fn name(&self) -> &'static str {
+        "Analyze job posting"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run /applyjob on the selected message"
+    }
+
+    fn register(&self) -> CreateCommand {
+        // Les commandes de menu contextuel n'ont pas de description côté Discord
+        CreateCommand::new(self.name()).kind(CommandType::Message)
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let message = interaction
+            .data
+            .target_id
+            .and_then(|target| interaction.data.resolved.messages.get(&target.to_message_id()))
+            .ok_or_else(|| CommandError::MissingParameter("message".to_string()))?;
+
+        let job_description = message_posting_text(message);
+        if job_description.chars().count() < MIN_POSTING_LENGTH {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content("❌ **Erreur**: Ce message est trop court pour être une offre d'emploi."),
+                )
+                .await
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            return Ok(());
+        }
+
+        info!(
+            "User {} analyzing message {} as a job posting",
+            interaction.user.id, message.id
+        );
+
+        let request = ApplyJobRequest {
+            job_url: first_url(&job_description),
+            ..ApplyJobRequest::new(job_description)
+        };
+        run_apply_job_with_timeout(ctx, interaction, request).await
+    }
+}
+
+/// Texte du message + contenu de ses embeds (les offres partagées par lien arrivent souvent en embed)
+fn message_posting_text(message: &Message) -> String {
+    let mut parts = vec![message.content.trim().to_string()];
+    for embed in &message.embeds {
+        parts.extend(embed.title.iter().cloned());
+        parts.extend(embed.description.iter().cloned());
+        for field in &embed.fields {
+            parts.push(format!("{}: {}", field.name, field.value));
+        }
+    }
+    parts.retain(|p| !p.trim().is_empty());
+    parts.join("\n\n")
+}
+
+/// Première URL http(s) du texte, utilisée comme lien de l'offre
+fn first_url(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_start_matches(['<', '(']))
+        .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+        .map(|url| url.trim_end_matches(['>', ')', ',', '.']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_url() {
+        assert_eq!(
+            first_url("Offre ici : <https://jobs.example.com/123>, postulez vite."),
+            Some("https://jobs.example.com/123".to_string())
+        );
+        assert_eq!(
+            first_url("(voir https://example.org/job.)"),
+            Some("https://example.org/job".to_string())
+        );
+        assert_eq!(first_url("pas de lien"), None);
+    }
+}
@@ -1,14 +1,32 @@
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serenity::all::{
-    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    ButtonStyle, Colour, CommandInteraction, CommandOptionType, ComponentInteraction, Context,
+    CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
     CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, error, warn};
 use uuid::Uuid;
 
-use super::{CommandError, SlashCommand, get_claude_client, get_database};
+use super::{CommandError, SlashCommand, attachment_option, defer_response, get_claude_client, get_database, private_option};
+use super::embeds::{field_value, list_field_value};
+use super::jobs::refresh_thread_name;
+use crate::db::{BaseCv, Database, JobApplication};
+use crate::services::anonymize::anonymize_if;
+use crate::services::{ClaudeClient, CvProfile, SkillsMatch, storage};
+
+const COLOR_PROFILE: Colour = Colour::from_rgb(52, 152, 219);
+/// Fenêtre (jours) des candidatures proposées au re-scoring après l'envoi d'un nouveau CV
+const RESCORE_WINDOW_DAYS: i64 = 30;
+/// Nombre maximum de candidatures re-scorées en un clic (un appel Claude chacune)
+const MAX_RESCORE_APPLICATIONS: i64 = 5;
+/// Dossier de stockage des CVs envoyés avec /sendcv
+const CV_STORAGE_DIR: &str = "data/cvs";
+/// Corbeille : fichiers des CVs supprimés avec /deletecv, restaurables avec /restorecv
+const CV_TRASH_DIR: &str = "data/cvs/trash";
+/// CVs listés par /trash
+const MAX_TRASH_DISPLAYED: i64 = 10;
 
 // ============================================================================
 // SendCV Command
@@ -38,6 +56,10 @@ impl SlashCommand for SendCvCommand {
         "Upload your CV to the bot"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -45,35 +67,21 @@ impl SlashCommand for SendCvCommand {
                 CreateCommandOption::new(CommandOptionType::Attachment, "cv", "Your CV file (PDF preferred)")
                     .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
         // Defer immédiatement pour éviter le timeout de 3s
-        interaction
-            .defer(&ctx.http)
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id;
         let username = &interaction.user.name;
 
-        // Récupérer l'attachment
-        let attachment_id = interaction
-            .data
-            .options
-            .iter()
-            .find(|opt| opt.name == "cv")
-            .and_then(|opt| opt.value.as_attachment_id())
+        // Récupérer l'attachment depuis les données résolues
+        let attachment = attachment_option(interaction, "cv")
+            .map_err(CommandError::InvalidInput)?
             .ok_or_else(|| CommandError::MissingParameter("cv".to_string()))?;
 
-        // Récupérer les infos de l'attachment depuis resolved
-        let attachment = interaction
-            .data
-            .resolved
-            .attachments
-            .get(&attachment_id)
-            .ok_or_else(|| CommandError::Internal("Attachment not found in resolved data".to_string()))?;
-
         // Vérifier le type de fichier
         let content_type = attachment.content_type.as_deref().unwrap_or("application/octet-stream");
         let allowed_types = ["application/pdf", "text/plain", "application/msword",
@@ -107,7 +115,7 @@ impl SlashCommand for SendCvCommand {
         };
 
         // Créer le dossier de stockage
-        let storage_dir = PathBuf::from("data/cvs");
+        let storage_dir = PathBuf::from(CV_STORAGE_DIR);
         if let Err(e) = tokio::fs::create_dir_all(&storage_dir).await {
             error!("Failed to create storage dir: {}", e);
             return Err(CommandError::Internal(format!("Storage error: {}", e)));
@@ -121,8 +129,8 @@ impl SlashCommand for SendCvCommand {
         let unique_filename = format!("{}_{}.{}", user_id, Uuid::new_v4(), extension);
         let file_path = storage_dir.join(&unique_filename);
 
-        // Sauvegarder le fichier
-        if let Err(e) = tokio::fs::write(&file_path, &file_bytes).await {
+        // Sauvegarder le fichier (temporaire + renommage : jamais de CV partiel)
+        if let Err(e) = storage::write_atomic(&file_path, &file_bytes).await {
             error!("Failed to write CV file: {}", e);
             return Err(CommandError::Internal(format!("File write error: {}", e)));
         }
@@ -163,46 +171,21 @@ impl SlashCommand for SendCvCommand {
 
         // Extraire le texte du CV
         let is_pdf = extension.to_lowercase() == "pdf";
-        let extracted_text = if is_pdf {
-            // Utiliser l'endpoint d'extraction PDF du serveur
-            let base64_content = BASE64.encode(&file_bytes);
-
-            match claude_client.extract_pdf(&base64_content).await {
-                Ok(text) => {
-                    if text.is_empty() {
-                        warn!("PDF extraction returned empty text");
-                        None
-                    } else {
-                        info!("Successfully extracted {} chars from PDF", text.len());
-                        Some(text)
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to extract PDF text: {}", e);
-                    None
-                }
-            }
-        } else {
-            // Pour les fichiers texte, lire directement
-            String::from_utf8(file_bytes.clone()).ok()
-        };
+        let extracted_text = extract_cv_text(&claude_client, &file_bytes, is_pdf).await;
 
-        // Sauvegarder le texte extrait
-        if let Some(ref text) = extracted_text {
-            if let Err(e) = db.update_cv_extracted_data(cv_id, text, "{}").await {
-                warn!("Failed to save extracted text: {}", e);
-            } else {
-                info!("Extracted text saved for CV {}", cv_id);
-            }
-        }
+        // Sauvegarder le texte extrait et le profil structuré
+        let profile = match extracted_text {
+            Some(ref text) => store_cv_profile(&claude_client, &db, cv_id, text).await,
+            None => None,
+        };
 
-        let extraction_status = if extracted_text.is_some() {
-            "✅ Texte extrait avec succès"
-        } else {
-            "⚠️ Extraction du texte non disponible"
+        let extraction_status = match (&extracted_text, &profile) {
+            (Some(_), Some(_)) => "✅ Texte et profil extraits avec succès (`/cvprofile`)",
+            (Some(_), None) => "✅ Texte extrait avec succès",
+            (None, _) => "⚠️ Extraction du texte non disponible",
         };
 
-        let response = format!(
+        let mut response = format!(
             "✅ **CV enregistré avec succès!**\n\n\
             👤 Utilisateur: <@{}>\n\
             📄 Fichier: `{}`\n\
@@ -217,8 +200,30 @@ impl SlashCommand for SendCvCommand {
             extraction_status
         );
 
+        // Proposer de re-scorer les candidatures récentes avec le nouveau CV
+        let mut components = vec![];
+        if extracted_text.is_some() {
+            match db.list_rescorable_applications(user_id.get() as i64, RESCORE_WINDOW_DAYS, MAX_RESCORE_APPLICATIONS).await {
+                Ok(apps) if !apps.is_empty() => {
+                    response.push_str(&format!(
+                        "\n\n🔁 **{} candidature(s) en cours** (moins de {} jours) pourraient bénéficier de ce nouveau CV.",
+                        apps.len(),
+                        RESCORE_WINDOW_DAYS
+                    ));
+                    components.push(CreateActionRow::Buttons(vec![
+                        CreateButton::new(format!("cvrescore_{}", cv_id))
+                            .label("Re-scorer avec ce CV")
+                            .emoji('🔁')
+                            .style(ButtonStyle::Primary),
+                    ]));
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to list applications to rescore: {}", e),
+            }
+        }
+
         interaction
-            .edit_response(&ctx.http, EditInteractionResponse::new().content(response))
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(response).components(components))
             .await
             .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
 
@@ -251,11 +256,17 @@ impl SlashCommand for DeleteCvCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Delete your CV from the bot"
+        "Move your active CV to the trash (restore it with /restorecv)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
     }
 
     fn register(&self) -> CreateCommand {
-        CreateCommand::new(self.name()).description(self.description())
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
@@ -269,34 +280,210 @@ impl SlashCommand for DeleteCvCommand {
 
         match cv {
             Some(cv) => {
-                // Supprimer de la DB d'abord (évite orphan en cas d'erreur fichier)
-                db.delete_active_cv(user_id.get() as i64).await
-                    .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
-
-                // Supprimer le fichier physique ensuite
-                let file_path = PathBuf::from(&cv.file_path);
-                if file_path.exists() {
-                    if let Err(e) = std::fs::remove_file(&file_path) {
-                        warn!("Failed to delete CV file (DB entry already removed): {}", e);
-                    } else {
-                        info!("Deleted CV file: {:?}", file_path);
+                // Déplacer le fichier dans la corbeille ; s'il est introuvable, le CV
+                // reste restaurable (texte extrait conservé en base)
+                let trash_path = PathBuf::from(CV_TRASH_DIR).join(&cv.filename);
+                let moved = move_cv_file(&cv.file_path, &trash_path).await;
+                let db_path = if moved { trash_path.to_string_lossy().to_string() } else { cv.file_path.clone() };
+
+                if let Err(e) = db.soft_delete_cv(user_id.get() as i64, cv.id, &db_path).await {
+                    if moved {
+                        move_cv_file(&db_path, &PathBuf::from(&cv.file_path)).await;
                     }
+                    return Err(CommandError::Internal(format!("Database error: {}", e)));
                 }
+                info!("CV {} moved to trash for user {}", cv.id, user_id);
 
                 let response = format!(
-                    "🗑️ **CV supprimé!**\n\n📄 Fichier: `{}`",
-                    cv.original_name
+                    "🗑️ **CV placé dans la corbeille!**\n\n📄 Fichier: `{}`\n\n\
+                     _Utilisez `/restorecv cv_id:{}` pour le restaurer, `/trash` pour voir la corbeille._",
+                    cv.original_name, cv.id
                 );
-                send_response(ctx, interaction, &response).await
+                send_response(ctx, interaction, self.is_ephemeral(interaction), &response).await
             }
             None => {
                 let response = "❌ Aucun CV actif trouvé.\n\nUtilisez `/sendcv` pour envoyer un CV.";
-                send_response(ctx, interaction, response).await
+                send_response(ctx, interaction, self.is_ephemeral(interaction), response).await
             }
         }
     }
 }
 
+// ============================================================================
+// Trash Command - CVs supprimés avec /deletecv
+// ============================================================================
+
+pub struct TrashCommand;
+
+impl TrashCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TrashCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for TrashCommand {
+    fn name(&self) -> &'static str {
+        "trash"
+    }
+
+    fn description(&self) -> &'static str {
+        "List your recently deleted CVs"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        let user_id = interaction.user.id;
+
+        let db = get_database(ctx).await?;
+        let cvs = db.list_deleted_cvs(user_id.get() as i64, MAX_TRASH_DISPLAYED).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        if cvs.is_empty() {
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "🗑️ **Corbeille**\n\n_Aucun CV supprimé._").await;
+        }
+
+        let mut response = format!("🗑️ **Corbeille** ({} CV(s))\n\n", cvs.len());
+        for cv in cvs {
+            let deleted_at = cv.deleted_at.as_deref().unwrap_or("");
+            response.push_str(&format!(
+                "📄 **{}**\n  └ ID: `{}` | {} Ko | supprimé le {}\n\n",
+                cv.original_name,
+                cv.id,
+                cv.file_size / 1024,
+                deleted_at.split([' ', 'T']).next().unwrap_or(deleted_at)
+            ));
+        }
+        response.push_str("_Utilisez `/restorecv cv_id:<ID>` pour restaurer un CV._");
+
+        send_response(ctx, interaction, self.is_ephemeral(interaction), &response).await
+    }
+}
+
+// ============================================================================
+// RestoreCv Command - Sort un CV de la corbeille
+// ============================================================================
+
+pub struct RestoreCvCommand;
+
+impl RestoreCvCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RestoreCvCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for RestoreCvCommand {
+    fn name(&self) -> &'static str {
+        "restorecv"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore a deleted CV from the trash and make it active"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "cv_id", "CV ID (from /trash)")
+                    .required(true)
+                    .min_int_value(1),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        let user_id = interaction.user.id.get() as i64;
+        let cv_id = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "cv_id")
+            .and_then(|opt| opt.value.as_i64())
+            .ok_or_else(|| CommandError::MissingParameter("cv_id".to_string()))?;
+
+        // Ré-extraction éventuelle via Claude : réponse différée
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let db = get_database(ctx).await?;
+        let cv = db.get_cv_by_id(cv_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .filter(|cv| cv.user_id == user_id && cv.deleted_at.is_some())
+            .ok_or_else(|| CommandError::NotFound(format!("CV #{} introuvable dans la corbeille", cv_id)))?;
+
+        let restored_path = PathBuf::from(CV_STORAGE_DIR).join(&cv.filename);
+        let moved = move_cv_file(&cv.file_path, &restored_path).await;
+        let db_path = if moved { restored_path.to_string_lossy().to_string() } else { cv.file_path.clone() };
+
+        if !db.restore_cv(user_id, cv_id, &db_path).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        {
+            return Err(CommandError::NotFound(format!("CV #{} introuvable dans la corbeille", cv_id)));
+        }
+        info!("CV {} restored from trash for user {}", cv_id, user_id);
+
+        // Texte jamais extrait (extraction échouée à l'envoi) : nouvelle tentative
+        let has_text = cv.extracted_text.as_deref().is_some_and(|t| !t.trim().is_empty());
+        let extraction_status = if has_text {
+            "✅ Texte extrait conservé"
+        } else {
+            match tokio::fs::read(&db_path).await {
+                Ok(bytes) => {
+                    let claude_client = get_claude_client(ctx).await?;
+                    let is_pdf = cv.filename.to_lowercase().ends_with(".pdf");
+                    match extract_cv_text(&claude_client, &bytes, is_pdf).await {
+                        Some(text) => {
+                            store_cv_profile(&claude_client, &db, cv_id, &text).await;
+                            "✅ Texte ré-extrait avec succès"
+                        }
+                        None => "⚠️ Extraction du texte non disponible",
+                    }
+                }
+                Err(e) => {
+                    warn!("Restored CV {} file not readable: {}", cv_id, e);
+                    "⚠️ Fichier introuvable : réuploadez votre CV avec `/sendcv`"
+                }
+            }
+        };
+
+        let response = format!(
+            "♻️ **CV restauré et activé!**\n\n📄 Fichier: `{}`\n🆔 ID: `{}`\n📝 {}",
+            cv.original_name, cv_id, extraction_status
+        );
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
 // ============================================================================
 // ListMyCvs Command
 // ============================================================================
@@ -325,8 +512,14 @@ impl SlashCommand for ListMyCvsCommand {
         "List your stored CVs"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
-        CreateCommand::new(self.name()).description(self.description())
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
@@ -340,7 +533,7 @@ impl SlashCommand for ListMyCvsCommand {
 
         if cvs.is_empty() {
             let response = "📋 **Vos CVs**\n\n_Aucun CV enregistré._\n\nUtilisez `/sendcv` pour envoyer un CV.";
-            send_response(ctx, interaction, response).await
+            send_response(ctx, interaction, self.is_ephemeral(interaction), response).await
         } else {
             let mut response = format!("📋 **Vos CVs** ({} total)\n\n", cvs.len());
 
@@ -357,11 +550,491 @@ impl SlashCommand for ListMyCvsCommand {
                 ));
             }
 
-            send_response(ctx, interaction, &response).await
+            send_response(ctx, interaction, self.is_ephemeral(interaction), &response).await
         }
     }
 }
 
+// ============================================================================
+// LinkCv Command - Associe le CV actif à une candidature analysée sans CV
+// ============================================================================
+
+pub struct LinkCvCommand;
+
+impl LinkCvCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LinkCvCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for LinkCvCommand {
+    fn name(&self) -> &'static str {
+        "linkcv"
+    }
+
+    fn description(&self) -> &'static str {
+        "Link your active CV to an existing application"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "application_id", "Application ID (from /status)")
+                    .required(true)
+                    .min_int_value(1),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        let user_id = interaction.user.id.get() as i64;
+        let application_id = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "application_id")
+            .and_then(|opt| opt.value.as_i64())
+            .ok_or_else(|| CommandError::MissingParameter("application_id".to_string()))?;
+
+        let db = get_database(ctx).await?;
+
+        let app = db.get_application(application_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .filter(|app| app.user_id == user_id)
+            .ok_or_else(|| CommandError::NotFound(format!("Candidature #{} introuvable", application_id)))?;
+
+        let Some(cv) = db.get_active_cv(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        else {
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ Aucun CV actif trouvé.\n\nUtilisez `/sendcv` pour envoyer un CV.").await;
+        };
+
+        if app.base_cv_id == Some(cv.id) {
+            return send_response(
+                ctx,
+                interaction,
+                self.is_ephemeral(interaction),
+                &format!("ℹ️ Le CV `{}` est déjà associé à la candidature #{}.", cv.original_name, application_id),
+            ).await;
+        }
+
+        db.update_application_cv(application_id, user_id, cv.id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        info!("Linked CV {} to application {} (user {})", cv.id, application_id, user_id);
+
+        let rematch_btn = CreateButton::new(format!("linkcv_rematch_{}", application_id))
+            .label("🔄 Relancer le matching")
+            .style(ButtonStyle::Primary);
+        let msg = CreateInteractionResponseMessage::new()
+            .ephemeral(self.is_ephemeral(interaction))
+            .content(format!(
+                "🔗 **CV associé!**\n\n📄 `{}` → candidature **#{}** ({} chez {})\n\n\
+                 Relancez le matching des compétences pour mettre à jour le score avec ce CV.",
+                cv.original_name,
+                application_id,
+                app.job_title.as_deref().unwrap_or("N/A"),
+                app.company.as_deref().unwrap_or("N/A"),
+            ))
+            .components(vec![CreateActionRow::Buttons(vec![rematch_btn])]);
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+    }
+}
+
+/// Bouton `linkcv_rematch_{id}` : relance le matching des compétences avec le CV associé
+pub async fn handle_linkcv_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let application_id: i64 = component
+        .data
+        .custom_id
+        .strip_prefix("linkcv_rematch_")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| CommandError::InvalidInput("Invalid application ID".to_string()))?;
+    let user_id = component.user.id.get() as i64;
+
+    let db = get_database(ctx).await?;
+    let app = db.get_application(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| CommandError::NotFound(format!("Candidature #{} introuvable", application_id)))?;
+    if app.user_id != user_id {
+        return Err(CommandError::Unauthorized("Cette candidature ne vous appartient pas".to_string()));
+    }
+
+    let cv = match app.base_cv_id {
+        Some(cv_id) => db.get_cv_by_id(cv_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?,
+        None => None,
+    };
+    let Some(cv) = cv.filter(|cv| cv.extracted_text.as_deref().is_some_and(|t| !t.trim().is_empty())) else {
+        return Err(CommandError::InvalidInput(
+            "Texte du CV non disponible - réuploadez votre CV avec /sendcv".to_string(),
+        ));
+    };
+
+    // Appel Claude long : acquitter le clic puis éditer le message
+    component.defer(&ctx.http).await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    let claude_client = get_claude_client(ctx).await?;
+    let content = match rematch_application(&db, &claude_client, &app, &cv, false).await {
+        Ok(skills) => {
+            let old_score = app.match_score.map(|s| format!("{}%", s)).unwrap_or_else(|| "N/A".to_string());
+            info!("Re-ran skills matching for application {}: {}%", application_id, skills.match_score);
+            refresh_thread_name(ctx, &JobApplication { match_score: Some(skills.match_score as i32), ..app.clone() }).await;
+            format!(
+                "🎯 **Matching mis à jour** pour la candidature #{}\n\n\
+                 Score: {} → **{}%**\n✅ Compétences correspondantes: {}\n⚠️ Compétences manquantes: {}",
+                application_id,
+                old_score,
+                skills.match_score,
+                skills.matched_skills.len(),
+                skills.missing_skills.len(),
+            )
+        }
+        Err(e) => {
+            error!("Rematch failed for application {}: {}", application_id, e);
+            format!("❌ **Erreur** lors du matching: {}", e)
+        }
+    };
+
+    component
+        .edit_response(&ctx.http, EditInteractionResponse::new().content(content).components(vec![]))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    Ok(())
+}
+
+/// Bouton `cvrescore_{cv_id}` de /sendcv : associe le nouveau CV aux candidatures récentes
+/// encore ouvertes et relance leur matching des compétences
+pub async fn handle_cv_rescore_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let cv_id: i64 = component
+        .data
+        .custom_id
+        .strip_prefix("cvrescore_")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| CommandError::InvalidInput("Invalid CV ID".to_string()))?;
+    let user_id = component.user.id.get() as i64;
+
+    let db = get_database(ctx).await?;
+    let cv = db.get_cv_by_id(cv_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .filter(|cv| cv.deleted_at.is_none())
+        .ok_or_else(|| CommandError::NotFound(format!("CV #{} introuvable", cv_id)))?;
+    if cv.user_id != user_id {
+        return Err(CommandError::Unauthorized("Ce CV ne vous appartient pas".to_string()));
+    }
+    if cv.extracted_text.as_deref().is_none_or(|t| t.trim().is_empty()) {
+        return Err(CommandError::InvalidInput(
+            "Texte du CV non disponible - réuploadez votre CV avec /sendcv".to_string(),
+        ));
+    }
+
+    // Un appel Claude par candidature : acquitter le clic puis éditer le message
+    component.defer(&ctx.http).await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    let apps = db.list_rescorable_applications(user_id, RESCORE_WINDOW_DAYS, MAX_RESCORE_APPLICATIONS).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+    let claude_client = get_claude_client(ctx).await?;
+    let mut lines = Vec::new();
+    for app in &apps {
+        let label = format!(
+            "#{} {} — {}",
+            app.id,
+            app.company.as_deref().unwrap_or("N/A"),
+            app.job_title.as_deref().unwrap_or("N/A")
+        );
+        match rematch_application(&db, &claude_client, app, &cv, false).await {
+            Ok(skills) => {
+                let old_score = app.match_score.map(|s| format!("{}%", s)).unwrap_or_else(|| "N/A".to_string());
+                refresh_thread_name(ctx, &JobApplication { match_score: Some(skills.match_score as i32), ..app.clone() }).await;
+                lines.push(format!("• {} : {} → **{}%**", label, old_score, skills.match_score));
+            }
+            Err(e) => {
+                error!("Rescore failed for application {}: {}", app.id, e);
+                lines.push(format!("• {} : ❌ {}", label, e));
+            }
+        }
+    }
+    info!("Rescored {} application(s) with CV {} for user {}", apps.len(), cv_id, user_id);
+
+    let summary = if lines.is_empty() {
+        "Aucune candidature en cours à re-scorer.".to_string()
+    } else {
+        format!("🔁 **Candidatures re-scorées avec le nouveau CV**\n{}", lines.join("\n"))
+    };
+    let content = format!("{}\n\n{}", component.message.content, summary);
+    component
+        .edit_response(&ctx.http, EditInteractionResponse::new().content(content).components(vec![]))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    Ok(())
+}
+
+// ============================================================================
+// CvProfile Command - Profil structuré extrait du CV actif
+// ============================================================================
+
+pub struct CvProfileCommand;
+
+impl CvProfileCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CvProfileCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for CvProfileCommand {
+    fn name(&self) -> &'static str {
+        "cvprofile"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the structured profile extracted from your active CV"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let db = get_database(ctx).await?;
+
+        let cv = db.get_active_cv(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let Some(cv) = cv else {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content("❌ Aucun CV actif trouvé.\n\nUtilisez `/sendcv` pour envoyer un CV."),
+                )
+                .await
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            return Ok(());
+        };
+
+        // CVs envoyés avant l'extraction du profil : l'extraire maintenant à partir du texte
+        let mut profile = cv.profile();
+        if profile.is_none() {
+            if let Some(text) = cv.extracted_text.as_deref().filter(|t| !t.trim().is_empty()) {
+                let claude_client = get_claude_client(ctx).await?;
+                profile = store_cv_profile(&claude_client, &db, cv.id, text).await;
+            }
+        }
+
+        let response = match profile {
+            Some(profile) => EditInteractionResponse::new().embed(build_profile_embed(&profile, &cv.original_name)),
+            None => EditInteractionResponse::new().content(
+                "⚠️ **Profil non disponible** pour ce CV (texte non extrait ou analyse impossible).\n\n\
+                 Réessayez plus tard ou renvoyez votre CV avec `/sendcv`.",
+            ),
+        };
+        interaction
+            .edit_response(&ctx.http, response)
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Texte d'un fichier CV : extraction PDF par le serveur, lecture directe sinon
+async fn extract_cv_text(claude_client: &ClaudeClient, file_bytes: &[u8], is_pdf: bool) -> Option<String> {
+    if !is_pdf {
+        // Pour les fichiers texte, lire directement
+        return String::from_utf8(file_bytes.to_vec()).ok();
+    }
+
+    // Utiliser l'endpoint d'extraction PDF du serveur
+    let base64_content = BASE64.encode(file_bytes);
+    match claude_client.extract_pdf(&base64_content).await {
+        Ok(text) => {
+            if text.is_empty() {
+                warn!("PDF extraction returned empty text");
+                None
+            } else {
+                info!("Successfully extracted {} chars from PDF", text.len());
+                Some(text)
+            }
+        }
+        Err(e) => {
+            warn!("Failed to extract PDF text: {}", e);
+            None
+        }
+    }
+}
+
+/// Relance le matching des compétences d'une candidature avec `cv` (anonymisé si l'option
+/// `anonymize` est enregistrée) et enregistre le nouveau score ; la synthèse et les
+/// compétences requises sont conservées. Avec `backfill` (/backfill), seul un matching
+/// manquant est complété, sans compter comme une activité de l'utilisateur ; sinon le CV
+/// est associé à la candidature.
+pub(crate) async fn rematch_application(
+    db: &Database,
+    claude_client: &ClaudeClient,
+    app: &JobApplication,
+    cv: &BaseCv,
+    backfill: bool,
+) -> Result<SkillsMatch, String> {
+    let cv_text = cv.extracted_text.as_deref()
+        .filter(|t| !t.trim().is_empty())
+        .ok_or("CV text not available")?;
+    let profile_name = cv.profile().and_then(|p| p.name);
+    let cv_text = anonymize_if(app.anonymize_cv, cv_text, profile_name.as_deref()).text;
+
+    let skills = claude_client
+        .match_skills(&app.raw_job_description, &cv_text, app.notes.as_deref(), &cv.declared_skills(), app.language.as_deref(), app.seniority.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let matched = serde_json::to_string(&skills.matched_skills).unwrap_or_default();
+    let missing = serde_json::to_string(&skills.missing_skills).unwrap_or_default();
+
+    let db_error = |e: rusqlite::Error| format!("Database error: {}", e);
+    if backfill {
+        db.backfill_application_skills(app.id, cv.id, &matched, &missing, skills.match_score as i32).await
+            .map_err(db_error)?;
+    } else {
+        db.update_application_cv(app.id, app.user_id, cv.id).await
+            .map_err(db_error)?;
+        db.update_application_analysis(
+            app.id,
+            app.job_synthesis.as_deref().unwrap_or_default(),
+            app.required_skills.as_deref().unwrap_or("[]"),
+            &matched,
+            &missing,
+            skills.match_score as i32,
+        ).await
+        .map_err(db_error)?;
+    }
+    Ok(skills)
+}
+
+/// Nouvelle tentative d'extraction du texte d'un CV à partir de son fichier ; le texte
+/// et le profil sont enregistrés en cas de succès
+pub(crate) async fn reextract_cv_text(claude_client: &ClaudeClient, db: &Database, cv: &BaseCv) -> Option<String> {
+    let bytes = match tokio::fs::read(&cv.file_path).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("CV {} file not readable: {}", cv.id, e);
+            return None;
+        }
+    };
+    let is_pdf = cv.filename.to_lowercase().ends_with(".pdf");
+    let text = extract_cv_text(claude_client, &bytes, is_pdf).await?
+        .trim()
+        .to_string();
+    if text.is_empty() {
+        return None;
+    }
+    store_cv_profile(claude_client, db, cv.id, &text).await;
+    Some(text)
+}
+
+/// Déplace un fichier CV (corbeille <-> stockage) ; false si le fichier source est introuvable
+async fn move_cv_file(from: &str, to: &Path) -> bool {
+    if let Some(dir) = to.parent() {
+        if let Err(e) = tokio::fs::create_dir_all(dir).await {
+            warn!("Failed to create directory {:?}: {}", dir, e);
+            return false;
+        }
+    }
+    match tokio::fs::rename(from, to).await {
+        Ok(()) => {
+            info!("Moved CV file {} -> {:?}", from, to);
+            true
+        }
+        Err(e) => {
+            warn!("Failed to move CV file {} -> {:?}: {}", from, to, e);
+            false
+        }
+    }
+}
+
+/// Extrait le profil du texte du CV via Claude et l'enregistre avec le texte.
+/// En cas d'échec, le texte est tout de même sauvegardé (parsed_data = "{}").
+async fn store_cv_profile(
+    claude_client: &ClaudeClient,
+    db: &Database,
+    cv_id: i64,
+    text: &str,
+) -> Option<CvProfile> {
+    let profile = match claude_client.parse_cv_profile(text).await {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            warn!("Failed to parse CV profile for CV {}: {}", cv_id, e);
+            None
+        }
+    };
+    let parsed_data = profile
+        .as_ref()
+        .and_then(|p| serde_json::to_string(p).ok())
+        .unwrap_or_else(|| "{}".to_string());
+
+    if let Err(e) = db.update_cv_extracted_data(cv_id, text, &parsed_data).await {
+        warn!("Failed to save extracted text: {}", e);
+    } else {
+        info!("Extracted text saved for CV {}", cv_id);
+    }
+    profile
+}
+
+fn build_profile_embed(profile: &CvProfile, file_name: &str) -> CreateEmbed {
+    let list = |items: &[String], max: usize| -> String {
+        if items.is_empty() {
+            return "Non renseigné".to_string();
+        }
+        list_field_value(items.to_vec(), ", ", max)
+    };
+    let experience = profile
+        .experience_years
+        .map(|y| format!("{} an(s)", y))
+        .unwrap_or_else(|| "Non renseigné".to_string());
+
+    CreateEmbed::new()
+        .title("👤 PROFIL DU CV")
+        .colour(COLOR_PROFILE)
+        .field("Nom", field_value(profile.name.as_deref().unwrap_or("Non renseigné")), true)
+        .field("Email", field_value(profile.email.as_deref().unwrap_or("Non renseigné")), true)
+        .field("Expérience", experience, true)
+        .field(format!("Compétences ({})", profile.skills.len()), list(&profile.skills, 25), false)
+        .field("Formation", list(&profile.education, 5), false)
+        .footer(serenity::all::CreateEmbedFooter::new(format!("Extrait de {}", file_name)))
+}
+
 // ============================================================================
 // Helper
 // ============================================================================
@@ -369,9 +1042,10 @@ impl SlashCommand for ListMyCvsCommand {
 async fn send_response(
     ctx: &Context,
     interaction: &CommandInteraction,
+    ephemeral: bool,
     content: &str,
 ) -> Result<(), CommandError> {
-    let msg = CreateInteractionResponseMessage::new().content(content);
+    let msg = CreateInteractionResponseMessage::new().content(content).ephemeral(ephemeral);
     
     interaction
         .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
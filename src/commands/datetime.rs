@@ -0,0 +1,347 @@
+// Parsing des dates et heures saisies par les utilisateurs (rappels, entretiens...)
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use super::CommandError;
+
+/// Heure par défaut des rappels quand aucune heure n'est précisée
+pub const DEFAULT_REMINDER_HOUR: u32 = 9;
+
+// ============================================================================
+// Helper: parse time string (HH:MM) into (hour, minute)
+// ============================================================================
+
+pub fn parse_time_option(time_str: Option<String>) -> Result<(u32, u32), CommandError> {
+    let t = time_str.unwrap_or_else(|| "09:00".to_string());
+    let parts: Vec<&str> = t.splitn(2, ':').collect();
+    if parts.len() != 2 {
+        return Err(CommandError::InvalidInput("Invalid time format. Use HH:MM (e.g. 14:30)".to_string()));
+    }
+    let hour: u32 = parts[0].parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid hour in time".to_string()))?;
+    let minute: u32 = parts[1].parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid minute in time".to_string()))?;
+    if hour > 23 || minute > 59 {
+        return Err(CommandError::InvalidInput("Time out of range (HH: 0-23, MM: 0-59)".to_string()));
+    }
+    Ok((hour, minute))
+}
+
+// ============================================================================
+// Helper: parse date string (YYYY-MM-DD or YYYY/MM/DD) with targeted errors
+// ============================================================================
+
+pub fn parse_date_option(date_str: &str) -> Result<NaiveDate, CommandError> {
+    let trimmed = date_str.trim();
+    let parts: Vec<&str> = trimmed.split(['-', '/', '.']).collect();
+
+    let all_numeric = parts.len() == 3
+        && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+    if !all_numeric {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid date `{}`. Use YYYY-MM-DD (e.g. 2025-03-15)",
+            trimmed
+        )));
+    }
+
+    // DD/MM/YYYY (format français courant) : proposer la version corrigée
+    if parts[0].len() <= 2 && parts[2].len() == 4 {
+        return Err(CommandError::InvalidInput(format!(
+            "Date `{}` looks like DD/MM/YYYY. Use YYYY-MM-DD instead: `{}-{:0>2}-{:0>2}`",
+            trimmed, parts[2], parts[1], parts[0]
+        )));
+    }
+
+    if parts[0].len() != 4 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid year in `{}`. Use YYYY-MM-DD (e.g. 2025-03-15)",
+            trimmed
+        )));
+    }
+
+    let year: i32 = parts[0].parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid year in date".to_string()))?;
+    let month: u32 = parts[1].parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid month in date".to_string()))?;
+    let day: u32 = parts[2].parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid day in date".to_string()))?;
+
+    if !(1..=12).contains(&month) {
+        // YYYY-DD-MM : jour et mois inversés
+        if (1..=12).contains(&day) {
+            return Err(CommandError::InvalidInput(format!(
+                "Month {} is out of range (01-12). Did you swap day and month? Try `{}-{:02}-{:02}`",
+                month, year, day, month
+            )));
+        }
+        return Err(CommandError::InvalidInput(format!(
+            "Month {} is out of range (01-12)",
+            month
+        )));
+    }
+
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| {
+        CommandError::InvalidInput(format!(
+            "Day {} does not exist in {}-{:02}",
+            day, year, month
+        ))
+    })
+}
+
+// ============================================================================
+// Helper: reminder date from `days` / `date` options
+// ============================================================================
+
+/// Date d'un rappel (UTC) : `date_str` (YYYY-MM-DD) prioritaire sur `days`, sinon dans `days` jours
+/// à partir de `now` ; dans les deux cas à l'heure `hour:minute`
+pub fn compute_reminder_datetime(
+    days: i64,
+    date_str: Option<&str>,
+    hour: u32,
+    minute: u32,
+    now: NaiveDateTime,
+) -> Result<NaiveDateTime, CommandError> {
+    let date = match date_str {
+        Some(date) => parse_date_option(date)?,
+        None => (now + Duration::days(days)).date(),
+    };
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| CommandError::InvalidInput("Invalid time".to_string()))?;
+    Ok(date.and_time(time))
+}
+
+// ============================================================================
+// Helper: parse natural-language time ("in 2 days", "demain 14:00", ...)
+// ============================================================================
+
+/// Convertit une expression temporelle libre en date absolue (UTC).
+///
+/// Formes acceptées (anglais et français) :
+/// - `in 2 days`, `in 3 hours`, `in 30 min`, `in a week` / `dans 2 jours`, `dans une semaine`
+///   (en jours ou semaines, optionnellement suivis de `at HH:MM` / `à HH:MM`)
+/// - `tomorrow`, `demain`, `today`, `aujourd'hui` (optionnellement suivis de `at HH:MM` / `à HH:MM`)
+/// - un jour de la semaine (`monday`, `lundi`...) : prochaine occurrence
+/// - une date explicite `YYYY-MM-DD [HH:MM]`
+pub fn parse_natural_datetime(input: &str, now: NaiveDateTime) -> Result<NaiveDateTime, CommandError> {
+    let normalized = input.trim().to_lowercase();
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    // « on monday », « le 2025-04-01 »
+    let tokens = match tokens.as_slice() {
+        ["on" | "le", rest @ ..] => rest,
+        all => all,
+    };
+
+    let Some((first, rest)) = tokens.split_first() else {
+        return Err(CommandError::InvalidInput("Empty time expression".to_string()));
+    };
+
+    let result = match *first {
+        "in" | "dans" => parse_relative(rest, now)?,
+        "tomorrow" | "demain" => at_time(now.date() + Duration::days(1), time_token(rest)?)?,
+        "today" | "aujourd'hui" | "aujourdhui" => at_time(now.date(), time_token(rest)?)?,
+        first => {
+            if let Some(weekday) = parse_weekday(first) {
+                let mut date = now.date() + Duration::days(1);
+                while date.weekday() != weekday {
+                    date += Duration::days(1);
+                }
+                at_time(date, time_token(rest)?)?
+            } else if first.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                at_time(parse_date_option(first)?, time_token(rest)?)?
+            } else {
+                return Err(CommandError::InvalidInput(format!(
+                    "Could not understand `{}`. Try `in 2 days`, `tomorrow 14:00` or `2025-03-15 09:00`",
+                    input.trim()
+                )));
+            }
+        }
+    };
+
+    if result <= now {
+        return Err(CommandError::InvalidInput(format!(
+            "`{}` is in the past",
+            input.trim()
+        )));
+    }
+
+    Ok(result)
+}
+
+fn parse_relative(tokens: &[&str], now: NaiveDateTime) -> Result<NaiveDateTime, CommandError> {
+    // Accepte "2 days", "a week" / "une semaine" ainsi que la forme collée "2d" / "2j"
+    let (amount_str, unit, rest) = match tokens {
+        [amount, unit, rest @ ..] if is_amount(amount) => (amount.to_string(), unit.to_string(), rest),
+        [compact, rest @ ..] => {
+            let split = compact.find(|c: char| !c.is_ascii_digit()).unwrap_or(compact.len());
+            (compact[..split].to_string(), compact[split..].to_string(), rest)
+        }
+        [] => return Err(CommandError::InvalidInput("Missing duration after `in`".to_string())),
+    };
+
+    let amount: i64 = match amount_str.as_str() {
+        "a" | "an" | "un" | "une" => 1,
+        digits => digits
+            .parse()
+            .map_err(|_| CommandError::InvalidInput(format!("Invalid amount `{}`", amount_str)))?,
+    };
+    if amount <= 0 || amount > 10_000 {
+        return Err(CommandError::InvalidInput("Duration must be between 1 and 10000".to_string()));
+    }
+
+    let (duration, whole_days) = match unit.as_str() {
+        "m" | "min" | "mins" | "minute" | "minutes" => (Duration::minutes(amount), false),
+        "h" | "hour" | "hours" | "heure" | "heures" => (Duration::hours(amount), false),
+        "d" | "j" | "day" | "days" | "jour" | "jours" => (Duration::days(amount), true),
+        "w" | "week" | "weeks" | "semaine" | "semaines" => (Duration::weeks(amount), true),
+        other => {
+            return Err(CommandError::InvalidInput(format!(
+                "Unknown time unit `{}` (use minutes, hours, days or weeks)",
+                other
+            )))
+        }
+    };
+
+    // « in 2 days at 10:00 » : l'heure ne s'applique qu'aux durées en jours ou semaines
+    match time_token(rest)? {
+        None => Ok(now + duration),
+        Some(time) if whole_days => at_time((now + duration).date(), Some(time)),
+        Some(time) => Err(CommandError::InvalidInput(format!(
+            "Cannot set a time (`{}`) on a duration in minutes or hours",
+            time
+        ))),
+    }
+}
+
+fn is_amount(token: &str) -> bool {
+    matches!(token, "a" | "an" | "un" | "une") || token.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Heure qui suit une date (`at 10:00`, `à 14h`, `10:00`) ; tout autre mot est refusé
+fn time_token<'a>(tokens: &[&'a str]) -> Result<Option<&'a str>, CommandError> {
+    let (connector, tokens) = match tokens {
+        [connector @ ("at" | "à" | "a"), rest @ ..] => (Some(*connector), rest),
+        all => (None, all),
+    };
+    match tokens {
+        [] => match connector {
+            Some(connector) => Err(CommandError::InvalidInput(format!("Missing time after `{}`", connector))),
+            None => Ok(None),
+        },
+        [time] => Ok(Some(*time)),
+        [_, extra, ..] => Err(CommandError::InvalidInput(format!("Unexpected `{}` after the time", extra))),
+    }
+}
+
+fn at_time(date: NaiveDate, time_token: Option<&str>) -> Result<NaiveDateTime, CommandError> {
+    let (hour, minute) = match time_token {
+        Some(t) => {
+            // Accepte aussi la notation française "14h30" / "14h"
+            let mut t = t.replace('h', ":");
+            if t.ends_with(':') {
+                t.push_str("00");
+            }
+            parse_time_option(Some(t))?
+        }
+        None => (DEFAULT_REMINDER_HOUR, 0),
+    };
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| CommandError::InvalidInput("Invalid time".to_string()))?;
+    Ok(date.and_time(time))
+}
+
+fn parse_weekday(token: &str) -> Option<Weekday> {
+    match token {
+        "monday" | "lundi" => Some(Weekday::Mon),
+        "tuesday" | "mardi" => Some(Weekday::Tue),
+        "wednesday" | "mercredi" => Some(Weekday::Wed),
+        "thursday" | "jeudi" => Some(Weekday::Thu),
+        "friday" | "vendredi" => Some(Weekday::Fri),
+        "saturday" | "samedi" => Some(Weekday::Sat),
+        "sunday" | "dimanche" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_compute_reminder_datetime_days_only() {
+        let now = at("2025-03-10 18:45");
+        assert_eq!(compute_reminder_datetime(7, None, 9, 0, now).unwrap(), at("2025-03-17 09:00"));
+        // Bornes de l'option `days` (1 à 90) et changements de mois, d'année et 29 février
+        assert_eq!(compute_reminder_datetime(1, None, 9, 0, now).unwrap(), at("2025-03-11 09:00"));
+        assert_eq!(compute_reminder_datetime(90, None, 9, 0, now).unwrap(), at("2025-06-08 09:00"));
+        assert_eq!(compute_reminder_datetime(1, None, 8, 30, at("2024-12-31 23:59")).unwrap(), at("2025-01-01 08:30"));
+        assert_eq!(compute_reminder_datetime(1, None, 9, 0, at("2024-02-28 10:00")).unwrap(), at("2024-02-29 09:00"));
+    }
+
+    #[test]
+    fn test_compute_reminder_datetime_date_only_and_precedence() {
+        let now = at("2025-03-10 18:45");
+        assert_eq!(compute_reminder_datetime(7, Some("2025-04-01"), 14, 30, now).unwrap(), at("2025-04-01 14:30"));
+        assert_eq!(compute_reminder_datetime(7, Some("2025/04/01"), 0, 0, now).unwrap(), at("2025-04-01 00:00"));
+        // `date` l'emporte sur `days` quand les deux sont fournis
+        assert_eq!(compute_reminder_datetime(90, Some(" 2025-03-12 "), 23, 59, now).unwrap(), at("2025-03-12 23:59"));
+    }
+
+    #[test]
+    fn test_compute_reminder_datetime_invalid_input() {
+        let now = at("2025-03-10 18:45");
+        for date in ["2025-02-30", "15/03/2025", "2025-13-01", "demain", ""] {
+            assert!(
+                matches!(compute_reminder_datetime(7, Some(date), 9, 0, now), Err(CommandError::InvalidInput(_))),
+                "{} should be rejected",
+                date
+            );
+        }
+        assert!(matches!(compute_reminder_datetime(7, None, 24, 0, now), Err(CommandError::InvalidInput(_))));
+        assert!(matches!(compute_reminder_datetime(7, None, 9, 60, now), Err(CommandError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_parse_time_option() {
+        assert_eq!(parse_time_option(None).unwrap(), (9, 0));
+        assert_eq!(parse_time_option(Some("23:59".to_string())).unwrap(), (23, 59));
+        assert!(parse_time_option(Some("24:00".to_string())).is_err());
+        assert!(parse_time_option(Some("9h".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_parse_natural_datetime() {
+        // Lundi 10 mars 2025
+        let now = at("2025-03-10 18:45");
+        assert_eq!(parse_natural_datetime("in 2 days", now).unwrap(), at("2025-03-12 18:45"));
+        assert_eq!(parse_natural_datetime("dans 3 heures", now).unwrap(), at("2025-03-10 21:45"));
+        assert_eq!(parse_natural_datetime("demain 14h30", now).unwrap(), at("2025-03-11 14:30"));
+        assert_eq!(parse_natural_datetime("tomorrow", now).unwrap(), at("2025-03-11 09:00"));
+        // Jour de la semaine : prochaine occurrence, jamais aujourd'hui
+        assert_eq!(parse_natural_datetime("lundi à 10:00", now).unwrap(), at("2025-03-17 10:00"));
+        assert_eq!(parse_natural_datetime("2025-04-01 08:00", now).unwrap(), at("2025-04-01 08:00"));
+        assert!(parse_natural_datetime("today 09:00", now).is_err());
+        assert!(parse_natural_datetime("someday", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_natural_datetime_articles_and_time() {
+        let now = at("2025-03-10 18:45");
+        // « a » / « une » valent 1, ils ne sont plus confondus avec « à »
+        assert_eq!(parse_natural_datetime("in a week", now).unwrap(), at("2025-03-17 18:45"));
+        assert_eq!(parse_natural_datetime("in an hour", now).unwrap(), at("2025-03-10 19:45"));
+        assert_eq!(parse_natural_datetime("dans une semaine", now).unwrap(), at("2025-03-17 18:45"));
+        // Heure appliquée après une durée en jours
+        assert_eq!(parse_natural_datetime("in 2 days at 10:00", now).unwrap(), at("2025-03-12 10:00"));
+        assert_eq!(parse_natural_datetime("dans 2j a 14h", now).unwrap(), at("2025-03-12 14:00"));
+        assert_eq!(parse_natural_datetime("demain a 8h", now).unwrap(), at("2025-03-11 08:00"));
+        assert_eq!(parse_natural_datetime("on friday", now).unwrap(), at("2025-03-14 09:00"));
+        // Mots en trop ou heure incompatible : refusés plutôt qu'ignorés
+        assert!(parse_natural_datetime("in 3 hours at 10:00", now).is_err());
+        assert!(parse_natural_datetime("in 2 days 10:00 please", now).is_err());
+        assert!(parse_natural_datetime("tomorrow at", now).is_err());
+        assert!(parse_natural_datetime("tomorrow morning", now).is_err());
+    }
+}
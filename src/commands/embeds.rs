@@ -0,0 +1,104 @@
+// Limites Discord des embeds : un seul dépassement fait rejeter tout le message
+use serenity::all::CreateEmbed;
+
+/// Nombre maximal de champs par embed
+pub const MAX_EMBED_FIELDS: usize = 25;
+
+/// Longueur maximale du nom d'un champ
+pub const MAX_FIELD_NAME_CHARS: usize = 256;
+
+/// Longueur maximale de la valeur d'un champ
+pub const MAX_FIELD_VALUE_CHARS: usize = 1024;
+
+/// Tronque à `max` caractères, « … » compris
+pub fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(max.saturating_sub(1)).collect::<String>())
+    }
+}
+
+/// Valeur de champ sûre : tronquée à 1024 caractères, « — » si vide (Discord refuse les valeurs vides)
+pub fn field_value(s: &str) -> String {
+    if s.trim().is_empty() {
+        "—".to_string()
+    } else {
+        truncate_chars(s, MAX_FIELD_VALUE_CHARS)
+    }
+}
+
+/// Liste pour une valeur de champ : au plus `max_items` éléments joints par `separator`, dans la
+/// limite de 1024 caractères ; les éléments non affichés sont résumés par « … et N de plus »
+pub fn list_field_value(items: Vec<String>, separator: &str, max_items: usize) -> String {
+    let total = items.len();
+    let overflow_note = |hidden: usize| format!("{}… et {} de plus", separator, hidden);
+    let mut value = String::new();
+    let mut shown = 0;
+    for item in items.into_iter().take(max_items) {
+        let hidden_after = total - shown - 1;
+        let reserve = if hidden_after > 0 { overflow_note(hidden_after).chars().count() } else { 0 };
+        let candidate = if shown == 0 { item } else { format!("{}{}", separator, item) };
+        if value.chars().count() + candidate.chars().count() + reserve > MAX_FIELD_VALUE_CHARS {
+            if shown == 0 {
+                // Un seul élément trop long : le tronquer plutôt que n'afficher que la note
+                value = truncate_chars(&candidate, MAX_FIELD_VALUE_CHARS - reserve);
+                shown = 1;
+            }
+            break;
+        }
+        value.push_str(&candidate);
+        shown += 1;
+    }
+    if shown < total {
+        value.push_str(&overflow_note(total - shown));
+    }
+    value
+}
+
+/// Ajoute des champs `(nom, valeur, inline)` sans dépasser `max_fields` (25 au plus) : les champs
+/// en trop sont résumés dans un dernier champ « … et N de plus »
+pub fn add_capped_fields(mut embed: CreateEmbed, fields: Vec<(String, String, bool)>, max_fields: usize) -> CreateEmbed {
+    let max_fields = max_fields.clamp(1, MAX_EMBED_FIELDS);
+    let total = fields.len();
+    let shown = if total > max_fields { max_fields - 1 } else { total };
+    for (name, value, inline) in fields.into_iter().take(shown) {
+        embed = embed.field(truncate_chars(&name, MAX_FIELD_NAME_CHARS), field_value(&value), inline);
+    }
+    if total > shown {
+        embed = embed.field("…", format!("… et {} de plus", total - shown), false);
+    }
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_field_value_caps_items_and_length() {
+        let items = |n: usize, len: usize| (0..n).map(|i| format!("{}{}", i, "x".repeat(len))).collect::<Vec<_>>();
+
+        assert_eq!(list_field_value(items(3, 1), ", ", 5), "0x, 1x, 2x");
+        assert_eq!(list_field_value(items(4, 1), "\n", 2), "0x\n1x\n… et 2 de plus");
+
+        // 30 éléments de ~100 caractères : coupé avant 1024 caractères, note comprise
+        let value = list_field_value(items(30, 100), "\n", 30);
+        assert!(value.chars().count() <= MAX_FIELD_VALUE_CHARS);
+        assert!(value.ends_with("de plus"));
+
+        let value = list_field_value(vec!["y".repeat(2000), "z".to_string()], "\n", 5);
+        assert!(value.chars().count() <= MAX_FIELD_VALUE_CHARS);
+        assert!(value.ends_with("\n… et 1 de plus"));
+    }
+
+    #[test]
+    fn test_add_capped_fields() {
+        let fields = (0..40).map(|i| (format!("Champ {}", i), String::new(), true)).collect();
+        let embed = serde_json::to_value(add_capped_fields(CreateEmbed::new(), fields, 100)).unwrap();
+        let fields = embed["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), MAX_EMBED_FIELDS);
+        assert_eq!(fields[0]["value"], "—");
+        assert_eq!(fields[24]["value"], "… et 16 de plus");
+    }
+}
@@ -3,9 +3,12 @@ use serenity::all::{
     ChannelId, Colour, CommandInteraction, CommandOptionType, Context, CreateCommand,
     CreateCommandOption, CreateEmbed, CreateMessage,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use super::{CommandError, SlashCommand, get_claude_client, get_database, get_cv_text};
+use super::{CommandError, SlashCommand, defer_response, get_claude_client, get_database, get_cv_text, private_option};
+
+/// Réponse vide du serveur Claude : proposer de relancer la commande
+const EMPTY_RESPONSE_MESSAGE: &str = "⚠️ Claude n'a renvoyé aucun texte. Réessayez dans quelques instants.";
 
 const COLOR_SYNTHESIS: Colour = Colour::from_rgb(46, 204, 113);
 
@@ -37,6 +40,10 @@ impl SlashCommand for SynthesizeOfferCommand {
         "Synthesize key information from a job description"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        false
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -48,10 +55,11 @@ impl SlashCommand for SynthesizeOfferCommand {
                 )
                 .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        defer_response(ctx, interaction).await?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let description = get_string_option(interaction, "description")?;
 
@@ -59,7 +67,7 @@ impl SlashCommand for SynthesizeOfferCommand {
 
         info!("Synthesizing job offer");
 
-        match claude_client.synthesize_job_offer(&description).await {
+        match claude_client.synthesize_job_offer(&description, None).await {
             Ok(synthesis) => {
                 let mut embed = CreateEmbed::new()
                     .title("📋 SYNTHÈSE DE L'OFFRE")
@@ -123,6 +131,10 @@ impl SlashCommand for GenerateResumeCommand {
         "Generate a tailored resume based on job description and your CV"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -134,10 +146,11 @@ impl SlashCommand for GenerateResumeCommand {
                 )
                 .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        defer_response(ctx, interaction).await?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let job_description = get_string_option(interaction, "job_description")?;
         let user_id = interaction.user.id;
@@ -161,7 +174,7 @@ impl SlashCommand for GenerateResumeCommand {
         info!("Generating resume for user {} with {} chars of CV", user_id, cv_content.len());
 
         // 1. Synthétiser l'offre
-        let synthesis = match claude_client.synthesize_job_offer(&job_description).await {
+        let synthesis = match claude_client.synthesize_job_offer(&job_description, None).await {
             Ok(s) => s,
             Err(e) => {
                 error!("Failed to synthesize: {}", e);
@@ -170,7 +183,8 @@ impl SlashCommand for GenerateResumeCommand {
         };
 
         // 2. Matcher les skills
-        let skills_match = match claude_client.match_skills(&job_description, &cv_content, None).await {
+        let declared_skills = user_cv.as_ref().map(|cv| cv.declared_skills()).unwrap_or_default();
+        let skills_match = match claude_client.match_skills(&job_description, &cv_content, None, &declared_skills, None, None).await {
             Ok(s) => s,
             Err(e) => {
                 error!("Failed to match skills: {}", e);
@@ -235,6 +249,10 @@ impl SlashCommand for GenerateCoverLetterCommand {
         "Generate a cover letter based on job description and your stored CV"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -254,10 +272,15 @@ impl SlashCommand for GenerateCoverLetterCommand {
                 )
                 .required(false),
             )
+            .add_option(private_option())
+    }
+
+    fn aliases(&self) -> &[&'static str] {
+        &["cover"]
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        defer_response(ctx, interaction).await?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let job_description = get_string_option(interaction, "job_description")?;
         let user_id = interaction.user.id;
@@ -367,6 +390,10 @@ impl SlashCommand for GenerateCoverLetterCommand {
 
                 followup_embed(ctx, interaction, embed).await
             }
+            Err(e) if e.is_empty_response() => {
+                warn!("Empty response while generating cover letter");
+                followup_response(ctx, interaction, EMPTY_RESPONSE_MESSAGE).await
+            }
             Err(e) => {
                 error!("Failed to generate cover letter: {}", e);
                 followup_response(ctx, interaction, &format!("Erreur: {}", e)).await
@@ -403,12 +430,22 @@ impl SlashCommand for GenerateMarketAnalysisCommand {
         "Generate a market analysis based on job trends and your skills"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        false
+    }
+
     fn register(&self) -> CreateCommand {
-        CreateCommand::new(self.name()).description(self.description())
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    fn aliases(&self) -> &[&'static str] {
+        &["market"]
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        defer_response(ctx, interaction).await?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id;
 
@@ -458,6 +495,10 @@ impl SlashCommand for GenerateMarketAnalysisCommand {
 
                 followup_embed(ctx, interaction, embed).await
             }
+            Err(e) if e.is_empty_response() => {
+                warn!("Empty response while analyzing market");
+                followup_response(ctx, interaction, EMPTY_RESPONSE_MESSAGE).await
+            }
             Err(e) => {
                 error!("Failed to analyze market: {}", e);
                 followup_response(ctx, interaction, &format!("❌ Erreur: {}", e)).await
@@ -493,13 +534,6 @@ fn get_string_option(interaction: &CommandInteraction, name: &str) -> Result<Str
         .ok_or_else(|| CommandError::MissingParameter(name.to_string()))
 }
 
-async fn defer_response(ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-    interaction
-        .defer(&ctx.http)
-        .await
-        .map_err(|e| CommandError::ResponseFailed(e.to_string()))
-}
-
 async fn followup_response(
     ctx: &Context,
     interaction: &CommandInteraction,
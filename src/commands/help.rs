@@ -27,6 +27,10 @@ impl SlashCommand for HelpCommand {
         "Display help information about the bot's commands"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name()).description(self.description())
     }
@@ -38,7 +42,7 @@ impl SlashCommand for HelpCommand {
             help_text.push_str(&format!("• **/{name}** — {description}\n"));
         }
 
-        let msg = CreateInteractionResponseMessage::new().content(help_text);
+        let msg = CreateInteractionResponseMessage::new().content(help_text).ephemeral(self.is_ephemeral(interaction));
         
         interaction
             .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
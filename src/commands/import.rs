@@ -0,0 +1,314 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use serenity::all::{
+    Colour, CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    CreateEmbed, EditInteractionResponse,
+};
+use tracing::info;
+
+use super::{CommandError, SlashCommand, attachment_option, defer_response, get_database, private_option};
+use super::embeds::truncate_chars;
+use crate::db::ImportedApplication;
+
+const COLOR_IMPORT: Colour = Colour::from_rgb(52, 152, 219);
+
+/// Colonnes du format CSV des candidatures (format d'export)
+pub const APPLICATION_CSV_HEADERS: &[&str] = &[
+    "id", "company", "job_title", "location", "job_url", "status", "match_score",
+    "applied_at", "created_at", "notes", "job_description",
+];
+
+/// Colonnes obligatoires pour l'import
+const REQUIRED_HEADERS: &[&str] = &["company", "job_title"];
+
+const VALID_STATUSES: &[&str] = &["generated", "applied", "interview", "offer", "rejected", "accepted"];
+
+const MAX_IMPORT_ROWS: usize = 500;
+const MAX_IMPORT_FILE_SIZE: u32 = 1024 * 1024; // 1 MB
+const MAX_ERRORS_DISPLAYED: usize = 15;
+
+// ============================================================================
+// ImportApplications Command - Bulk import from a CSV file
+// ============================================================================
+
+pub struct ImportApplicationsCommand;
+
+impl ImportApplicationsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ImportApplicationsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for ImportApplicationsCommand {
+    fn name(&self) -> &'static str {
+        "importapplications"
+    }
+
+    fn description(&self) -> &'static str {
+        "Import your existing applications from a CSV file"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Attachment,
+                    "file",
+                    "CSV file (columns: company, job_title, location, job_url, status, applied_at, notes...)",
+                )
+                .required(true),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+
+        let attachment = attachment_option(interaction, "file")
+            .map_err(CommandError::InvalidInput)?
+            .ok_or_else(|| CommandError::MissingParameter("file".to_string()))?;
+
+        if !attachment.filename.to_lowercase().ends_with(".csv") {
+            return Err(CommandError::InvalidInput(format!(
+                "`{}` is not a CSV file",
+                attachment.filename
+            )));
+        }
+        if attachment.size > MAX_IMPORT_FILE_SIZE {
+            return Err(CommandError::InvalidInput("CSV file is too large (max 1 MB)".to_string()));
+        }
+
+        let bytes = attachment.download().await
+            .map_err(|e| CommandError::Internal(format!("Download failed: {}", e)))?;
+        let content = String::from_utf8(bytes)
+            .map_err(|_| CommandError::InvalidInput("CSV file must be UTF-8 encoded".to_string()))?;
+
+        let (rows, errors) = parse_applications_csv(&content)?;
+
+        let db = get_database(ctx).await?;
+
+        let imported = if rows.is_empty() {
+            0
+        } else {
+            // job_applications référence users(id)
+            db.upsert_user(user_id, &interaction.user.name).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+            db.import_applications(user_id, &rows).await
+                .map_err(|e| CommandError::Internal(format!("Import failed: {}", e)))?
+        };
+
+        info!(
+            "User {} imported {} application(s) from CSV ({} row error(s))",
+            user_id, imported, errors.len()
+        );
+
+        let mut embed = CreateEmbed::new()
+            .title("📥 Import de candidatures")
+            .colour(COLOR_IMPORT)
+            .field("✅ Importées", imported.to_string(), true)
+            .field("⚠️ Lignes ignorées", errors.len().to_string(), true);
+
+        if !errors.is_empty() {
+            let mut details: Vec<String> = errors.iter().take(MAX_ERRORS_DISPLAYED).cloned().collect();
+            if errors.len() > MAX_ERRORS_DISPLAYED {
+                details.push(format!("... et {} autre(s)", errors.len() - MAX_ERRORS_DISPLAYED));
+            }
+            embed = embed.field("Erreurs", truncate_chars(&details.join("\n"), 1000), false);
+        }
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// CSV parsing
+// ============================================================================
+
+/// Parse un CSV au format d'export. Retourne les lignes valides et les erreurs par ligne.
+/// Une erreur d'en-tête est fatale (aucune ligne importée).
+pub fn parse_applications_csv(
+    content: &str,
+) -> Result<(Vec<ImportedApplication>, Vec<String>), CommandError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| CommandError::InvalidInput(format!("Invalid CSV header: {}", e)))?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').to_lowercase())
+        .collect();
+
+    let unknown: Vec<&str> = headers
+        .iter()
+        .map(|h| h.as_str())
+        .filter(|h| !APPLICATION_CSV_HEADERS.contains(h))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(CommandError::InvalidInput(format!(
+            "Unknown column(s): {}. Expected: {}",
+            unknown.join(", "),
+            APPLICATION_CSV_HEADERS.join(", ")
+        )));
+    }
+    let missing: Vec<&str> = REQUIRED_HEADERS
+        .iter()
+        .copied()
+        .filter(|required| !headers.iter().any(|h| h == required))
+        .collect();
+    if !missing.is_empty() {
+        return Err(CommandError::InvalidInput(format!(
+            "Missing required column(s): {}",
+            missing.join(", ")
+        )));
+    }
+
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let idx_company = column("company");
+    let idx_title = column("job_title");
+    let idx_location = column("location");
+    let idx_url = column("job_url");
+    let idx_status = column("status");
+    let idx_applied_at = column("applied_at");
+    let idx_notes = column("notes");
+    let idx_description = column("job_description");
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, record) in reader.records().enumerate() {
+        // Ligne 1 = en-tête
+        let line = record
+            .as_ref()
+            .ok()
+            .and_then(|r| r.position())
+            .map(|p| p.line())
+            .unwrap_or(i as u64 + 2);
+
+        if rows.len() + errors.len() >= MAX_IMPORT_ROWS {
+            errors.push(format!("Ligne {}+ : limite de {} lignes atteinte", line, MAX_IMPORT_ROWS));
+            break;
+        }
+
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                errors.push(format!("Ligne {} : CSV invalide ({})", line, e));
+                continue;
+            }
+        };
+
+        let field = |idx: Option<usize>| {
+            idx.and_then(|i| record.get(i))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+        };
+
+        let (Some(company), Some(job_title)) = (field(idx_company), field(idx_title)) else {
+            errors.push(format!("Ligne {} : `company` et `job_title` sont obligatoires", line));
+            continue;
+        };
+
+        let applied_at = field(idx_applied_at);
+        if let Some(ref date) = applied_at {
+            let valid = NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+                || NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").is_ok();
+            if !valid {
+                errors.push(format!("Ligne {} : date `{}` invalide (YYYY-MM-DD)", line, date));
+                continue;
+            }
+        }
+
+        let status = match field(idx_status).map(|s| s.to_lowercase()) {
+            Some(s) if VALID_STATUSES.contains(&s.as_str()) => s,
+            Some(s) => {
+                errors.push(format!("Ligne {} : statut `{}` inconnu", line, s));
+                continue;
+            }
+            None if applied_at.is_some() => "applied".to_string(),
+            None => "generated".to_string(),
+        };
+
+        let job_url = field(idx_url);
+        if let Some(ref url) = job_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                errors.push(format!("Ligne {} : URL `{}` invalide", line, url));
+                continue;
+            }
+        }
+
+        rows.push(ImportedApplication {
+            company,
+            job_title,
+            location: field(idx_location),
+            job_url,
+            status,
+            applied_at,
+            notes: field(idx_notes),
+            raw_job_description: field(idx_description),
+        });
+    }
+
+    Ok((rows, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_rows_and_defaults() {
+        let csv = "company,job_title,status,applied_at,job_url\n\
+                   Acme,Backend Engineer,interview,2025-03-01,https://acme.io/jobs/1\n\
+                   Globex,Data Engineer,,2025-02-20,\n\
+                   Initech,SRE,,,\n";
+        let (rows, errors) = parse_applications_csv(csv).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].status, "interview");
+        assert_eq!(rows[1].status, "applied");
+        assert_eq!(rows[2].status, "generated");
+        assert_eq!(rows[2].job_url, None);
+    }
+
+    #[test]
+    fn test_parse_reports_row_errors() {
+        let csv = "company,job_title,status,applied_at\n\
+                   Acme,Backend Engineer,pending,\n\
+                   ,Data Engineer,,\n\
+                   Globex,SRE,,15/03/2025\n\
+                   Initech,Dev,applied,2025-03-15\n";
+        let (rows, errors) = parse_applications_csv(csv).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("Ligne 2"));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_headers() {
+        assert!(parse_applications_csv("company,title\nAcme,Dev\n").is_err());
+        assert!(parse_applications_csv("company,location\nAcme,Paris\n").is_err());
+    }
+}
@@ -1,14 +1,28 @@
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serenity::all::{
-    ButtonStyle, ChannelType, Colour, CommandInteraction, CommandOptionType, Context,
+    ButtonStyle, ChannelId, ChannelType, Colour, CommandInteraction, CommandOptionType, Context,
     CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateAttachment,
-    CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
-    CreateThread, EditInteractionResponse,
+    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage, CreateMessage, CreateThread, EditInteractionResponse, EditThread,
+    ComponentInteraction, CreateInputText, CreateModal, GuildChannel, GuildId, Http, InputTextStyle,
+    Message, ModalInteraction, ModalInteractionData, Permissions, User, UserId, ActionRowComponent,
 };
+use serenity::prelude::TypeMapKey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
 use tracing::{error, info, warn};
 
-use super::{CommandError, SlashCommand, get_claude_client, get_database};
-use crate::services::{ClaudeClient, JobSynthesis, SalaryAnalysis, SkillsMatch};
+use super::{CommandError, ModalHandler, SlashCommand, attachment_option, defer_response, get_claude_client, get_config, get_database, private_option};
+use super::cv::reextract_cv_text;
+use super::datetime::parse_natural_datetime;
+use super::embeds::{MAX_EMBED_FIELDS, add_capped_fields, field_value, list_field_value};
+use crate::db::{self, AnalysisOptions, BaseCv, Database, JobApplication, UserSettings};
+use crate::services::anonymize::{Redaction, anonymize_if};
+use crate::services::events::{self, ApplicationEvent};
+use crate::services::language::detect_language;
+use crate::services::claude_client::{DEFAULT_CURRENCY, MissingSkill};
+use crate::services::{ClaudeClient, JobSynthesis, PdfStyle, RoleSuggestions, SalaryAnalysis, SkillsMatch, matching};
 
 // Couleurs des embeds
 const COLOR_SYNTHESIS: Colour = Colour::from_rgb(46, 204, 113);   // Vert
@@ -65,6 +79,23 @@ impl SlashCommand for ApplyJobCommand {
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Attachment,
+                    "description_image",
+                    "Capture d'écran de l'offre (PNG, JPEG, WebP, GIF ; 5 Mo max), lue par Claude",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "from_application",
+                    "ID d'une candidature déjà analysée : réutilise son offre et sa synthèse",
+                )
+                .required(false)
+                .min_int_value(1),
+            )
             .add_option(
                 CreateCommandOption::new(
                     CommandOptionType::String,
@@ -89,6 +120,14 @@ impl SlashCommand for ApplyJobCommand {
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "location",
+                    "Job location (optional, overrides the extracted one for the salary analysis)",
+                )
+                .required(false),
+            )
             .add_option(
                 CreateCommandOption::new(
                     CommandOptionType::Integer,
@@ -103,7 +142,7 @@ impl SlashCommand for ApplyJobCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "language",
-                    "Langue de sortie du CV (défaut: fr)",
+                    "Langue de sortie (défaut: langue détectée de l'offre, sinon fr)",
                 )
                 .required(false)
                 .add_string_choice("Français", "fr")
@@ -111,6 +150,19 @@ impl SlashCommand for ApplyJobCommand {
                 .add_string_choice("Español", "es")
                 .add_string_choice("Deutsch", "de"),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "currency",
+                    "Devise de l'analyse salariale (défaut: selon le lieu de l'offre, EUR en France)",
+                )
+                .required(false)
+                .add_string_choice("Euro (EUR)", "EUR")
+                .add_string_choice("Dollar US (USD)", "USD")
+                .add_string_choice("Livre sterling (GBP)", "GBP")
+                .add_string_choice("Franc suisse (CHF)", "CHF")
+                .add_string_choice("Dollar canadien (CAD)", "CAD"),
+            )
             .add_option(
                 CreateCommandOption::new(
                     CommandOptionType::String,
@@ -119,6 +171,114 @@ impl SlashCommand for ApplyJobCommand {
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "link_to",
+                    "ID d'une candidature précédente pour la même offre (historique des versions)",
+                )
+                .required(false)
+                .min_int_value(1),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Channel,
+                    "channel",
+                    "Salon où créer le thread d'analyse (défaut: salon courant)",
+                )
+                .required(false)
+                .channel_types(vec![ChannelType::Text, ChannelType::News]),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "template",
+                    "Style visuel du CV PDF (défaut: rendu standard)",
+                )
+                .required(false)
+                .add_string_choice("Classique", "classic")
+                .add_string_choice("Moderne", "modern")
+                .add_string_choice("Compact", "compact"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "accent_color",
+                    "Couleur d'accent du CV PDF (hexadécimal, ex: #2c3e50)",
+                )
+                .required(false)
+                .max_length(7),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "preview",
+                    "Vérifier l'entreprise et le poste détectés avant l'analyse complète",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "skip_salary",
+                    "Ne pas lancer l'analyse salariale (mobilité interne, salaire connu...)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "suggest_roles",
+                    "Suggérer des postes proches et des mots-clés de recherche (appel Claude supplémentaire)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "save_synthesis_pdf",
+                    "Joindre la synthèse de l'offre en PDF pour vos archives (Markdown si le PDF échoue)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "private_thread",
+                    "Résultats dans un fil privé visible par vous seul (défaut: préférence /settings)",
+                )
+                .required(false),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "visibility",
+                    "Contenu de l'embed de suivi dans le salon (défaut: complet)",
+                )
+                .required(false)
+                .add_string_choice("Complet", "full")
+                .add_string_choice("Minimal (statut et lien du thread)", "minimal"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "seniority",
+                    "Niveau de poste visé, pris en compte dans le matching des compétences",
+                )
+                .required(false)
+                .add_string_choice("Junior", "junior")
+                .add_string_choice("Confirmé", "mid")
+                .add_string_choice("Senior", "senior")
+                .add_string_choice("Lead", "lead"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "anonymize",
+                    "Masquer nom, email et téléphone du CV envoyé à l'IA (réinsérés dans le CV généré)",
+                )
+                .required(false),
+            )
     }
 
     async fn execute(
@@ -127,37 +287,141 @@ impl SlashCommand for ApplyJobCommand {
         interaction: &CommandInteraction,
     ) -> Result<(), CommandError> {
         // Defer - this will take time (AI processing)
-        interaction
-            .defer(&ctx.http)
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id;
-        let channel_id = interaction.channel_id;
 
         // Get options
         let text_description = get_optional_string_option(interaction, "description");
+        let from_application = get_optional_int_option(interaction, "from_application");
         let job_url = get_optional_string_option(interaction, "url");
         let company_override = get_optional_string_option(interaction, "company");
         let title_override = get_optional_string_option(interaction, "title");
+        let location_override = get_optional_string_option(interaction, "location");
         let fit_level = get_optional_int_option(interaction, "fit").unwrap_or(1) as u8;
-        let language = get_optional_string_option(interaction, "language").unwrap_or_else(|| "fr".to_string());
+        let language = get_optional_string_option(interaction, "language");
+        let currency = get_optional_string_option(interaction, "currency");
         let notes = get_optional_string_option(interaction, "notes");
+        let link_to = get_optional_int_option(interaction, "link_to");
+        let output_channel = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "channel")
+            .and_then(|opt| opt.value.as_channel_id());
+
+        let accent_color = match get_optional_string_option(interaction, "accent_color") {
+            Some(raw) => match parse_accent_color(&raw) {
+                Some(color) => Some(color),
+                None => {
+                    return send_error_response(
+                        ctx,
+                        interaction,
+                        &format!("Couleur d'accent invalide `{}` (format attendu: #RRGGBB).", raw),
+                    )
+                    .await;
+                }
+            },
+            None => None,
+        };
+        let pdf_style = PdfStyle {
+            template: get_optional_string_option(interaction, "template"),
+            accent_color,
+        };
+        let preview = match interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "preview")
+            .and_then(|opt| opt.value.as_bool())
+        {
+            Some(preview) => preview,
+            None => get_config(ctx).await?.applyjob_preview,
+        };
+        let skip_salary = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "skip_salary")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(false);
+        let suggest_roles = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "suggest_roles")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(false);
+        let save_synthesis_pdf = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "save_synthesis_pdf")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(false);
+        let private_thread = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "private_thread")
+            .and_then(|opt| opt.value.as_bool());
+        let minimal_tracking = get_optional_string_option(interaction, "visibility").as_deref() == Some("minimal");
+        let seniority = get_optional_string_option(interaction, "seniority");
+        let anonymize = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "anonymize")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(false);
 
         // Check for file attachment
         let file_description = get_optional_attachment_content(interaction, "description_file").await;
 
-        // Determine job description: file takes priority, then text
-        let job_description = match (file_description, text_description) {
-            (Ok(Some(content)), _) => {
+        // Offre d'une candidature existante : ni texte ni fichier à fournir
+        let source = match from_application {
+            Some(source_id) => {
+                if text_description.is_some() || matches!(file_description, Ok(Some(_))) {
+                    return send_error_response(
+                        ctx,
+                        interaction,
+                        "L'option `from_application` réutilise l'offre enregistrée : ne fournissez pas de description.",
+                    )
+                    .await;
+                }
+                let db = get_database(ctx).await?;
+                match db.get_application(source_id).await
+                    .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+                {
+                    Some(app) if app.user_id == user_id.get() as i64 => Some(app),
+                    _ => {
+                        return send_error_response(
+                            ctx,
+                            interaction,
+                            &format!("Candidature #{} introuvable (option `from_application`).", source_id),
+                        )
+                        .await;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Determine job description: stored offer, then file, then text, then screenshot
+        let job_description = match (source.as_ref(), file_description, text_description) {
+            (Some(app), _, _) => {
+                info!("Reusing job description of application {} for user {}", app.id, user_id);
+                app.raw_job_description.clone()
+            }
+            (None, Ok(Some(content)), _) => {
                 info!("Using job description from file for user {}", user_id);
                 content
             }
-            (_, Some(text)) => {
+            (None, _, Some(text)) => {
                 info!("Using job description from text for user {}", user_id);
                 text
             }
-            (Err(e), None) => {
+            (None, Err(e), None) => {
                 return send_error_response(
                     ctx,
                     interaction,
@@ -165,814 +429,3030 @@ impl SlashCommand for ApplyJobCommand {
                 )
                 .await;
             }
-            (Ok(None), None) => {
-                return send_error_response(
-                    ctx,
-                    interaction,
-                    "Veuillez fournir une description de l'offre (texte ou fichier).",
+            (None, Ok(None), None) => match get_optional_image_text(ctx, interaction, "description_image").await {
+                Ok(Some(text)) => {
+                    info!("Using job description from screenshot for user {}", user_id);
+                    text
+                }
+                Ok(None) => {
+                    return send_error_response(
+                        ctx,
+                        interaction,
+                        "Veuillez fournir une description de l'offre (texte, fichier ou capture d'écran).",
+                    )
+                    .await;
+                }
+                Err(e) => return send_error_response(ctx, interaction, &e).await,
+            },
+        };
+
+        // Métadonnées de la candidature source, sauf options explicites ;
+        // la nouvelle analyse est rattachée à la source comme nouvelle version
+        let (job_url, language, currency, link_to, stored_synthesis) = match source {
+            Some(app) => {
+                let synthesis = stored_synthesis(&app);
+                if synthesis.is_none() {
+                    info!("No stored synthesis for application {}, synthesizing again", app.id);
+                }
+                (
+                    job_url.or(app.job_url),
+                    language.or(app.language),
+                    currency.or_else(|| Some(app.salary_currency).filter(|c| c != DEFAULT_CURRENCY)),
+                    link_to.or(Some(app.id)),
+                    synthesis,
                 )
-                .await;
             }
+            None => (job_url, language, currency, link_to, None),
         };
 
         info!("Processing job application for user {}", user_id);
 
-        // Timeout global sur l'ensemble du workflow (10 min max)
-        let result = tokio::time::timeout(
-            std::time::Duration::from_secs(600),
-            self.run_apply_job(
-                ctx, interaction, user_id, channel_id,
-                job_description, job_url, company_override, title_override,
-                fit_level, language, notes,
-            ),
-        ).await;
-
-        match result {
-            Ok(inner) => return inner,
-            Err(_) => {
-                return interaction
-                    .edit_response(
-                        &ctx.http,
-                        EditInteractionResponse::new().content(
-                            "⏱️ **Délai dépassé** — Le traitement a pris plus de 10 minutes.\n\
-                            Le serveur Claude est peut-être surchargé. Réessayez dans quelques instants."
-                        ),
-                    )
-                    .await
-                    .map(|_| ())
-                    .map_err(|e| CommandError::ResponseFailed(e.to_string()));
-            }
-        }
+        let request = ApplyJobRequest {
+            job_description,
+            job_url,
+            company_override,
+            title_override,
+            location_override,
+            fit_level,
+            language,
+            currency,
+            notes,
+            link_to,
+            output_channel,
+            pdf_style,
+            preview,
+            skip_salary,
+            suggest_roles,
+            save_synthesis_pdf,
+            private_thread,
+            minimal_tracking,
+            seniority,
+            anonymize,
+            stored_synthesis,
+            without_cv: false,
+        };
+        run_apply_job_with_timeout(ctx, interaction, request).await
     }
 }
 
-impl ApplyJobCommand {
-    #[allow(clippy::too_many_arguments)]
-    async fn run_apply_job(
-        &self,
-        ctx: &Context,
-        interaction: &CommandInteraction,
-        user_id: serenity::all::UserId,
-        channel_id: serenity::all::ChannelId,
-        job_description: String,
-        job_url: Option<String>,
-        company_override: Option<String>,
-        title_override: Option<String>,
-        fit_level: u8,
-        language: String,
-        notes: Option<String>,
-    ) -> Result<(), CommandError> {
-        let claude_client = get_claude_client(ctx).await?;
-        let db = get_database(ctx).await?;
-
-        // Envoyer un embed de suivi initial dans le canal principal
-        let initial_tracking_embed = build_tracking_embed_progress("Synthèse de l'offre...", None, None);
-        interaction
-            .edit_response(
-                &ctx.http,
-                EditInteractionResponse::new().embed(initial_tracking_embed),
-            )
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-
-        // 1. Synthétiser l'offre d'emploi
-        let synthesis = match claude_client.synthesize_job_offer(&job_description).await {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to synthesize job offer: {}", e);
-                return send_error_response(
-                    ctx,
-                    interaction,
-                    &format!("Erreur lors de la synthèse: {}", e),
-                )
-                .await;
-            }
-        };
+// ============================================================================
+// ApplyJobForm Command
+// /applyjob via un formulaire : zone de texte multi-ligne pour les longues offres
+// ============================================================================
 
-        // 2. Récupérer le CV de l'utilisateur depuis la DB
-        let user_cv = db.get_active_cv(user_id.get() as i64).await
-            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+/// Taille maximale d'un champ de texte de modal imposée par Discord
+const MAX_MODAL_DESCRIPTION_CHARS: u16 = 4000;
 
-        // Sauvegarder la candidature en DB
-        let cv_id = user_cv.as_ref().map(|cv| cv.id); // None si pas de CV → FK nullable
-        // Utiliser les overrides fournis par l'utilisateur en priorité sur la synthèse
-        let final_title = title_override.as_deref().unwrap_or(&synthesis.title);
-        let final_company = company_override.as_deref().unwrap_or(&synthesis.company);
-        let application_id = db
-            .create_application(
-                user_id.get() as i64,
-                cv_id,
-                Some(final_title),
-                Some(final_company),
-                Some(&synthesis.location),
-                job_url.as_deref(),
-                &job_description,
-            ).await
-            .map_err(|e| CommandError::Internal(format!("Failed to save application: {}", e)))?;
-
-        // Sauvegarder les notes si fournies
-        if let Some(ref notes_text) = notes {
-            if let Err(e) = db.update_application_notes(application_id, notes_text).await {
-                warn!("Failed to save application notes: {}", e);
-            }
-        }
+pub struct ApplyJobFormCommand;
 
-        info!("Created application {} for user {}", application_id, user_id);
+impl ApplyJobFormCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
 
-        // Créer le thread pour les résultats détaillés
-        let thread_name = format!("📋 {} - {}", final_company, final_title);
-        let thread_name = if thread_name.len() > 100 {
-            format!("{}...", safe_truncate_bytes(&thread_name, 97))
-        } else {
-            thread_name
-        };
+impl Default for ApplyJobFormCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        let thread = channel_id
-            .create_thread(
-                &ctx.http,
-                CreateThread::new(thread_name.clone())
-                    .kind(ChannelType::PublicThread)
-                    .auto_archive_duration(serenity::all::AutoArchiveDuration::OneDay),
-            )
-            .await
-            .map_err(|e| CommandError::Internal(format!("Failed to create thread: {}", e)))?;
+#[async_trait]
+impl SlashCommand for ApplyJobFormCommand {
+    fn name(&self) -> &'static str {
+        "applyjobform"
+    }
 
-        info!("Created thread {} for job application", thread.id);
+    fn description(&self) -> &'static str {
+        "Apply to a job by pasting the description in a multi-line form"
+    }
 
-        // Sauvegarder le thread_id en DB
-        if let Err(e) = db.update_application_thread(application_id, thread.id.get() as i64).await {
-            warn!("Failed to save thread_id: {}", e);
-        }
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
 
-        // Mettre à jour l'embed de suivi avec le lien vers le thread
-        let tracking_embed = build_tracking_embed_progress(
-            "Analyse des compétences...",
-            Some(&synthesis),
-            Some(thread.id.get()),
-        );
+    async fn execute(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        // La visibilité choisie à l'appel est portée par l'identifiant du modal
+        let visibility = if self.is_ephemeral(interaction) { "private" } else { "public" };
+        let description = CreateInputText::new(InputTextStyle::Paragraph, "Description de l'offre", "description")
+            .placeholder("Collez le texte complet de l'offre")
+            .required(true)
+            .max_length(MAX_MODAL_DESCRIPTION_CHARS);
+        let url = CreateInputText::new(InputTextStyle::Short, "URL de l'offre (facultatif)", "url")
+            .required(false)
+            .max_length(512);
+        let company = CreateInputText::new(InputTextStyle::Short, "Entreprise (facultatif)", "company")
+            .placeholder("Détectée automatiquement si vide")
+            .required(false)
+            .max_length(100);
+        let title = CreateInputText::new(InputTextStyle::Short, "Intitulé du poste (facultatif)", "title")
+            .placeholder("Détecté automatiquement si vide")
+            .required(false)
+            .max_length(100);
+        let notes = CreateInputText::new(InputTextStyle::Paragraph, "Notes sur votre expérience (facultatif)", "notes")
+            .placeholder("Domaines, projets, contexte...")
+            .required(false)
+            .max_length(1000);
+        let modal = CreateModal::new(format!("applyjobform_{}", visibility), "Analyser une offre")
+            .components(vec![
+                CreateActionRow::InputText(description),
+                CreateActionRow::InputText(url),
+                CreateActionRow::InputText(company),
+                CreateActionRow::InputText(title),
+                CreateActionRow::InputText(notes),
+            ]);
         interaction
-            .edit_response(
-                &ctx.http,
-                EditInteractionResponse::new().embed(tracking_embed),
-            )
+            .create_response(&ctx.http, CreateInteractionResponse::Modal(modal))
             .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+    }
+}
 
-        // Envoyer l'embed de synthèse dans le thread
-        let synthesis_embed = build_synthesis_embed(&synthesis);
-        thread
-            .send_message(&ctx.http, CreateMessage::new().embed(synthesis_embed))
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+/// Validation du formulaire `applyjobform_{public|private}` : lance le pipeline /applyjob
+/// sur le texte saisi, avec les options par défaut
+pub struct ApplyJobFormModal;
 
-        let cv_content = match &user_cv {
-            Some(cv) => {
-                if let Some(ref extracted) = cv.extracted_text {
-                    if !extracted.is_empty() {
-                        info!("Using extracted text for CV {} (user {})", cv.id, user_id);
-                        extracted.clone()
-                    } else {
-                        warn!("Extracted text is empty for CV {}", cv.id);
-                        format!("CV: {} (texte non disponible - réuploadez votre CV)", cv.original_name)
-                    }
-                } else {
-                    match tokio::fs::read_to_string(&cv.file_path).await {
-                        Ok(content) => {
-                            info!("Read CV file directly for user {}", user_id);
-                            content
-                        }
-                        Err(_) => {
-                            warn!("No extracted text and file not readable for CV {}", cv.id);
-                            format!("CV: {} (texte non extrait - réuploadez votre CV avec /sendcv)", cv.original_name)
-                        }
-                    }
-                }
-            }
-            None => {
-                info!("No CV found for user {}", user_id);
-                "CV non fourni - analyse basée sur l'offre uniquement".to_string()
-            }
-        };
+#[async_trait]
+impl ModalHandler for ApplyJobFormModal {
+    fn custom_id_prefix(&self) -> &'static str {
+        "applyjobform_"
+    }
 
-        let has_cv = user_cv.is_some();
+    async fn handle(&self, ctx: &Context, modal: &ModalInteraction) -> Result<(), CommandError> {
+        run_applyjob_form(ctx, modal).await
+    }
+}
 
-        // Analyse des compétences
-        let skills_match = match claude_client
-            .match_skills(&job_description, &cv_content, notes.as_deref())
-            .await
-        {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to match skills: {}", e);
-                let default_highlight = if has_cv {
-                    "Analyse en cours...".to_string()
-                } else {
-                    "Uploadez votre CV avec `/sendcv` pour une analyse personnalisée".to_string()
-                };
-                SkillsMatch {
-                    match_score: 0,
-                    matched_skills: vec![],
-                    missing_skills: vec![],
-                    highlights: vec![default_highlight],
-                    recommendations: vec![],
-                }
-            }
-        };
+async fn run_applyjob_form(ctx: &Context, modal: &ModalInteraction) -> Result<(), CommandError> {
+    let ephemeral = modal.data.custom_id == "applyjobform_private";
+    modal
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new().ephemeral(ephemeral)),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
 
-        // Mettre à jour le tracking
-        let tracking_embed = build_tracking_embed_progress(
-            "Analyse salariale...",
-            Some(&synthesis),
-            Some(thread.id.get()),
-        );
-        interaction
+    let Some(job_description) = modal_text_value(&modal.data, "description") else {
+        return modal
             .edit_response(
                 &ctx.http,
-                EditInteractionResponse::new().embed(tracking_embed),
+                EditInteractionResponse::new().content("❌ Veuillez coller la description de l'offre."),
             )
             .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-
-        // Envoyer l'embed des compétences dans le thread
-        let skills_embed = build_skills_embed(&skills_match);
-        thread
-            .send_message(&ctx.http, CreateMessage::new().embed(skills_embed))
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            .map(|_| ())
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+    };
+    info!("Using job description from form for user {}", modal.user.id);
+
+    let request = ApplyJobRequest {
+        job_url: modal_text_value(&modal.data, "url"),
+        company_override: modal_text_value(&modal.data, "company"),
+        title_override: modal_text_value(&modal.data, "title"),
+        notes: modal_text_value(&modal.data, "notes"),
+        preview: get_config(ctx).await?.applyjob_preview,
+        ..ApplyJobRequest::new(job_description)
+    };
+    start_apply_job(ctx, TrackingInteraction::Modal(modal), request).await
+}
 
-        // 3. Analyse salariale
-        let salary_analysis = match claude_client
-            .analyze_salary(&job_description, Some(&synthesis.location))
-            .await
-        {
-            Ok(s) => s,
-            Err(e) => {
-                error!("Failed to analyze salary: {}", e);
-                SalaryAnalysis {
-                    offered_min: None,
-                    offered_max: None,
-                    market_low: 0,
-                    market_median: 0,
-                    market_high: 0,
-                    currency: "EUR".to_string(),
-                    analysis: format!("Analyse non disponible: {}", e),
-                    negotiation_tips: vec![],
-                }
-            }
-        };
+// ============================================================================
+// Clone Command
+// Même offre pour une autre entreprise : nouvelle candidature, nouvelle analyse
+// ============================================================================
 
-        // Envoyer l'embed salarial dans le thread
-        let salary_embed = build_salary_embed(&salary_analysis);
-        thread
-            .send_message(&ctx.http, CreateMessage::new().embed(salary_embed))
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+pub struct CloneApplicationCommand;
 
-        // 4. Génération de CV personnalisé si CV disponible
-        let cv_generated = if has_cv {
-            // Mettre à jour le tracking
-            let tracking_embed = build_tracking_embed_progress(
-                "Génération du CV personnalisé...",
-                Some(&synthesis),
-                Some(thread.id.get()),
-            );
-            interaction
-                .edit_response(
-                    &ctx.http,
-                    EditInteractionResponse::new().embed(tracking_embed),
-                )
-                .await
-                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-
-            match claude_client
-                .generate_tailored_cv(&cv_content, &synthesis, &skills_match, fit_level, &language, notes.as_deref())
-                .await
-            {
-                Ok(generated_cv) => {
-                    let fit_display = match fit_level {
-                        1 => "1️⃣ Standard",
-                        2 => "2️⃣ Modéré",
-                        3 => "3️⃣ Laxiste",
-                        _ => "1️⃣ Standard",
-                    };
-                    let lang_display = match language.as_str() {
-                        "fr" => "🇫🇷 Français",
-                        "en" => "🇬🇧 English",
-                        "es" => "🇪🇸 Español",
-                        "de" => "🇩🇪 Deutsch",
-                        _ => "🇫🇷 Français",
-                    };
-                    let mut embed = CreateEmbed::new()
-                        .title("📄 CV PERSONNALISÉ GÉNÉRÉ")
-                        .colour(COLOR_CV)
-                        .field("🎚️ Adaptation", fit_display, true)
-                        .field("🌐 Langue", lang_display, true)
-                        .field("📝 Résumé des adaptations", &generated_cv.summary, false);
-
-                    if !generated_cv.adaptations.is_empty() {
-                        let adaptations = generated_cv
-                            .adaptations
-                            .iter()
-                            .take(5)
-                            .map(|a| format!("• {}", a))
-                            .collect::<Vec<_>>()
-                            .join("\n");
-                        embed = embed.field("✨ Modifications apportées", adaptations, false);
-                    }
+impl CloneApplicationCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
 
-                    let cv_text = generated_cv.get_content();
-                    let username = &interaction.user.name;
+impl Default for CloneApplicationCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-                    // Heuristique: si le contenu est long, forcer single_page dès la première tentative
-                    let try_single_page_first = cv_text.len() > 8000;
-                    if try_single_page_first {
-                        info!("CV content is large ({} bytes), using single_page=true directly", cv_text.len());
-                    }
+#[async_trait]
+impl SlashCommand for CloneApplicationCommand {
+    fn name(&self) -> &'static str {
+        "clone"
+    }
 
-                    match claude_client
-                        .generate_pdf(cv_text, username, &synthesis.title, &synthesis.company, try_single_page_first)
-                        .await
-                    {
-                        Ok(pdf_bytes) => {
-                            let page_count = ClaudeClient::count_pdf_pages(&pdf_bytes);
-                            let final_pdf = if !try_single_page_first && page_count > 1 {
-                                info!("CV PDF has {} pages, retrying with single_page=true", page_count);
-                                match claude_client
-                                    .generate_pdf(cv_text, username, &synthesis.title, &synthesis.company, true)
-                                    .await
-                                {
-                                    Ok(retry_bytes) => {
-                                        let retry_pages = ClaudeClient::count_pdf_pages(&retry_bytes);
-                                        if retry_pages > 1 {
-                                            warn!("CV PDF still has {} pages after single_page retry", retry_pages);
-                                        }
-                                        retry_bytes
-                                    }
-                                    Err(e) => {
-                                        warn!("Single-page PDF retry failed: {}, using original", e);
-                                        pdf_bytes
-                                    }
-                                }
-                            } else {
-                                pdf_bytes
-                            };
-
-                            let safe_title = synthesis.title
-                                .chars()
-                                .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
-                                .collect::<String>()
-                                .replace(' ', "_");
-                            let filename = format!("CV_{}_{}.pdf", username, safe_title);
-                            let attachment = CreateAttachment::bytes(final_pdf, &filename);
-
-                            embed = embed.field(
-                                "📥 Téléchargement",
-                                "✅ PDF généré et joint ci-dessous!",
-                                false,
-                            );
-
-                            thread
-                                .send_message(
-                                    &ctx.http,
-                                    CreateMessage::new().embed(embed).add_file(attachment),
-                                )
-                                .await
-                                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-                            true
-                        }
-                        Err(e) => {
-                            warn!("Failed to generate PDF: {}", e);
-                            embed = embed.field(
-                                "📥 Téléchargement",
-                                format!("⚠️ Génération PDF échouée: {}", e),
-                                false,
-                            );
-
-                            thread
-                                .send_message(&ctx.http, CreateMessage::new().embed(embed))
-                                .await
-                                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-                            true
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to generate tailored CV: {}", e);
-                    let embed = CreateEmbed::new()
-                        .title("📄 Génération de CV")
-                        .description(format!("Erreur lors de la génération: {}", e))
-                        .colour(COLOR_CV)
-                        .field(
-                            "💡 Conseil",
-                            "Réessayez avec `/applyjob` ou vérifiez que votre CV est bien uploadé.",
-                            false,
-                        );
+    fn description(&self) -> &'static str {
+        "Analyze the job description of an application again for another company"
+    }
 
-                    thread
-                        .send_message(&ctx.http, CreateMessage::new().embed(embed))
-                        .await
-                        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-                    false
-                }
-            }
-        } else {
-            let embed = CreateEmbed::new()
-                .title("📄 Génération de CV")
-                .description("Pour générer un CV personnalisé, uploadez d'abord votre CV de base.")
-                .colour(COLOR_CV)
-                .field(
-                    "Prochaines étapes",
-                    "1. `/sendcv` - Uploader votre CV\n2. `/applyjob` - Relancer l'analyse\n3. Télécharger votre CV personnalisé",
-                    false,
-                );
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "application_id",
+                    "Application ID to clone (from /status)",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "company", "Company of the new application")
+                    .required(true),
+            )
+            .add_option(private_option())
+    }
 
-            thread
-                .send_message(&ctx.http, CreateMessage::new().embed(embed))
-                .await
-                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-            false
-        };
+    async fn execute(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
-        // Mettre à jour l'analyse en DB
-        if let Err(e) = db.update_application_analysis(
-            application_id,
-            &synthesis.summary,
-            &serde_json::to_string(&synthesis.key_requirements).unwrap_or_default(),
-            &serde_json::to_string(&skills_match.matched_skills).unwrap_or_default(),
-            &serde_json::to_string(&skills_match.missing_skills).unwrap_or_default(),
-            skills_match.match_score as i32,
-        ).await {
-            warn!("Failed to update application analysis: {}", e);
+        let user_id = interaction.user.id;
+        let application_id = get_int_option(interaction, "application_id")?;
+        let company = get_string_option(interaction, "company")?.trim().to_string();
+        if company.is_empty() {
+            return send_error_response(ctx, interaction, "Veuillez indiquer l'entreprise de la nouvelle candidature.").await;
         }
 
-        // Mettre à jour l'embed de suivi final dans le canal principal avec les boutons
-        let final_tracking_embed = build_tracking_embed_complete(
-            &synthesis,
-            skills_match.match_score,
-            has_cv,
-            cv_generated,
-            thread.id.get(),
-            application_id,
-            "generated",
-        );
-        let action_rows = build_status_buttons(application_id, "generated");
-        interaction
-            .edit_response(
-                &ctx.http,
-                EditInteractionResponse::new()
-                    .embed(final_tracking_embed)
-                    .components(action_rows),
-            )
-            .await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
-
-        info!("Job application analysis completed for user {}", user_id);
-
-        Ok(())
+        let db = get_database(ctx).await?;
+        let source = match db.get_application(application_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        {
+            Some(app) if app.user_id == user_id.get() as i64 => app,
+            _ => {
+                return send_error_response(ctx, interaction, &format!("Candidature #{} introuvable.", application_id)).await;
+            }
+        };
+        info!("Cloning application {} for user {} (company: {})", source.id, user_id, company);
+
+        // La synthèse est refaite : l'entreprise détectée et l'analyse dépendent de l'offre clonée.
+        // Pas de rattachement `link_to` : il s'agit d'une autre candidature, pas d'une nouvelle version
+        let request = ApplyJobRequest {
+            job_url: source.job_url,
+            company_override: Some(company),
+            title_override: source.job_title,
+            language: source.language,
+            currency: Some(source.salary_currency).filter(|c| c != DEFAULT_CURRENCY),
+            seniority: source.seniority,
+            anonymize: source.anonymize_cv,
+            preview: get_config(ctx).await?.applyjob_preview,
+            ..ApplyJobRequest::new(source.raw_job_description)
+        };
+        run_apply_job_with_timeout(ctx, interaction, request).await
     }
 }
 
-// ============================================================================
-// Embed builders
-// ============================================================================
-
-fn build_synthesis_embed(synthesis: &JobSynthesis) -> CreateEmbed {
-    let mut embed = CreateEmbed::new()
-        .title("📋 SYNTHÈSE DE L'OFFRE")
-        .colour(COLOR_SYNTHESIS)
-        .field("🏢 Entreprise", &synthesis.company, true)
-        .field("💼 Poste", &synthesis.title, true)
-        .field("📍 Lieu", &synthesis.location, true)
-        .field("📝 Contrat", &synthesis.contract_type, true);
-
-    if let Some(salary) = &synthesis.salary_range {
-        embed = embed.field("💰 Salaire", salary, true);
+/// Synthèse enregistrée d'une candidature (None si absente ou illisible)
+fn stored_synthesis(app: &JobApplication) -> Option<JobSynthesis> {
+    let json = app.synthesis_json.as_deref()?;
+    match serde_json::from_str(json) {
+        Ok(synthesis) => Some(synthesis),
+        Err(e) => {
+            warn!("Invalid stored synthesis for application {}: {}", app.id, e);
+            None
+        }
     }
+}
 
-    let requirements = if synthesis.key_requirements.is_empty() {
-        "Non spécifié".to_string()
-    } else {
-        synthesis
-            .key_requirements
-            .iter()
-            .map(|r| format!("• {}", r))
-            .collect::<Vec<_>>()
-            .join("\n")
-    };
-
-    embed = embed.field("🎯 Compétences clés", requirements, false);
-    embed = embed.field("📖 Résumé", &synthesis.summary, false);
-
-    embed
+/// Paramètres du pipeline /applyjob (partagés avec la commande de menu contextuel)
+pub struct ApplyJobRequest {
+    pub job_description: String,
+    pub job_url: Option<String>,
+    pub company_override: Option<String>,
+    pub title_override: Option<String>,
+    /// Lieu saisi par l'utilisateur, prioritaire sur le lieu extrait (analyse salariale comprise)
+    pub location_override: Option<String>,
+    /// 1=standard, 2=modéré, 3=laxiste
+    pub fit_level: u8,
+    /// Langue de sortie ; None = langue détectée de l'offre
+    pub language: Option<String>,
+    /// Devise de l'analyse salariale ; None = choisie selon le lieu de l'offre
+    pub currency: Option<String>,
+    pub notes: Option<String>,
+    /// Version précédente de la même candidature
+    pub link_to: Option<i64>,
+    /// Salon où créer le thread (défaut: salon de la commande)
+    pub output_channel: Option<ChannelId>,
+    /// Style du PDF généré
+    pub pdf_style: PdfStyle,
+    /// Afficher l'entreprise / le poste détectés et attendre une confirmation avant l'analyse complète
+    pub preview: bool,
+    /// Ne pas lancer l'analyse salariale ni envoyer son embed
+    pub skip_salary: bool,
+    /// Suggérer des postes proches et des mots-clés de recherche dans le thread
+    pub suggest_roles: bool,
+    /// Joindre la synthèse de l'offre en PDF (Markdown en cas d'échec) dans le thread
+    pub save_synthesis_pdf: bool,
+    /// Fil privé pour les résultats (None = préférence « réponses privées » de /settings)
+    pub private_thread: Option<bool>,
+    /// Embed de suivi du salon réduit au statut et au lien du thread (option `visibility:minimal`)
+    pub minimal_tracking: bool,
+    /// Niveau visé (junior, mid, senior, lead) transmis au matching des compétences
+    pub seniority: Option<String>,
+    /// Nom, emails et téléphones masqués dans le CV envoyé au serveur Claude
+    pub anonymize: bool,
+    /// Synthèse déjà faite (option `from_application`) : ni synthèse ni appel combiné
+    pub stored_synthesis: Option<JobSynthesis>,
+    /// Analyse sans CV confirmée par l'utilisateur (texte du CV actif illisible)
+    pub without_cv: bool,
 }
 
-fn build_skills_embed(skills: &SkillsMatch) -> CreateEmbed {
-    let score_bar = build_progress_bar(skills.match_score, 100);
+impl ApplyJobRequest {
+    /// Requête avec les options par défaut de /applyjob pour le texte d'offre donné
+    pub fn new(job_description: String) -> Self {
+        Self {
+            job_description,
+            job_url: None,
+            company_override: None,
+            title_override: None,
+            location_override: None,
+            fit_level: 1,
+            language: None,
+            currency: None,
+            notes: None,
+            link_to: None,
+            output_channel: None,
+            pdf_style: PdfStyle::default(),
+            preview: false,
+            skip_salary: false,
+            suggest_roles: false,
+            save_synthesis_pdf: false,
+            private_thread: None,
+            minimal_tracking: false,
+            seniority: None,
+            anonymize: false,
+            stored_synthesis: None,
+            without_cv: false,
+        }
+    }
+}
 
-    let mut embed = CreateEmbed::new()
-        .title("🎯 ANALYSE DE COMPATIBILITÉ")
-        .colour(COLOR_SKILLS)
-        .field(
-            "Score de matching",
-            format!("{} **{}%**", score_bar, skills.match_score),
-            false,
-        );
+/// Exécute le pipeline complet (synthèse, matching, salaire, CV) sur une interaction déjà différée,
+/// avec un timeout global de 10 minutes. Une offre plus longue que `APPLYJOB_CONFIRM_CHARS`
+/// attend d'abord la confirmation du coût estimé.
+pub async fn run_apply_job_with_timeout(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    request: ApplyJobRequest,
+) -> Result<(), CommandError> {
+    start_apply_job(ctx, TrackingInteraction::Command(interaction), request).await
+}
 
-    // Compétences matchées
-    if !skills.matched_skills.is_empty() {
-        let matched = skills
-            .matched_skills
-            .iter()
-            .take(5)
-            .map(|s| {
-                let icon = if s.is_match { "✅" } else { "⚠️" };
-                format!("{} **{}**: {} → Requis: {}", icon, s.skill, s.cv_level, s.required)
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-        embed = embed.field("✅ Compétences matchées", matched, false);
+async fn start_apply_job(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    request: ApplyJobRequest,
+) -> Result<(), CommandError> {
+    let threshold = get_config(ctx).await?.applyjob_confirm_chars;
+    let chars = request.job_description.chars().count();
+    if threshold > 0 && chars as i64 > threshold {
+        return ask_cost_confirmation(ctx, interaction, request, chars, threshold).await;
     }
+    run_tracked_apply_job(ctx, interaction, request).await
+}
 
-    // Compétences manquantes
-    if !skills.missing_skills.is_empty() {
-        let missing = skills
-            .missing_skills
-            .iter()
-            .take(5)
-            .map(|s| format!("❌ **{}** ({})", s.skill, s.importance))
-            .collect::<Vec<_>>()
-            .join("\n");
-        embed = embed.field("❌ Compétences manquantes", missing, false);
+async fn run_tracked_apply_job(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    request: ApplyJobRequest,
+) -> Result<(), CommandError> {
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(600),
+        run_apply_job(ctx, interaction, request),
+    ).await;
+
+    match result {
+        Ok(inner) => inner,
+        Err(_) => {
+            interaction
+                .edit_response(&ctx.http, EditInteractionResponse::new().content(ANALYSIS_TIMEOUT_MESSAGE))
+                .await
+                .map(|_| ())
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+        }
     }
+}
 
-    // Points forts
-    if !skills.highlights.is_empty() {
-        let highlights = skills
-            .highlights
-            .iter()
-            .take(3)
-            .map(|h| format!("⭐ {}", h))
-            .collect::<Vec<_>>()
-            .join("\n");
-        embed = embed.field("⭐ Points forts à mettre en avant", highlights, false);
-    }
+/// Caractères par token, ordre de grandeur pour du texte français ou anglais
+const CHARS_PER_TOKEN: usize = 4;
 
-    embed
+/// Estimation grossière d'un /applyjob à partir de la taille de l'offre :
+/// (tokens de l'offre, durée en secondes). Environ 1 min 30 pour une offre courte
+/// (analyse, CV, PDF), puis 1 s pour 50 tokens lus.
+fn estimate_applyjob_cost(description_chars: usize) -> (usize, u64) {
+    let tokens = description_chars.div_ceil(CHARS_PER_TOKEN);
+    (tokens, 90 + tokens as u64 / 50)
 }
 
-fn build_salary_embed(salary: &SalaryAnalysis) -> CreateEmbed {
+/// Affiche le coût estimé d'une offre très longue avec les boutons
+/// `applyjobcost_run_{id}` / `applyjobcost_cancel_{id}`
+async fn ask_cost_confirmation(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    request: ApplyJobRequest,
+    chars: usize,
+    threshold: i64,
+) -> Result<(), CommandError> {
+    let (tokens, secs) = estimate_applyjob_cost(chars);
     let mut embed = CreateEmbed::new()
-        .title("💰 ANALYSE SALARIALE")
-        .colour(COLOR_SALARY);
-
-    // Salaire proposé
-    if salary.offered_min.is_some() || salary.offered_max.is_some() {
-        let offered = match (salary.offered_min, salary.offered_max) {
-            (Some(min), Some(max)) => format!("{}k€ - {}k€", min / 1000, max / 1000),
-            (Some(min), None) => format!("À partir de {}k€", min / 1000),
-            (None, Some(max)) => format!("Jusqu'à {}k€", max / 1000),
-            _ => "Non spécifié".to_string(),
-        };
-        embed = embed.field("💵 Salaire annoncé", offered, false);
-    }
-
-    // Fourchette marché
-    if salary.market_median > 0 {
-        let market = format!(
-            "📉 Bas: **{}k€**\n📊 Médian: **{}k€**\n📈 Haut: **{}k€**",
-            salary.market_low / 1000,
-            salary.market_median / 1000,
-            salary.market_high / 1000
-        );
+        .title("⚠️ OFFRE TRÈS LONGUE")
+        .colour(Colour::ORANGE)
+        .description(
+            "Cette offre est bien plus longue qu'une annonce habituelle : \
+             vérifiez qu'il s'agit du bon document avant de lancer l'analyse.",
+        )
+        .field("📏 Taille", format!("{} caractères (seuil : {})", chars, threshold), true)
+        .field("🔢 Estimation", format!("≈ {} tokens", tokens), true)
+        .field("⏱️ Durée estimée", format!("≈ {} min", secs.div_ceil(60)), true);
+    if secs > 600 {
         embed = embed.field(
-            format!("📊 Marché ({}) ", salary.currency),
-            market,
+            "⏳ Délai",
+            "L'analyse risque de dépasser la limite de 10 minutes : raccourcissez l'offre si possible.",
             false,
         );
     }
 
-    if !salary.analysis.is_empty() {
-        embed = embed.field("📝 Analyse", &salary.analysis, false);
-    }
+    let key = interaction.id();
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("applyjobcost_run_{}", key))
+            .label("Lancer l'analyse")
+            .emoji('▶')
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!("applyjobcost_cancel_{}", key))
+            .label("Annuler")
+            .style(ButtonStyle::Secondary),
+    ]);
 
-    // Conseils de négociation
-    if !salary.negotiation_tips.is_empty() {
-        let tips = salary
-            .negotiation_tips
-            .iter()
-            .take(3)
-            .map(|t| format!("💡 {}", t))
-            .collect::<Vec<_>>()
-            .join("\n");
-        embed = embed.field("💡 Conseils de négociation", tips, false);
+    let store = pending_apply_jobs(ctx).await?;
+    {
+        let mut store = lock_recovering(&store, "pending apply jobs");
+        store.retain(|_, p| p.created_at.elapsed() < PENDING_ANALYSIS_TTL);
+        store.insert(key, PendingApplyJob {
+            user_id: interaction.user().id,
+            request,
+            created_at: std::time::Instant::now(),
+        });
     }
-
-    embed
+    interaction
+        .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed).components(vec![buttons]))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    info!("Long job description ({} chars) waiting for confirmation from user {}", chars, interaction.user().id);
+    Ok(())
 }
 
-fn build_progress_bar(value: u32, max: u32) -> String {
-    let percentage = (value as f32 / max as f32 * 10.0).round() as usize;
-    let filled = "█".repeat(percentage.min(10));
-    let empty = "░".repeat(10 - percentage.min(10));
-    format!("{}{}", filled, empty)
+/// /applyjob en attente d'une confirmation (coût estimé d'une offre très longue, CV illisible)
+pub struct PendingApplyJob {
+    user_id: UserId,
+    request: ApplyJobRequest,
+    created_at: std::time::Instant,
 }
 
-fn build_tracking_embed_progress(
-    current_step: &str,
-    synthesis: Option<&JobSynthesis>,
-    thread_id: Option<u64>,
-) -> CreateEmbed {
-    let mut embed = CreateEmbed::new()
-        .title("🔄 ANALYSE EN COURS")
-        .colour(COLOR_TRACKING);
-
-    if let Some(s) = synthesis {
-        embed = embed
-            .field("🏢 Entreprise", &s.company, true)
-            .field("💼 Poste", &s.title, true);
-    }
-
-    embed = embed.field("⏳ Étape actuelle", current_step, false);
+/// Clé TypeMap des /applyjob en attente de confirmation (par ID d'interaction)
+pub struct PendingApplyJobsKey;
 
-    if let Some(tid) = thread_id {
-        embed = embed.field(
-            "📋 Détails",
-            format!("Consultez le thread <#{}> pour les résultats détaillés", tid),
-            false,
-        );
-    }
+impl TypeMapKey for PendingApplyJobsKey {
+    type Value = Arc<Mutex<HashMap<u64, PendingApplyJob>>>;
+}
 
-    embed
+async fn pending_apply_jobs(ctx: &Context) -> Result<Arc<Mutex<HashMap<u64, PendingApplyJob>>>, CommandError> {
+    ctx.data
+        .read()
+        .await
+        .get::<PendingApplyJobsKey>()
+        .cloned()
+        .ok_or_else(|| CommandError::Internal("Pending apply jobs not found".to_string()))
 }
 
-fn build_tracking_embed_complete(
-    synthesis: &JobSynthesis,
-    match_score: u32,
-    has_cv: bool,
-    cv_generated: bool,
-    thread_id: u64,
-    application_id: i64,
-    status: &str,
-) -> CreateEmbed {
-    let score_bar = build_progress_bar(match_score, 100);
-    let score_emoji = if match_score >= 70 {
-        "🟢"
-    } else if match_score >= 40 {
-        "🟡"
+/// Gère les boutons `applyjobcost_run_{id}` / `applyjobcost_cancel_{id}` de la confirmation du coût
+/// et `applyjobnocv_retry_{id}` / `applyjobnocv_run_{id}` / `applyjobnocv_cancel_{id}` du CV illisible
+pub async fn handle_applyjob_cost_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let custom_id = component.data.custom_id.as_str();
+    let (action, key) = if let Some(key) = custom_id.strip_prefix("applyjobcost_run_") {
+        (PendingApplyJobAction::Run, key)
+    } else if let Some(key) = custom_id.strip_prefix("applyjobnocv_run_") {
+        (PendingApplyJobAction::RunWithoutCv, key)
+    } else if let Some(key) = custom_id.strip_prefix("applyjobnocv_retry_") {
+        (PendingApplyJobAction::RetryExtraction, key)
+    } else if let Some(key) = custom_id.strip_prefix("applyjobcost_cancel_")
+        .or_else(|| custom_id.strip_prefix("applyjobnocv_cancel_"))
+    {
+        (PendingApplyJobAction::Cancel, key)
     } else {
-        "🔴"
+        return Err(CommandError::InvalidInput(format!("Unknown button: {}", custom_id)));
+    };
+    let key: u64 = key
+        .parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid interaction ID".to_string()))?;
+
+    let store = pending_apply_jobs(ctx).await?;
+    let pending = {
+        let mut store = lock_recovering(&store, "pending apply jobs");
+        match store.get(&key) {
+            Some(p) if p.user_id != component.user.id => {
+                return Err(CommandError::Unauthorized("Cette analyse ne vous appartient pas".to_string()));
+            }
+            Some(p) if p.created_at.elapsed() >= PENDING_ANALYSIS_TTL => {
+                store.remove(&key);
+                None
+            }
+            Some(_) => store.remove(&key),
+            None => None,
+        }
     };
 
-    let cv_status = if cv_generated {
-        "✅ CV personnalisé généré"
-    } else if has_cv {
-        "⚠️ Erreur de génération"
-    } else {
-        "❌ Aucun CV (utilisez `/sendcv`)"
+    let Some(pending) = pending else {
+        let response = CreateInteractionResponseMessage::new()
+            .content("⏱️ Cette confirmation a expiré. Relancez `/applyjob` pour analyser l'offre.")
+            .embeds(vec![])
+            .components(vec![]);
+        return component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()));
     };
 
-    let status_display = get_status_display(status);
+    if action == PendingApplyJobAction::Cancel {
+        info!("Pending /applyjob cancelled by user {}", component.user.id);
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ Analyse annulée. Aucun appel n'a été effectué.")
+            .embeds(vec![])
+            .components(vec![]);
+        return component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+    }
 
-    CreateEmbed::new()
-        .title("📊 SUIVI DE CANDIDATURE")
-        .colour(COLOR_TRACKING)
-        .field("🏢 Entreprise", &synthesis.company, true)
-        .field("💼 Poste", &synthesis.title, true)
-        .field("📍 Lieu", &synthesis.location, true)
-        .field(
-            "🎯 Score de compatibilité",
-            format!("{} {} **{}%**", score_emoji, score_bar, match_score),
-            false,
-        )
-        .field("📄 CV", cv_status, true)
-        .field("📌 Statut", status_display, true)
-        .field(
-            "📋 Résultats détaillés",
-            format!("👉 <#{}>", thread_id),
-            false,
-        )
-        .footer(serenity::all::CreateEmbedFooter::new(format!("ID: {}", application_id)))
-}
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    component
+        .edit_response(&ctx.http, EditInteractionResponse::new().components(vec![]))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
 
-fn get_status_display(status: &str) -> &'static str {
-    match status {
-        "generated" => "📝 Générée",
-        "applied" => "📤 Postulée",
-        "interview" => "🗓️ Entretien",
-        "offer" => "🎉 Offre reçue",
-        "rejected" => "❌ Refusée",
-        "accepted" => "✅ Acceptée",
-        _ => "❓ Inconnu",
+    let mut request = pending.request;
+    match action {
+        PendingApplyJobAction::RunWithoutCv => request.without_cv = true,
+        PendingApplyJobAction::RetryExtraction => {
+            // En cas de nouvel échec, le garde-fou de run_apply_job repropose les options
+            let db = get_database(ctx).await?;
+            let cv = db.get_active_cv(component.user.id.get() as i64).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+            if let Some(cv) = cv {
+                let claude_client = get_claude_client(ctx).await?;
+                if reextract_cv_text(&claude_client, &db, &cv).await.is_none() {
+                    warn!("Re-extraction failed for CV {} (user {})", cv.id, component.user.id);
+                }
+            }
+        }
+        PendingApplyJobAction::Run | PendingApplyJobAction::Cancel => {}
     }
+    run_tracked_apply_job(ctx, TrackingInteraction::Component(component), request).await
 }
 
-fn build_status_buttons(application_id: i64, current_status: &str) -> Vec<CreateActionRow> {
-    let buttons_row1 = CreateActionRow::Buttons(vec![
-        CreateButton::new(format!("status_{}_{}", application_id, "applied"))
-            .label("📤 Postulée")
-            .style(if current_status == "applied" {
-                ButtonStyle::Success
-            } else {
-                ButtonStyle::Secondary
-            })
-            .disabled(current_status == "applied"),
-        CreateButton::new(format!("status_{}_{}", application_id, "interview"))
-            .label("🗓️ Entretien")
-            .style(if current_status == "interview" {
-                ButtonStyle::Success
-            } else {
-                ButtonStyle::Primary
-            })
-            .disabled(current_status == "interview"),
-        CreateButton::new(format!("status_{}_{}", application_id, "offer"))
-            .label("🎉 Offre")
-            .style(if current_status == "offer" {
-                ButtonStyle::Success
-            } else {
-                ButtonStyle::Primary
-            })
-            .disabled(current_status == "offer"),
-    ]);
+/// Choix possibles sur un /applyjob en attente (coût estimé ou CV illisible)
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PendingApplyJobAction {
+    Run,
+    RunWithoutCv,
+    RetryExtraction,
+    Cancel,
+}
 
-    let buttons_row2 = CreateActionRow::Buttons(vec![
-        CreateButton::new(format!("status_{}_{}", application_id, "accepted"))
-            .label("✅ Acceptée")
-            .style(ButtonStyle::Success)
-            .disabled(current_status == "accepted"),
-        CreateButton::new(format!("status_{}_{}", application_id, "rejected"))
-            .label("❌ Refusée")
-            .style(ButtonStyle::Danger)
-            .disabled(current_status == "rejected"),
+/// Met un /applyjob en attente lorsque le CV actif n'a aucun texte exploitable : sans ce
+/// garde-fou, l'analyse tournerait sur un texte de remplacement et le score serait proche de 0 %.
+async fn ask_empty_cv_confirmation(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    request: ApplyJobRequest,
+    cv: &BaseCv,
+) -> Result<(), CommandError> {
+    let embed = CreateEmbed::new()
+        .title("⚠️ TEXTE DU CV INDISPONIBLE")
+        .colour(Colour::ORANGE)
+        .description(format!(
+            "Le texte de votre CV actif `{}` n'a pas pu être extrait : l'analyse donnerait \
+             un score de correspondance quasi nul.\n\n\
+             • **Ré-extraire** : nouvelle tentative d'extraction, puis analyse\n\
+             • **Continuer sans CV** : analyse de l'offre seule\n\n\
+             Si le problème persiste, renvoyez votre CV avec `/sendcv` (PDF contenant du texte).",
+            cv.original_name
+        ));
+
+    let key = interaction.id();
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("applyjobnocv_retry_{}", key))
+            .label("Ré-extraire")
+            .emoji('🔄')
+            .style(ButtonStyle::Primary),
+        CreateButton::new(format!("applyjobnocv_run_{}", key))
+            .label("Continuer sans CV")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new(format!("applyjobnocv_cancel_{}", key))
+            .label("Annuler")
+            .style(ButtonStyle::Secondary),
     ]);
 
-    vec![buttons_row1, buttons_row2]
+    let store = pending_apply_jobs(ctx).await?;
+    {
+        let mut store = lock_recovering(&store, "pending apply jobs");
+        store.retain(|_, p| p.created_at.elapsed() < PENDING_ANALYSIS_TTL);
+        store.insert(key, PendingApplyJob {
+            user_id: interaction.user().id,
+            request,
+            created_at: std::time::Instant::now(),
+        });
+    }
+    interaction
+        .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed).components(vec![buttons]))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    info!("CV {} has no usable text, waiting for a choice from user {}", cv.id, interaction.user().id);
+    Ok(())
 }
 
-/// Reconstruit l'embed de suivi à partir d'une application existante
-#[allow(clippy::too_many_arguments)]
-pub fn rebuild_tracking_embed_from_status(
-    company: &str,
-    title: &str,
-    location: &str,
-    match_score: u32,
-    has_cv: bool,
-    thread_id: Option<u64>,
-    application_id: i64,
-    status: &str,
-) -> CreateEmbed {
-    let score_bar = build_progress_bar(match_score, 100);
-    let score_emoji = if match_score >= 70 {
-        "🟢"
-    } else if match_score >= 40 {
-        "🟡"
+async fn run_apply_job(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    request: ApplyJobRequest,
+) -> Result<(), CommandError> {
+    // CV actif sans texte exploitable : demander quoi faire avant le pipeline coûteux
+    if !request.without_cv {
+        let db = get_database(ctx).await?;
+        let cv = db.get_active_cv(interaction.user().id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        if let Some(cv) = cv {
+            if readable_cv_text(&cv).await.is_none() {
+                return ask_empty_cv_confirmation(ctx, interaction, request, &cv).await;
+            }
+        }
+    }
+
+    let ApplyJobRequest {
+        job_description,
+        job_url,
+        company_override,
+        title_override,
+        location_override,
+        fit_level,
+        language,
+        currency,
+        notes,
+        link_to,
+        output_channel,
+        pdf_style,
+        preview,
+        skip_salary,
+        suggest_roles,
+        save_synthesis_pdf,
+        private_thread,
+        minimal_tracking,
+        seniority,
+        anonymize,
+        stored_synthesis,
+        without_cv,
+    } = request;
+    let user_id = interaction.user().id;
+    // Le thread peut être créé ailleurs ; l'embed de suivi reste dans le salon de la commande
+    let channel_id = output_channel.unwrap_or(interaction.channel_id());
+    let claude_client = get_claude_client(ctx).await?;
+    let db = get_database(ctx).await?;
+    let config = get_config(ctx).await?;
+
+    // Langue de sortie : option explicite, sinon langue de l'offre (français par défaut)
+    let detected_language = detect_language(&job_description);
+    let language = language.unwrap_or_else(|| detected_language.unwrap_or("fr").to_string());
+    info!("Job posting language: detected {:?}, output {}", detected_language, language);
+
+    // Vérifier la version précédente avant de lancer l'analyse
+    if let Some(parent_id) = link_to {
+        let owner = db.get_application_owner(parent_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        if owner != Some(user_id.get() as i64) {
+            return send_error_response(
+                ctx,
+                interaction,
+                &format!("Candidature #{} introuvable (option `link_to`).", parent_id),
+            )
+            .await;
+        }
+    }
+
+    if channel_id != interaction.channel_id() {
+        if let Err(message) = check_thread_channel(ctx, interaction.guild_id(), channel_id).await {
+            return send_error_response(ctx, interaction, &message).await;
+        }
+    }
+
+    // Envoyer un embed de suivi initial dans le canal principal
+    let initial_tracking_embed = build_tracking_embed_progress("Synthèse de l'offre...", None, None);
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(initial_tracking_embed),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    let private_thread = match private_thread {
+        Some(private) => private,
+        None => db.get_settings(user_id.get() as i64).await
+            .map(|settings| settings.private_by_default)
+            .unwrap_or_else(|e| {
+                warn!("Failed to load settings for user {}: {}", user_id, e);
+                false
+            }),
+    };
+
+    // 1. Récupérer le CV de l'utilisateur depuis la DB (ignoré si l'utilisateur l'a choisi)
+    let user_cv = if without_cv {
+        info!("Running /applyjob without CV for user {}", user_id);
+        None
     } else {
-        "🔴"
+        db.get_active_cv(user_id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
     };
 
-    let cv_status = if has_cv {
-        "✅ CV personnalisé"
+    // 2. Analyser l'offre. Sans aperçu, synthèse + compétences + salaire en un seul appel ;
+    // avec aperçu, seule la synthèse est nécessaire avant confirmation. Une synthèse
+    // réutilisée (from_application) ou skip_salary (l'appel combiné inclut toujours le
+    // salaire) laissent les étapes suivantes aux appels séparés.
+    let mut timings = PipelineTimings::default();
+    let combined = if preview || skip_salary || stored_synthesis.is_some() {
+        None
     } else {
-        "❌ Aucun CV"
+        let cv_content = backend_cv_content(user_cv.as_ref(), user_id, anonymize).await;
+        let declared_skills = user_cv.as_ref().map(|cv| cv.declared_skills()).unwrap_or_default();
+        let started = std::time::Instant::now();
+        let analysis = claude_client
+            .analyze_all(&job_description, &cv_content.text, notes.as_deref(), &declared_skills, Some(&language), currency.as_deref(), seniority.as_deref())
+            .await;
+        timings.record("Synthèse + compétences + salaire", BACKEND_CLAUDE_COMBINED, started);
+        match analysis {
+            Ok(analysis) => Some(analysis),
+            Err(e) => {
+                warn!("Combined analysis failed, falling back to separate calls: {}", e);
+                None
+            }
+        }
+    };
+    let (mut synthesis, precomputed) = match (stored_synthesis, combined) {
+        (Some(synthesis), _) => (synthesis, None),
+        (None, Some(analysis)) => (analysis.synthesis, Some((analysis.skills_match, Some(analysis.salary_analysis)))),
+        (None, None) => {
+            let started = std::time::Instant::now();
+            let result = claude_client.synthesize_job_offer(&job_description, Some(&language)).await;
+            timings.record("Synthèse", BACKEND_CLAUDE, started);
+            match result {
+                Ok(s) => (s, None),
+                Err(e) => {
+                    error!("Failed to synthesize job offer: {}", e);
+                    return send_error_response(
+                        ctx,
+                        interaction,
+                        &format!("Erreur lors de la synthèse: {}\n{}", e, e.user_hint()),
+                    )
+                    .await;
+                }
+            }
+        }
     };
 
-    let status_display = get_status_display(status);
+    // Lieu corrigé par l'utilisateur : il remplace le lieu extrait partout (candidature, embeds)
+    // et l'analyse salariale de l'appel combiné, faite sur le lieu extrait, est refaite
+    let precomputed = match location_override {
+        Some(location) => {
+            info!("Location override: {} (extracted: {})", location, synthesis.location);
+            synthesis.location = location;
+            precomputed.map(|(skills, _)| (skills, None))
+        }
+        None => precomputed,
+    };
 
-    let mut embed = CreateEmbed::new()
-        .title("📊 SUIVI DE CANDIDATURE")
-        .colour(COLOR_TRACKING)
-        .field("🏢 Entreprise", company, true)
-        .field("💼 Poste", title, true)
-        .field("📍 Lieu", location, true)
-        .field(
-            "🎯 Score de compatibilité",
-            format!("{} {} **{}%**", score_emoji, score_bar, match_score),
-            false,
+    // Sauvegarder la candidature en DB
+    let cv_id = user_cv.as_ref().map(|cv| cv.id); // None si pas de CV → FK nullable
+    // Utiliser les overrides fournis par l'utilisateur en priorité sur la synthèse
+    let final_title = title_override.as_deref().unwrap_or(&synthesis.title);
+    let final_company = company_override.as_deref().unwrap_or(&synthesis.company);
+
+    // Avertir si une candidature similaire existe déjà (noms approximatifs)
+    let threshold = config.duplicate_threshold;
+    match db.list_applications(user_id.get() as i64, None, 500).await {
+        Ok(existing) => {
+            let duplicates: Vec<String> = existing
+                .iter()
+                .filter(|app| {
+                    matching::is_probable_duplicate(
+                        final_company,
+                        final_title,
+                        app.company.as_deref().unwrap_or(""),
+                        app.job_title.as_deref().unwrap_or(""),
+                        threshold,
+                    )
+                })
+                .take(5)
+                .map(|app| {
+                    format!(
+                        "• `#{}` {} — {} ({}, {})",
+                        app.id,
+                        app.company.as_deref().unwrap_or("N/A"),
+                        app.job_title.as_deref().unwrap_or("N/A"),
+                        get_status_display(&app.status),
+                        safe_truncate_bytes(&app.created_at, 10),
+                    )
+                })
+                .collect();
+
+            if !duplicates.is_empty() {
+                info!("Possible duplicate application for user {}: {} match(es)", user_id, duplicates.len());
+                let warning = format!(
+                    "⚠️ **Candidature similaire déjà enregistrée**\n{}\n\nL'analyse continue, mais vérifiez qu'il ne s'agit pas d'un doublon.",
+                    duplicates.join("\n")
+                );
+                if let Err(e) = interaction
+                    .create_followup(
+                        &ctx.http,
+                        CreateInteractionResponseFollowup::new().content(warning).ephemeral(true),
+                    )
+                    .await
+                {
+                    warn!("Failed to send duplicate warning: {}", e);
+                }
+            }
+        }
+        Err(e) => warn!("Failed to check for duplicate applications: {}", e),
+    }
+
+    let application_id = db
+        .create_application(
+            user_id.get() as i64,
+            cv_id,
+            Some(final_title),
+            Some(final_company),
+            Some(&synthesis.location),
+            job_url.as_deref(),
+            &job_description,
+        ).await
+        .map_err(|e| CommandError::Internal(format!("Failed to save application: {}", e)))?;
+    if let Err(e) = db.start_application_pipeline(application_id, channel_id.get() as i64).await {
+        warn!("Failed to save pipeline start of application {}: {}", application_id, e);
+    }
+
+    match serde_json::to_string(&synthesis) {
+        Ok(json) => {
+            if let Err(e) = db.update_application_synthesis_json(application_id, &json).await {
+                warn!("Failed to save application synthesis: {}", e);
+            }
+        }
+        Err(e) => warn!("Failed to serialize application synthesis: {}", e),
+    }
+    if let Some(detected) = detected_language {
+        if let Err(e) = db.update_application_language(application_id, detected).await {
+            warn!("Failed to save application language: {}", e);
+        }
+    }
+    if let Some(ref currency) = currency {
+        if let Err(e) = db.update_application_currency(application_id, currency).await {
+            warn!("Failed to save application currency: {}", e);
+        }
+    }
+
+    // Conserver les avantages de l'offre pour /benefits
+    if !synthesis.benefits.is_empty() {
+        if let Err(e) = db.update_application_benefits(application_id, &synthesis.benefits).await {
+            warn!("Failed to save application benefits: {}", e);
+        }
+    }
+
+    // Sauvegarder les notes si fournies
+    if let Some(ref notes_text) = notes {
+        if let Err(e) = db.update_application_notes(application_id, notes_text).await {
+            warn!("Failed to save application notes: {}", e);
+        }
+    }
+
+    if minimal_tracking {
+        if let Err(e) = db.set_application_minimal_tracking(application_id, true).await {
+            warn!("Failed to save tracking visibility of application {}: {}", application_id, e);
+        }
+    }
+
+    if anonymize {
+        if let Err(e) = db.set_application_anonymize_cv(application_id, true).await {
+            warn!("Failed to save CV anonymization of application {}: {}", application_id, e);
+        }
+    }
+
+    if let Some(ref level) = seniority {
+        if let Err(e) = db.update_application_seniority(application_id, level).await {
+            warn!("Failed to save seniority of application {}: {}", application_id, e);
+        }
+    }
+
+    let analysis_options = AnalysisOptions {
+        fit_level: fit_level as i32,
+        output_language: Some(language.clone()),
+        pdf_template: pdf_style.template.clone(),
+        pdf_accent_color: pdf_style.accent_color.clone(),
+        skip_salary,
+        suggest_roles,
+        save_synthesis_pdf,
+    };
+    if let Err(e) = db.update_application_analysis_options(application_id, &analysis_options).await {
+        warn!("Failed to save analysis options of application {}: {}", application_id, e);
+    }
+
+    info!("Created application {} for user {}", application_id, user_id);
+
+    if let Some(parent_id) = link_to {
+        if let Err(e) = db.set_application_parent(application_id, parent_id).await {
+            warn!("Failed to link application {} to {}: {}", application_id, parent_id, e);
+        }
+    }
+
+    let (final_company, final_title) = (final_company.to_string(), final_title.to_string());
+    let pending = PendingAnalysis {
+        application_id,
+        user_id,
+        channel_id,
+        synthesis,
+        final_company,
+        final_title,
+        job_description,
+        job_url,
+        user_cv,
+        fit_level,
+        language,
+        currency,
+        notes,
+        link_to,
+        pdf_style,
+        precomputed,
+        skip_salary,
+        suggest_roles,
+        save_synthesis_pdf,
+        private_thread,
+        minimal_tracking,
+        seniority,
+        anonymize,
+        previous_thread: None,
+        retry: false,
+        timings,
+        created_at: std::time::Instant::now(),
+    };
+
+    if preview {
+        let embed = if pending.minimal_tracking {
+            build_preview_embed_minimal(application_id)
+        } else {
+            build_preview_embed(&pending.synthesis, &pending.final_company, &pending.final_title)
+        };
+        let buttons = CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("applyjob_proceed_{}", application_id))
+                .label("Lancer l'analyse")
+                .emoji('▶')
+                .style(ButtonStyle::Success),
+            CreateButton::new(format!("applyjob_cancel_{}", application_id))
+                .label("Annuler")
+                .style(ButtonStyle::Secondary),
+        ]);
+        store_pending_analysis(ctx, pending).await?;
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(embed).components(vec![buttons]),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        info!("Application {} waiting for preview confirmation", application_id);
+        return Ok(());
+    }
+
+    let application_id = pending.application_id;
+    let tracking = interaction;
+    if let Err(e) = run_analysis(ctx, tracking, pending).await {
+        error!("Analysis failed for application {}: {}", application_id, e);
+        return show_retry(ctx, tracking, application_id, &format!("❌ **Erreur**: {}", e)).await;
+    }
+    Ok(())
+}
+
+async fn create_public_thread(ctx: &Context, channel_id: ChannelId, name: &str) -> Result<GuildChannel, CommandError> {
+    channel_id
+        .create_thread(
+            &ctx.http,
+            CreateThread::new(name)
+                .kind(ChannelType::PublicThread)
+                .auto_archive_duration(serenity::all::AutoArchiveDuration::OneDay),
         )
-        .field("📄 CV", cv_status, true)
-        .field("📌 Statut", status_display, true);
+        .await
+        .map_err(|e| CommandError::Internal(format!("Failed to create thread: {}", e)))
+}
 
-    if let Some(tid) = thread_id {
-        embed = embed.field(
-            "📋 Résultats détaillés",
-            format!("👉 <#{}>", tid),
-            false,
-        );
+/// Fil privé non invitable, avec l'utilisateur comme seul membre (en plus du bot)
+async fn create_private_thread(
+    ctx: &Context,
+    channel_id: ChannelId,
+    name: &str,
+    user_id: UserId,
+) -> serenity::Result<GuildChannel> {
+    let thread = channel_id
+        .create_thread(
+            &ctx.http,
+            CreateThread::new(name)
+                .kind(ChannelType::PrivateThread)
+                .invitable(false)
+                .auto_archive_duration(serenity::all::AutoArchiveDuration::OneDay),
+        )
+        .await?;
+    if let Err(e) = thread.id.add_thread_member(&ctx.http, user_id).await {
+        // Fil inutilisable sans l'utilisateur : le supprimer pour repasser en public
+        let _ = thread.id.delete(&ctx.http).await;
+        return Err(e);
     }
+    Ok(thread)
+}
 
-    embed.footer(serenity::all::CreateEmbedFooter::new(format!("ID: {}", application_id)))
+/// Thread d'une analyse précédente, s'il existe encore (supprimé ou inaccessible : None)
+async fn existing_thread(ctx: &Context, thread_id: ChannelId) -> Option<GuildChannel> {
+    match thread_id.to_channel(&ctx.http).await {
+        Ok(channel) => channel.guild(),
+        Err(e) => {
+            info!("Previous thread {} unavailable, creating a new one: {}", thread_id, e);
+            None
+        }
+    }
 }
 
-/// Exporte la fonction pour construire les boutons (utilisée par le handler)
-pub fn get_status_buttons(application_id: i64, current_status: &str) -> Vec<CreateActionRow> {
-    build_status_buttons(application_id, current_status)
+/// Texte exploitable d'un CV : texte extrait, sinon fichier lisible tel quel (CV texte)
+async fn readable_cv_text(cv: &BaseCv) -> Option<String> {
+    if let Some(extracted) = cv.extracted_text.as_deref().filter(|t| !t.trim().is_empty()) {
+        return Some(extracted.to_string());
+    }
+    match tokio::fs::read_to_string(&cv.file_path).await {
+        Ok(content) if !content.trim().is_empty() => Some(content),
+        _ => None,
+    }
+}
+
+/// Texte du CV envoyé à Claude (texte extrait, sinon fichier brut, sinon message explicatif)
+async fn cv_content_for(user_cv: Option<&BaseCv>, user_id: UserId) -> String {
+    match user_cv {
+        Some(cv) => match readable_cv_text(cv).await {
+            Some(content) => {
+                info!("Using CV {} text for user {}", cv.id, user_id);
+                content
+            }
+            None => {
+                warn!("No usable text for CV {}", cv.id);
+                format!("CV: {} (texte non extrait - réuploadez votre CV avec /sendcv)", cv.original_name)
+            }
+        },
+        None => {
+            info!("No CV found for user {}", user_id);
+            "CV non fourni - analyse basée sur l'offre uniquement".to_string()
+        }
+    }
+}
+
+/// Texte du CV envoyé à Claude, avec nom (profil structuré, sinon première ligne du CV), emails et
+/// téléphones masqués si `anonymize` ; `restore` réinsère les coordonnées dans le CV généré
+async fn backend_cv_content(user_cv: Option<&BaseCv>, user_id: UserId, anonymize: bool) -> Redaction {
+    let content = cv_content_for(user_cv, user_id).await;
+    let name = user_cv.and_then(BaseCv::profile).and_then(|p| p.name);
+    let redaction = anonymize_if(anonymize, &content, name.as_deref());
+    if anonymize {
+        info!("Anonymized CV text for user {} ({} value(s) masked)", user_id, redaction.count());
+    }
+    redaction
 }
 
-async fn send_error_response(
+/// Étapes coûteuses du pipeline (thread, compétences, salaire, CV) sur une candidature déjà créée
+async fn run_analysis(
     ctx: &Context,
-    interaction: &CommandInteraction,
-    message: &str,
+    interaction: TrackingInteraction<'_>,
+    pending: PendingAnalysis,
 ) -> Result<(), CommandError> {
+    let PendingAnalysis {
+        application_id,
+        user_id,
+        channel_id,
+        synthesis,
+        final_company,
+        final_title,
+        job_description,
+        job_url,
+        user_cv,
+        fit_level,
+        language,
+        currency,
+        notes,
+        link_to,
+        pdf_style,
+        precomputed,
+        skip_salary,
+        suggest_roles,
+        save_synthesis_pdf,
+        private_thread,
+        minimal_tracking,
+        seniority,
+        anonymize,
+        previous_thread,
+        retry,
+        mut timings,
+        ..
+    } = pending;
+    // Mode minimal : ni entreprise ni poste dans l'embed du salon, tout reste dans le thread
+    let tracked_synthesis = (!minimal_tracking).then_some(&synthesis);
+    let claude_client = get_claude_client(ctx).await?;
+    let db = get_database(ctx).await?;
+    let config = get_config(ctx).await?;
+
+    if !retry {
+        events::emit(config.event_webhook_url.as_deref(), ApplicationEvent::created(
+            user_id.get() as i64,
+            application_id,
+            Some(&final_company),
+            "generated",
+        ));
+    }
+
+    // Thread pour les résultats détaillés : celui d'une analyse précédente s'il existe encore,
+    // sinon un nouveau thread (score inconnu à ce stade)
+    let reused_thread = match previous_thread {
+        Some(thread_id) => existing_thread(ctx, thread_id).await,
+        None => None,
+    };
+    let thread_name = format_thread_name(&config.thread_name_template, &final_company, &final_title, None, "generated");
+
+    let (thread, private_fallback) = match reused_thread {
+        Some(thread) => {
+            info!("Reusing thread {} for job application {}", thread.id, application_id);
+            (thread, false)
+        }
+        None if private_thread => match create_private_thread(ctx, channel_id, &thread_name, user_id).await {
+            Ok(thread) => (thread, false),
+            Err(e) => {
+                warn!("Private thread unavailable in {}, falling back to public: {}", channel_id, e);
+                (create_public_thread(ctx, channel_id, &thread_name).await?, true)
+            }
+        },
+        None => (create_public_thread(ctx, channel_id, &thread_name).await?, false),
+    };
+
+    info!("Using thread {} for job application", thread.id);
+
+    // Sauvegarder le thread_id en DB
+    if let Err(e) = db.update_application_thread(application_id, thread.id.get() as i64).await {
+        warn!("Failed to save thread_id: {}", e);
+    }
+    record_pipeline_stage(&db, application_id, Some(db::PIPELINE_THREAD)).await;
+
+    // Mettre à jour l'embed de suivi avec le lien vers le thread
+    let tracking_embed = build_tracking_embed_progress(
+        "Analyse des compétences...",
+        tracked_synthesis,
+        Some(thread.id.get()),
+    );
     interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(tracking_embed),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    // Envoyer l'embed de synthèse dans le thread
+    let synthesis_embed = build_synthesis_embed(&synthesis);
+    thread
+        .send_message(&ctx.http, CreateMessage::new().embed(synthesis_embed))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    let cv_content = backend_cv_content(user_cv.as_ref(), user_id, anonymize).await;
+    if anonymize && user_cv.is_some() && !cv_content.masks_name() {
+        let notice = "⚠️ **Anonymisation** : nom introuvable dans le CV (ni dans le profil, ni en première ligne), \
+                      il a été transmis en clair. Emails et téléphones restent masqués.";
+        if let Err(e) = thread.send_message(&ctx.http, CreateMessage::new().content(notice)).await {
+            warn!("Failed to send anonymization notice: {}", e);
+        }
+    }
+
+    let has_cv = user_cv.is_some();
+    let (precomputed_skills, precomputed_salary) = match precomputed {
+        Some((skills, salary)) => (Some(skills), salary),
+        None => (None, None),
+    };
+
+    // Analyse des compétences (déjà faite si l'appel combiné a réussi)
+    let skills_match = match precomputed_skills {
+        Some(s) => Ok(s),
+        None => {
+            let declared_skills = user_cv.as_ref().map(|cv| cv.declared_skills()).unwrap_or_default();
+            let started = std::time::Instant::now();
+            let result = claude_client
+                .match_skills(&job_description, &cv_content.text, notes.as_deref(), &declared_skills, Some(&language), seniority.as_deref())
+                .await;
+            timings.record("Compétences", BACKEND_CLAUDE, started);
+            result
+        }
+    };
+    let skills_failed = skills_match.is_err();
+    let skills_match = match skills_match {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to match skills: {}", e);
+            let default_highlight = if has_cv {
+                "Analyse en cours...".to_string()
+            } else {
+                "Uploadez votre CV avec `/sendcv` pour une analyse personnalisée".to_string()
+            };
+            SkillsMatch {
+                match_score: 0,
+                matched_skills: vec![],
+                missing_skills: vec![],
+                highlights: vec![default_highlight],
+                recommendations: vec![],
+            }
+        }
+    };
+
+    // Mettre à jour le tracking
+    let tracking_embed = build_tracking_embed_progress(
+        if skip_salary { "Finalisation..." } else { "Analyse salariale..." },
+        tracked_synthesis,
+        Some(thread.id.get()),
+    );
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(tracking_embed),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    // Envoyer l'embed des compétences dans le thread
+    let skills_embed = build_skills_embed(&skills_match);
+    thread
+        .send_message(&ctx.http, CreateMessage::new().embed(skills_embed))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    record_pipeline_stage(&db, application_id, Some(db::PIPELINE_SKILLS)).await;
+
+    // Pistes de recherche (option suggest_roles) : un échec n'interrompt pas l'analyse
+    if suggest_roles {
+        let mut profile_skills = user_cv.as_ref().map(|cv| cv.declared_skills()).unwrap_or_default();
+        if profile_skills.is_empty() {
+            profile_skills = skills_match.matched_skills.iter()
+                .filter(|s| s.is_match)
+                .map(|s| s.skill.clone())
+                .collect();
+        }
+        let started = std::time::Instant::now();
+        let suggestions = claude_client.suggest_roles(&synthesis, &profile_skills, &language).await;
+        timings.record("Pistes de recherche", BACKEND_CLAUDE, started);
+        match suggestions {
+            Ok(suggestions) => {
+                if let Err(e) = thread
+                    .send_message(&ctx.http, CreateMessage::new().embed(build_role_suggestions_embed(&suggestions)))
+                    .await
+                {
+                    warn!("Failed to send role suggestions: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to suggest roles for application {}: {}", application_id, e),
+        }
+    }
+
+    // Synthèse de l'offre en pièce jointe (option save_synthesis_pdf) : Markdown si le PDF échoue
+    if save_synthesis_pdf {
+        let archived = JobSynthesis {
+            company: final_company.clone(),
+            title: final_title.clone(),
+            ..synthesis.clone()
+        };
+        let stem = format!("Offre_{}", safe_file_stem(&archived.title));
+        let started = std::time::Instant::now();
+        let synthesis_pdf = claude_client.generate_synthesis_pdf(&archived, &pdf_style).await;
+        timings.record("PDF de la synthèse", BACKEND_PDF, started);
+        let message = match synthesis_pdf {
+            Ok(pdf_bytes) => CreateMessage::new()
+                .content("🗂️ Synthèse de l'offre pour vos archives")
+                .add_file(CreateAttachment::bytes(pdf_bytes, format!("{}.pdf", stem))),
+            Err(e) => {
+                warn!("Failed to generate synthesis PDF for application {}: {}", application_id, e);
+                CreateMessage::new()
+                    .content("🗂️ Synthèse de l'offre pour vos archives (PDF indisponible, version Markdown)")
+                    .add_file(CreateAttachment::bytes(synthesis_markdown(&archived).into_bytes(), format!("{}.md", stem)))
+            }
+        };
+        if let Err(e) = thread.send_message(&ctx.http, message).await {
+            warn!("Failed to send synthesis archive: {}", e);
+        }
+    }
+
+    // 3. Analyse salariale (option skip_salary : ni appel ni embed)
+    if skip_salary {
+        info!("Salary analysis skipped for application {}", application_id);
+    } else {
+        let salary_analysis = match precomputed_salary {
+            Some(s) => Ok(s),
+            None => {
+                let started = std::time::Instant::now();
+                let result = claude_client
+                    .analyze_salary(&job_description, Some(&synthesis.location), Some(&language), currency.as_deref())
+                    .await;
+                timings.record("Analyse salariale", BACKEND_CLAUDE, started);
+                result
+            }
+        };
+        let salary_analysis = match salary_analysis {
+            Ok(mut s) => {
+                // Montants demandés dans la devise choisie : l'étiquette suit l'option
+                if let Some(ref currency) = currency {
+                    s.currency = currency.clone();
+                }
+                if let Err(e) = db.update_application_salary(
+                    application_id,
+                    s.offered_min.map(|v| v as i32),
+                    s.offered_max.map(|v| v as i32),
+                    &s.analysis,
+                    Some(s.market_low as i32),
+                    Some(s.market_median as i32),
+                    Some(s.market_high as i32),
+                    &s.currency,
+                ).await {
+                    warn!("Failed to save salary analysis: {}", e);
+                }
+                s
+            }
+            Err(e) => {
+                error!("Failed to analyze salary: {}", e);
+                SalaryAnalysis {
+                    offered_min: None,
+                    offered_max: None,
+                    market_low: 0,
+                    market_median: 0,
+                    market_high: 0,
+                    currency: currency.clone().unwrap_or_else(|| DEFAULT_CURRENCY.to_string()),
+                    analysis: format!("Analyse non disponible: {}", e),
+                    negotiation_tips: vec![],
+                }
+            }
+        };
+
+        // Envoyer l'embed salarial dans le thread, comparé à l'objectif de l'utilisateur
+        let salary_target = match db.get_settings(user_id.get() as i64).await {
+            Ok(settings) => settings.salary_target(),
+            Err(e) => {
+                warn!("Failed to load salary target for user {}: {}", user_id, e);
+                None
+            }
+        };
+        let salary_embed = build_salary_embed(&salary_analysis, salary_target);
+        thread
+            .send_message(&ctx.http, CreateMessage::new().embed(salary_embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    }
+    record_pipeline_stage(&db, application_id, Some(db::PIPELINE_SALARY)).await;
+
+    // 4. Génération de CV personnalisé si CV disponible
+    let cv_generated = if has_cv {
+        // Mettre à jour le tracking
+        let tracking_embed = build_tracking_embed_progress(
+            "Génération du CV personnalisé...",
+            tracked_synthesis,
+            Some(thread.id.get()),
+        );
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(tracking_embed),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        let started = std::time::Instant::now();
+        let generated_cv = claude_client
+            .generate_tailored_cv(&cv_content.text, &synthesis, &skills_match, fit_level, &language, notes.as_deref())
+            .await;
+        timings.record("CV personnalisé", BACKEND_CLAUDE, started);
+        match generated_cv {
+            Ok(generated_cv) => {
+                let fit_display = match fit_level {
+                    1 => "1️⃣ Standard",
+                    2 => "2️⃣ Modéré",
+                    3 => "3️⃣ Laxiste",
+                    _ => "1️⃣ Standard",
+                };
+                let lang_display = match language.as_str() {
+                    "fr" => "🇫🇷 Français",
+                    "en" => "🇬🇧 English",
+                    "es" => "🇪🇸 Español",
+                    "de" => "🇩🇪 Deutsch",
+                    _ => "🇫🇷 Français",
+                };
+                let mut embed = CreateEmbed::new()
+                    .title("📄 CV PERSONNALISÉ GÉNÉRÉ")
+                    .colour(COLOR_CV)
+                    .field("🎚️ Adaptation", fit_display, true)
+                    .field("🌐 Langue", lang_display, true)
+                    .field("📝 Résumé des adaptations", &generated_cv.summary, false);
+
+                if !generated_cv.adaptations.is_empty() {
+                    let adaptations = generated_cv
+                        .adaptations
+                        .iter()
+                        .take(5)
+                        .map(|a| format!("• {}", a))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    embed = embed.field("✨ Modifications apportées", adaptations, false);
+                }
+
+                // Coordonnées masquées avant l'envoi : réinsérées ici, avant l'enregistrement et le PDF
+                let restored_cv = cv_content.restore(generated_cv.get_content());
+                let cv_text = restored_cv.as_str();
+                let username = &interaction.user().name;
+
+                // Conserver le texte généré pour /comparecv
+                if let Err(e) = db.save_generated_cv_text(application_id, cv_text, &generated_cv.adaptations).await {
+                    warn!("Failed to save generated CV text: {}", e);
+                }
+
+                // Heuristique: si le contenu est long, forcer single_page dès la première tentative
+                let try_single_page_first = cv_text.len() > 8000;
+                if try_single_page_first {
+                    info!("CV content is large ({} bytes), using single_page=true directly", cv_text.len());
+                }
+
+                let started = std::time::Instant::now();
+                let pdf = claude_client
+                    .generate_pdf(cv_text, username, &synthesis.title, &synthesis.company, try_single_page_first, &pdf_style)
+                    .await;
+                match pdf {
+                    Ok(pdf_bytes) => {
+                        let page_count = ClaudeClient::count_pdf_pages(&pdf_bytes);
+                        let final_pdf = if !try_single_page_first && page_count > 1 {
+                            info!("CV PDF has {} pages, retrying with single_page=true", page_count);
+                            match claude_client
+                                .generate_pdf(cv_text, username, &synthesis.title, &synthesis.company, true, &pdf_style)
+                                .await
+                            {
+                                Ok(retry_bytes) => {
+                                    let retry_pages = ClaudeClient::count_pdf_pages(&retry_bytes);
+                                    if retry_pages > 1 {
+                                        warn!("CV PDF still has {} pages after single_page retry", retry_pages);
+                                    }
+                                    retry_bytes
+                                }
+                                Err(e) => {
+                                    warn!("Single-page PDF retry failed: {}, using original", e);
+                                    pdf_bytes
+                                }
+                            }
+                        } else {
+                            pdf_bytes
+                        };
+                        timings.record("PDF du CV", BACKEND_PDF, started);
+
+                        let filename = format!("CV_{}_{}.pdf", username, safe_file_stem(&synthesis.title));
+                        let attachment = CreateAttachment::bytes(final_pdf, &filename);
+
+                        embed = embed.field(
+                            "📥 Téléchargement",
+                            "✅ PDF généré et joint ci-dessous!",
+                            false,
+                        );
+
+                        thread
+                            .send_message(
+                                &ctx.http,
+                                CreateMessage::new().embed(embed).add_file(attachment),
+                            )
+                            .await
+                            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+                        true
+                    }
+                    Err(e) => {
+                        timings.record("PDF du CV", BACKEND_PDF, started);
+                        warn!("Failed to generate PDF: {}", e);
+                        embed = embed.field(
+                            "📥 Téléchargement",
+                            format!("⚠️ Génération PDF échouée: {}", e),
+                            false,
+                        );
+
+                        thread
+                            .send_message(&ctx.http, CreateMessage::new().embed(embed))
+                            .await
+                            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+                        true
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to generate tailored CV: {}", e);
+                let embed = CreateEmbed::new()
+                    .title("📄 Génération de CV")
+                    .description(format!("Erreur lors de la génération: {}", e))
+                    .colour(COLOR_CV)
+                    .field(
+                        "💡 Conseil",
+                        "Réessayez avec `/applyjob` ou vérifiez que votre CV est bien uploadé.",
+                        false,
+                    );
+
+                thread
+                    .send_message(&ctx.http, CreateMessage::new().embed(embed))
+                    .await
+                    .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+                false
+            }
+        }
+    } else {
+        let embed = CreateEmbed::new()
+            .title("📄 Génération de CV")
+            .description("Pour générer un CV personnalisé, uploadez d'abord votre CV de base.")
+            .colour(COLOR_CV)
+            .field(
+                "Prochaines étapes",
+                "1. `/sendcv` - Uploader votre CV\n2. `/applyjob` - Relancer l'analyse\n3. Télécharger votre CV personnalisé",
+                false,
+            );
+
+        thread
+            .send_message(&ctx.http, CreateMessage::new().embed(embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        false
+    };
+    record_pipeline_stage(&db, application_id, Some(db::PIPELINE_CV)).await;
+
+    // Mettre à jour l'analyse en DB
+    if let Err(e) = db.update_application_analysis(
+        application_id,
+        &synthesis.summary,
+        &serde_json::to_string(&synthesis.key_requirements).unwrap_or_default(),
+        &serde_json::to_string(&skills_match.matched_skills).unwrap_or_default(),
+        &serde_json::to_string(&skills_match.missing_skills).unwrap_or_default(),
+        skills_match.match_score as i32,
+    ).await {
+        warn!("Failed to update application analysis: {}", e);
+    }
+    record_pipeline_stage(&db, application_id, None).await;
+
+    // Progression du score sur les versions précédentes de la candidature
+    if link_to.is_some() {
+        match db.get_application_versions(application_id).await {
+            Ok(versions) if versions.len() > 1 => {
+                let embed = CreateEmbed::new()
+                    .title("📈 HISTORIQUE DES VERSIONS")
+                    .colour(COLOR_SKILLS)
+                    .description(format_score_progression(&versions, application_id));
+                if let Err(e) = thread.send_message(&ctx.http, CreateMessage::new().embed(embed)).await {
+                    warn!("Failed to send version history: {}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to load application versions: {}", e),
+        }
+    }
+
+    // Durées des étapes, pour repérer ce qui ralentit une analyse
+    if let Some(embed) = build_timings_embed(&timings) {
+        if let Err(e) = thread.send_message(&ctx.http, CreateMessage::new().embed(embed)).await {
+            warn!("Failed to send pipeline timings: {}", e);
+        }
+    }
+
+    // Mettre à jour l'embed de suivi final dans le canal principal avec les boutons
+    let mut final_tracking_embed = if minimal_tracking {
+        build_tracking_embed_minimal(Some(thread.id.get()), application_id, "generated")
+    } else {
+        build_tracking_embed_complete(
+            &synthesis,
+            skills_match.match_score,
+            has_cv,
+            cv_generated,
+            thread.id.get(),
+            application_id,
+            "generated",
+        )
+    };
+    // Verdict : seulement si le score a été calculé à partir d'un CV
+    if has_cv && !skills_failed && !minimal_tracking {
+        final_tracking_embed = final_tracking_embed.description(format!("**{}**", match_verdict(&skills_match)));
+    }
+    if skip_salary && !minimal_tracking {
+        final_tracking_embed = final_tracking_embed.field("💰 Salaire", "⏭️ Analyse ignorée (`skip_salary`)", true);
+    }
+    // Score faible : seulement s'il a été calculé à partir d'un CV
+    if has_cv && !skills_failed && !minimal_tracking {
+        let threshold = match db.get_settings(user_id.get() as i64).await {
+            Ok(settings) => settings.min_fit_warn,
+            Err(e) => {
+                warn!("Failed to load fit threshold for user {}: {}", user_id, e);
+                UserSettings::default().min_fit_warn
+            }
+        };
+        if let Some(warning) = low_fit_warning(&skills_match, threshold) {
+            final_tracking_embed = final_tracking_embed.field(
+                format!("⚠️ Correspondance faible ({}% < {}%)", skills_match.match_score, threshold),
+                warning,
+                false,
+            );
+        }
+    }
+    if private_fallback {
+        final_tracking_embed = final_tracking_embed.field(
+            "🔓 Fil public",
+            "⚠️ Les fils privés ne sont pas disponibles dans ce salon : les résultats sont visibles par tous.",
+            false,
+        );
+    } else if private_thread {
+        final_tracking_embed = final_tracking_embed.field("🔒 Fil privé", "Visible par vous seul", true);
+    }
+    let mut action_rows = build_status_buttons(application_id, "generated", job_url.as_deref());
+    if has_cv && !cv_generated {
+        action_rows.push(build_retry_row(application_id));
+    }
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(final_tracking_embed)
+                .components(action_rows),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    // Score connu : renommer le thread si le modèle l'affiche
+    if thread_name_is_dynamic(&config.thread_name_template) {
+        let score = (has_cv && !skills_failed).then_some(skills_match.match_score);
+        let name = format_thread_name(&config.thread_name_template, &final_company, &final_title, score, "generated");
+        if name != thread_name {
+            rename_thread(ctx, thread.id, &name).await;
+        }
+    }
+
+    info!("Job application analysis completed for user {}", user_id);
+
+    Ok(())
+}
+
+// ============================================================================
+// Embed builders
+// ============================================================================
+
+/// Exigences clés affichées dans l'embed de synthèse (le reste est compté)
+const MAX_SYNTHESIS_REQUIREMENTS: usize = 12;
+
+fn build_synthesis_embed(synthesis: &JobSynthesis) -> CreateEmbed {
+    let mut fields = vec![
+        ("🏢 Entreprise".to_string(), synthesis.company.clone(), true),
+        ("💼 Poste".to_string(), synthesis.title.clone(), true),
+        ("📍 Lieu".to_string(), synthesis.location.clone(), true),
+        ("📝 Contrat".to_string(), synthesis.contract_type.clone(), true),
+    ];
+
+    if let Some(salary) = &synthesis.salary_range {
+        fields.push(("💰 Salaire".to_string(), salary.clone(), true));
+    }
+
+    let requirements = if synthesis.key_requirements.is_empty() {
+        "Non spécifié".to_string()
+    } else {
+        list_field_value(
+            synthesis.key_requirements.iter().map(|r| format!("• {}", r)).collect(),
+            "\n",
+            MAX_SYNTHESIS_REQUIREMENTS,
+        )
+    };
+
+    fields.push(("🎯 Compétences clés".to_string(), requirements, false));
+    fields.push(("📖 Résumé".to_string(), synthesis.summary.clone(), false));
+
+    let embed = CreateEmbed::new()
+        .title("📋 SYNTHÈSE DE L'OFFRE")
+        .colour(COLOR_SYNTHESIS);
+    add_capped_fields(embed, fields, MAX_EMBED_FIELDS)
+}
+
+/// Nom de fichier sûr tiré d'un intitulé (lettres, chiffres et tirets, espaces remplacés par `_`)
+fn safe_file_stem(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
+        .collect::<String>()
+        .replace(' ', "_")
+}
+
+/// Synthèse de l'offre en Markdown (repli de l'option save_synthesis_pdf)
+fn synthesis_markdown(synthesis: &JobSynthesis) -> String {
+    let mut md = format!("# {} — {}\n\n", synthesis.title, synthesis.company);
+    md.push_str(&format!("- **Lieu :** {}\n", synthesis.location));
+    md.push_str(&format!("- **Contrat :** {}\n", synthesis.contract_type));
+    if let Some(salary) = synthesis.salary_range.as_deref().filter(|s| !s.trim().is_empty()) {
+        md.push_str(&format!("- **Salaire :** {}\n", salary));
+    }
+    md.push_str(&format!("\n## Résumé\n\n{}\n", synthesis.summary));
+    for (heading, items) in [
+        ("Exigences clés", &synthesis.key_requirements),
+        ("Responsabilités", &synthesis.responsibilities),
+        ("Avantages", &synthesis.benefits),
+    ] {
+        if !items.is_empty() {
+            md.push_str(&format!("\n## {}\n\n", heading));
+            for item in items {
+                md.push_str(&format!("- {}\n", item));
+            }
+        }
+    }
+    md
+}
+
+/// Embed des postes proches et mots-clés de recherche (option suggest_roles)
+fn build_role_suggestions_embed(suggestions: &RoleSuggestions) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("🔎 PISTES DE RECHERCHE")
+        .colour(COLOR_SKILLS);
+    if !suggestions.titles.is_empty() {
+        let titles = suggestions.titles.iter().map(|t| format!("• {}", t)).collect();
+        embed = embed.field("Postes proches", list_field_value(titles, "\n", suggestions.titles.len()), false);
+    }
+    if !suggestions.keywords.is_empty() {
+        let keywords = suggestions.keywords.iter().map(|k| format!("`{}`", k)).collect();
+        embed = embed.field("Mots-clés de recherche", list_field_value(keywords, " · ", suggestions.keywords.len()), false);
+    }
+    embed
+}
+
+fn build_skills_embed(skills: &SkillsMatch) -> CreateEmbed {
+    let score_bar = build_progress_bar(skills.match_score, 100);
+
+    let mut embed = CreateEmbed::new()
+        .title("🎯 ANALYSE DE COMPATIBILITÉ")
+        .colour(COLOR_SKILLS)
+        .field(
+            "Score de matching",
+            format!("{} **{}%**", score_bar, skills.match_score),
+            false,
+        );
+
+    // Compétences matchées
+    if !skills.matched_skills.is_empty() {
+        let matched = skills
+            .matched_skills
+            .iter()
+            .map(|s| {
+                let icon = if s.is_match { "✅" } else { "⚠️" };
+                format!("{} **{}**: {} → Requis: {}", icon, s.skill, s.cv_level, s.required)
+            })
+            .collect();
+        embed = embed.field("✅ Compétences matchées", list_field_value(matched, "\n", 5), false);
+    }
+
+    // Compétences manquantes
+    if !skills.missing_skills.is_empty() {
+        let missing = skills
+            .missing_skills
+            .iter()
+            .map(|s| format!("❌ **{}** ({})", s.skill, s.importance))
+            .collect();
+        embed = embed.field("❌ Compétences manquantes", list_field_value(missing, "\n", 5), false);
+    }
+
+    // Points forts
+    if !skills.highlights.is_empty() {
+        let highlights = skills.highlights.iter().map(|h| format!("⭐ {}", h)).collect();
+        embed = embed.field("⭐ Points forts à mettre en avant", list_field_value(highlights, "\n", 3), false);
+    }
+
+    embed
+}
+
+/// Montant en milliers avec le symbole de la devise ("45k€", "60k$", "80k CHF")
+fn format_salary_k(amount: u32, currency: &str) -> String {
+    match currency.to_uppercase().as_str() {
+        "EUR" => format!("{}k€", amount / 1000),
+        "USD" => format!("{}k$", amount / 1000),
+        "GBP" => format!("{}k£", amount / 1000),
+        code => format!("{}k {}", amount / 1000, code),
+    }
+}
+
+fn build_salary_embed(salary: &SalaryAnalysis, target: Option<(i64, Option<i64>)>) -> CreateEmbed {
+    // L'objectif de /settarget est en euros : pas de comparaison dans une autre devise
+    let comparison = target
+        .filter(|_| salary.currency.eq_ignore_ascii_case(DEFAULT_CURRENCY))
+        .map(|(min, max)| compare_salary_to_target(salary, min, max));
+    let k = |amount: u32| format_salary_k(amount, &salary.currency);
+    let title = match comparison {
+        Some((true, _)) => "💰 ANALYSE SALARIALE — ⚠️ SOUS VOTRE OBJECTIF",
+        _ => "💰 ANALYSE SALARIALE",
+    };
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .colour(COLOR_SALARY);
+
+    // Salaire proposé
+    if salary.offered_min.is_some() || salary.offered_max.is_some() {
+        let offered = match (salary.offered_min, salary.offered_max) {
+            (Some(min), Some(max)) => format!("{} - {}", k(min), k(max)),
+            (Some(min), None) => format!("À partir de {}", k(min)),
+            (None, Some(max)) => format!("Jusqu'à {}", k(max)),
+            _ => "Non spécifié".to_string(),
+        };
+        embed = embed.field("💵 Salaire annoncé", offered, false);
+    }
+
+    // Fourchette marché
+    if salary.market_median > 0 {
+        let market = format!(
+            "📉 Bas: **{}**\n📊 Médian: **{}**\n📈 Haut: **{}**",
+            k(salary.market_low),
+            k(salary.market_median),
+            k(salary.market_high)
+        );
+        embed = embed.field(
+            format!("📊 Marché ({}) ", salary.currency),
+            market,
+            false,
+        );
+    }
+
+    if let Some((_, text)) = comparison {
+        embed = embed.field("🎯 Votre objectif", text, false);
+    }
+
+    if !salary.analysis.is_empty() {
+        embed = embed.field("📝 Analyse", field_value(&salary.analysis), false);
+    }
+
+    // Conseils de négociation
+    if !salary.negotiation_tips.is_empty() {
+        let tips = salary.negotiation_tips.iter().map(|t| format!("💡 {}", t)).collect();
+        embed = embed.field("💡 Conseils de négociation", list_field_value(tips, "\n", 3), false);
+    }
+
+    embed
+}
+
+/// Compétences manquantes listées dans l'avertissement de correspondance faible
+const MAX_LOW_FIT_SKILLS: usize = 3;
+
+/// Compétences manquantes, requises d'abord (« nice-to-have » en dernier)
+fn missing_by_importance(skills: &SkillsMatch) -> Vec<&MissingSkill> {
+    let mut missing: Vec<_> = skills.missing_skills.iter().collect();
+    missing.sort_by_key(|s| s.importance.eq_ignore_ascii_case("nice-to-have"));
+    missing
+}
+
+/// Verdict en une phrase pour l'embed de suivi, calculé localement :
+/// « Bonne adéquation — vous couvrez 8/10 exigences clés, principale lacune : Kubernetes »
+fn match_verdict(skills: &SkillsMatch) -> String {
+    let verdict = if skills.match_score >= 70 {
+        "Bonne adéquation"
+    } else if skills.match_score >= 40 {
+        "Adéquation partielle"
+    } else {
+        "Faible adéquation"
+    };
+
+    let covered = skills.matched_skills.iter().filter(|s| s.is_match).count();
+    let total = skills.matched_skills.len() + skills.missing_skills.len();
+    if total == 0 {
+        return verdict.to_string();
+    }
+    let gap = match missing_by_importance(skills).first() {
+        Some(top) => format!("principale lacune : {}", top.skill),
+        None => "aucune lacune identifiée".to_string(),
+    };
+    format!("{} — vous couvrez {}/{} exigences clés, {}", verdict, covered, total, gap)
+}
+
+/// Avertissement affiché quand le score est sous le seuil `min_fit_warn` (0 = désactivé) :
+/// principales compétences manquantes (requises d'abord) et conseil
+fn low_fit_warning(skills: &SkillsMatch, threshold: i64) -> Option<String> {
+    if threshold <= 0 || i64::from(skills.match_score) >= threshold {
+        return None;
+    }
+
+    let missing = missing_by_importance(skills);
+    let mut text = String::new();
+    if !missing.is_empty() {
+        let top = missing
+            .iter()
+            .take(MAX_LOW_FIT_SKILLS)
+            .map(|s| format!("• {}", s.skill))
+            .collect::<Vec<_>>()
+            .join("\n");
+        text.push_str(&format!("**Principales lacunes :**\n{}\n\n", top));
+    }
+    text.push_str(
+        "💡 Ce poste correspond peu à votre profil : reconsidérez la candidature, \
+         ou mettez en avant ces compétences (projets, formations) dans votre CV et votre lettre.",
+    );
+    Some(text)
+}
+
+/// Compare le salaire annoncé et la médiane du marché à l'objectif de l'utilisateur.
+/// Retourne (offre sous l'objectif, texte du champ). Sans salaire annoncé, l'offre est
+/// jugée sur la médiane du marché.
+fn compare_salary_to_target(salary: &SalaryAnalysis, target_min: i64, target_max: Option<i64>) -> (bool, String) {
+    let k = |amount: i64| format!("{}k€", amount / 1000);
+    let mut lines = vec![match target_max {
+        Some(max) => format!("Objectif : **{} - {}**", k(target_min), k(max)),
+        None => format!("Objectif : **≥ {}**", k(target_min)),
+    }];
+
+    let offered_low = salary.offered_min.or(salary.offered_max).map(i64::from);
+    let offered_high = salary.offered_max.or(salary.offered_min).map(i64::from);
+    let median = i64::from(salary.market_median);
+
+    let below = match (offered_low, offered_high) {
+        (Some(low), Some(high)) => {
+            if high < target_min {
+                lines.push(format!("⚠️ Offre sous votre objectif ({} max)", k(high)));
+                true
+            } else if target_max.is_some_and(|max| low > max) {
+                lines.push("🚀 Offre au-dessus de votre objectif".to_string());
+                false
+            } else if low >= target_min {
+                lines.push("✅ Offre dans votre objectif".to_string());
+                false
+            } else {
+                lines.push(format!("➖ Bas de fourchette ({}) sous votre objectif : à négocier", k(low)));
+                false
+            }
+        }
+        _ => {
+            lines.push("❔ Salaire non annoncé".to_string());
+            median > 0 && median < target_min
+        }
+    };
+
+    if median > 0 {
+        if median < target_min {
+            lines.push(format!("📉 Médiane du marché ({}) sous votre objectif", k(median)));
+        } else {
+            lines.push(format!("📊 Médiane du marché ({}) au niveau de votre objectif", k(median)));
+        }
+    }
+
+    (below, lines.join("\n"))
+}
+
+/// Une ligne par version : score, évolution par rapport à la version précédente et statut
+fn format_score_progression(versions: &[crate::db::ApplicationVersion], current_id: i64) -> String {
+    let mut previous: Option<i32> = None;
+    versions
+        .iter()
+        .enumerate()
+        .map(|(i, version)| {
+            let score = match version.match_score {
+                Some(score) => format!("{}%", score),
+                None => "—".to_string(),
+            };
+            let delta = match (previous, version.match_score) {
+                (Some(before), Some(after)) if after > before => format!(" (📈 +{})", after - before),
+                (Some(before), Some(after)) if after < before => format!(" (📉 {})", after - before),
+                (Some(_), Some(_)) => " (=)".to_string(),
+                _ => String::new(),
+            };
+            if version.match_score.is_some() {
+                previous = version.match_score;
+            }
+            let marker = if version.id == current_id { " ← actuelle" } else { "" };
+            format!(
+                "**v{}** `#{}` — {}{} · {} · {}{}",
+                i + 1,
+                version.id,
+                score,
+                delta,
+                get_status_display(&version.status),
+                safe_truncate_bytes(&version.created_at, 10),
+                marker
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn build_progress_bar(value: u32, max: u32) -> String {
+    let percentage = (value as f32 / max as f32 * 10.0).round() as usize;
+    let filled = "█".repeat(percentage.min(10));
+    let empty = "░".repeat(10 - percentage.min(10));
+    format!("{}{}", filled, empty)
+}
+
+fn build_tracking_embed_progress(
+    current_step: &str,
+    synthesis: Option<&JobSynthesis>,
+    thread_id: Option<u64>,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("🔄 ANALYSE EN COURS")
+        .colour(COLOR_TRACKING);
+
+    if let Some(s) = synthesis {
+        embed = embed
+            .field("🏢 Entreprise", &s.company, true)
+            .field("💼 Poste", &s.title, true);
+    }
+
+    embed = embed.field("⏳ Étape actuelle", current_step, false);
+
+    if let Some(tid) = thread_id {
+        embed = embed.field(
+            "📋 Détails",
+            format!("Consultez le thread <#{}> pour les résultats détaillés", tid),
+            false,
+        );
+    }
+
+    embed
+}
+
+fn build_tracking_embed_complete(
+    synthesis: &JobSynthesis,
+    match_score: u32,
+    has_cv: bool,
+    cv_generated: bool,
+    thread_id: u64,
+    application_id: i64,
+    status: &str,
+) -> CreateEmbed {
+    let score_bar = build_progress_bar(match_score, 100);
+    let score_emoji = if match_score >= 70 {
+        "🟢"
+    } else if match_score >= 40 {
+        "🟡"
+    } else {
+        "🔴"
+    };
+
+    let cv_status = if cv_generated {
+        "✅ CV personnalisé généré"
+    } else if has_cv {
+        "⚠️ Erreur de génération"
+    } else {
+        "❌ Aucun CV (utilisez `/sendcv`)"
+    };
+
+    let status_display = get_status_display(status);
+
+    CreateEmbed::new()
+        .title("📊 SUIVI DE CANDIDATURE")
+        .colour(COLOR_TRACKING)
+        .field("🏢 Entreprise", &synthesis.company, true)
+        .field("💼 Poste", &synthesis.title, true)
+        .field("📍 Lieu", &synthesis.location, true)
+        .field(
+            "🎯 Score de compatibilité",
+            format!("{} {} **{}%**", score_emoji, score_bar, match_score),
+            false,
+        )
+        .field("📄 CV", cv_status, true)
+        .field("📌 Statut", status_display, true)
+        .field(
+            "📋 Résultats détaillés",
+            format!("👉 <#{}>", thread_id),
+            false,
+        )
+        .footer(serenity::all::CreateEmbedFooter::new(format!("ID: {}", application_id)))
+}
+
+/// Embed de suivi réduit (`visibility:minimal`) : statut et lien du thread, sans entreprise,
+/// poste ni score, pour les salons partagés
+fn build_tracking_embed_minimal(thread_id: Option<u64>, application_id: i64, status: &str) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("📊 SUIVI DE CANDIDATURE")
+        .colour(COLOR_TRACKING)
+        .description("✅ Analyse terminée")
+        .field("📌 Statut", get_status_display(status), true);
+    if let Some(tid) = thread_id {
+        embed = embed.field("📋 Résultats détaillés", format!("👉 <#{}>", tid), false);
+    }
+    embed.footer(serenity::all::CreateEmbedFooter::new(format!("ID: {}", application_id)))
+}
+
+fn get_status_display(status: &str) -> &'static str {
+    match status {
+        "generated" => "📝 Générée",
+        "applied" => "📤 Postulée",
+        "interview" => "🗓️ Entretien",
+        "offer" => "🎉 Offre reçue",
+        "rejected" => "❌ Refusée",
+        "accepted" => "✅ Acceptée",
+        _ => "❓ Inconnu",
+    }
+}
+
+/// Identifiant du champ de texte du modal de clôture
+const OUTCOME_NOTES_INPUT: &str = "outcome_notes";
+
+/// Longueur maximale des notes de clôture saisies dans le modal
+const MAX_OUTCOME_NOTES_CHARS: u16 = 1000;
+
+/// Statuts terminaux pour lesquels le bouton ouvre le modal des notes de clôture
+pub fn is_terminal_status(status: &str) -> bool {
+    matches!(status, "accepted" | "rejected")
+}
+
+/// Modal `outcome_{application_id}_{status}` demandant des notes de clôture facultatives ;
+/// le statut n'est appliqué qu'à la validation du modal
+pub fn build_outcome_notes_modal(application_id: i64, status: &str) -> CreateModal {
+    let (title, label, placeholder) = if status == "accepted" {
+        (
+            "✅ Candidature acceptée",
+            "Détails de l'offre (facultatif)",
+            "Salaire, date de début, conditions négociées…",
+        )
+    } else {
+        (
+            "❌ Candidature refusée",
+            "Raison du refus (facultatif)",
+            "Motif donné, retours de l'entretien, ce qui aurait pu mieux se passer…",
+        )
+    };
+    let input = CreateInputText::new(InputTextStyle::Paragraph, label, OUTCOME_NOTES_INPUT)
+        .placeholder(placeholder)
+        .required(false)
+        .max_length(MAX_OUTCOME_NOTES_CHARS);
+    CreateModal::new(format!("outcome_{}_{}", application_id, status), title)
+        .components(vec![CreateActionRow::InputText(input)])
+}
+
+/// Notes de clôture saisies dans le modal (None si le champ est vide)
+fn outcome_notes_from_modal(data: &ModalInteractionData) -> Option<String> {
+    modal_text_value(data, OUTCOME_NOTES_INPUT)
+}
+
+/// Valeur d'un champ de texte d'un modal, sans espaces superflus (None si vide ou absent)
+fn modal_text_value(data: &ModalInteractionData, custom_id: &str) -> Option<String> {
+    data.components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == custom_id => input.value.clone(),
+            _ => None,
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Ajoute les notes de clôture aux notes existantes de la candidature, préfixées par le statut
+fn append_outcome_notes(existing: Option<&str>, status: &str, outcome: &str) -> String {
+    let entry = format!("{} : {}", get_status_display(status), outcome.trim());
+    match existing.map(str::trim).filter(|notes| !notes.is_empty()) {
+        Some(notes) => format!("{}\n\n{}", notes, entry),
+        None => entry,
+    }
+}
+
+/// Validation du modal `outcome_{application_id}_{status}` : applique le statut terminal
+/// et ajoute les notes de clôture saisies à la candidature
+pub struct OutcomeNotesModal;
+
+#[async_trait]
+impl ModalHandler for OutcomeNotesModal {
+    fn custom_id_prefix(&self) -> &'static str {
+        "outcome_"
+    }
+
+    async fn handle(&self, ctx: &Context, modal: &ModalInteraction) -> Result<(), CommandError> {
+        let custom_id = modal.data.custom_id.as_str();
+        let (application_id, new_status) = custom_id
+            .strip_prefix(self.custom_id_prefix())
+            .and_then(|rest| rest.split_once('_'))
+            .ok_or_else(|| CommandError::InvalidInput(format!("Unknown modal: {}", custom_id)))?;
+        let application_id: i64 = application_id
+            .parse()
+            .map_err(|_| CommandError::InvalidInput("Invalid application ID".to_string()))?;
+        if !is_terminal_status(new_status) {
+            return Err(CommandError::InvalidInput(format!("Invalid status: {}", new_status)));
+        }
+
+        let outcome = outcome_notes_from_modal(&modal.data);
+        let response = apply_status_update(ctx, modal.user.id, application_id, new_status, outcome.as_deref()).await?;
+
+        if let Some(outcome) = outcome.as_deref() {
+            let db = get_database(ctx).await?;
+            let existing = db.get_application(application_id).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+                .and_then(|app| app.notes);
+            let notes = append_outcome_notes(existing.as_deref(), new_status, outcome);
+            db.update_application_notes(application_id, &notes).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+            info!("Outcome notes saved for application {}", application_id);
+        }
+
+        // Modal ouvert depuis un bouton : mettre à jour le message d'origine
+        modal
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+    }
+}
+
+/// Applique un changement de statut (DB, webhook d'événements) et renvoie l'embed de suivi
+/// et les boutons reconstruits
+pub async fn apply_status_update(
+    ctx: &Context,
+    user: UserId,
+    application_id: i64,
+    new_status: &str,
+    note: Option<&str>,
+) -> Result<CreateInteractionResponseMessage, CommandError> {
+    let user_id = user.get() as i64;
+
+    info!(
+        "Status update: user {} changing application {} to {}",
+        user_id, application_id, new_status
+    );
+
+    let db = get_database(ctx).await?;
+
+    // Statut précédent (pour le webhook d'événements)
+    let old_status = db.get_application(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .map(|a| a.status);
+
+    // Mettre à jour le statut en DB
+    let updated = db.update_application_status(application_id, user_id, new_status, note).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+    if !updated {
+        return Err(CommandError::NotFound("Cette candidature ne vous appartient pas ou n'existe pas.".to_string()));
+    }
+
+    // Récupérer l'application mise à jour pour reconstruire l'embed
+    let app = db
+        .get_application(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| CommandError::Internal("Application not found after update".to_string()))?;
+
+    let config = get_config(ctx).await?;
+    events::emit(config.event_webhook_url.as_deref(), ApplicationEvent::status_changed(
+        user_id,
+        application_id,
+        app.company.as_deref(),
+        old_status.as_deref(),
+        new_status,
+    ));
+
+    // Statut ou emoji dans le nom du thread : le renommer
+    refresh_thread_name(ctx, &app).await;
+
+    // Reconstruire l'embed avec le nouveau statut, dans la visibilité choisie à l'analyse
+    let thread_id = app.thread_id.map(|t| t as u64);
+    let embed = if app.minimal_tracking {
+        build_tracking_embed_minimal(thread_id, application_id, new_status)
+    } else {
+        rebuild_tracking_embed_from_status(
+            app.company.as_deref().unwrap_or("N/A"),
+            app.job_title.as_deref().unwrap_or("N/A"),
+            app.location.as_deref().unwrap_or("N/A"),
+            app.match_score.unwrap_or(0) as u32,
+            app.generated_cv_path.is_some(),
+            thread_id,
+            application_id,
+            new_status,
+        )
+    };
+
+    // Reconstruire les boutons
+    let buttons = get_status_buttons(application_id, new_status, app.job_url.as_deref());
+
+    info!(
+        "Successfully updated application {} to status {}",
+        application_id, new_status
+    );
+    Ok(CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .components(buttons))
+}
+
+/// Lien de l'offre utilisable dans un bouton (Discord rejette tout le message si l'URL est invalide)
+fn apply_link(job_url: Option<&str>) -> Option<&str> {
+    job_url
+        .map(str::trim)
+        .filter(|url| (url.starts_with("https://") || url.starts_with("http://")) && url.len() <= 512)
+        .filter(|url| !url.contains(char::is_whitespace))
+}
+
+fn build_status_buttons(application_id: i64, current_status: &str, job_url: Option<&str>) -> Vec<CreateActionRow> {
+    let link = apply_link(job_url);
+    let mut row1 = Vec::new();
+    // « Postuler » ouvre l'offre ; « Marquer postulée » enregistre la candidature externe
+    if let Some(url) = link {
+        row1.push(CreateButton::new_link(url).label("🔗 Postuler"));
+    }
+    row1.extend([
+        CreateButton::new(format!("status_{}_{}", application_id, "applied"))
+            .label(if link.is_some() { "📤 Marquer postulée" } else { "📤 Postulée" })
+            .style(if current_status == "applied" {
+                ButtonStyle::Success
+            } else {
+                ButtonStyle::Secondary
+            })
+            .disabled(current_status == "applied"),
+        CreateButton::new(format!("status_{}_{}", application_id, "interview"))
+            .label("🗓️ Entretien")
+            .style(if current_status == "interview" {
+                ButtonStyle::Success
+            } else {
+                ButtonStyle::Primary
+            })
+            .disabled(current_status == "interview"),
+        CreateButton::new(format!("status_{}_{}", application_id, "offer"))
+            .label("🎉 Offre")
+            .style(if current_status == "offer" {
+                ButtonStyle::Success
+            } else {
+                ButtonStyle::Primary
+            })
+            .disabled(current_status == "offer"),
+    ]);
+    let buttons_row1 = CreateActionRow::Buttons(row1);
+
+    let buttons_row2 = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("status_{}_{}", application_id, "accepted"))
+            .label("✅ Acceptée")
+            .style(ButtonStyle::Success)
+            .disabled(current_status == "accepted"),
+        CreateButton::new(format!("status_{}_{}", application_id, "rejected"))
+            .label("❌ Refusée")
+            .style(ButtonStyle::Danger)
+            .disabled(current_status == "rejected"),
+        // Rappel de relance en un clic (délai : /settings, sinon 7 jours)
+        CreateButton::new(format!("quickreminder_{}", application_id))
+            .label("⏰ Rappel de relance")
+            .style(ButtonStyle::Secondary)
+            .disabled(matches!(current_status, "accepted" | "rejected")),
+    ]);
+
+    vec![buttons_row1, buttons_row2]
+}
+
+/// Reconstruit l'embed de suivi à partir d'une application existante
+#[allow(clippy::too_many_arguments)]
+pub fn rebuild_tracking_embed_from_status(
+    company: &str,
+    title: &str,
+    location: &str,
+    match_score: u32,
+    has_cv: bool,
+    thread_id: Option<u64>,
+    application_id: i64,
+    status: &str,
+) -> CreateEmbed {
+    let score_bar = build_progress_bar(match_score, 100);
+    let score_emoji = if match_score >= 70 {
+        "🟢"
+    } else if match_score >= 40 {
+        "🟡"
+    } else {
+        "🔴"
+    };
+
+    let cv_status = if has_cv {
+        "✅ CV personnalisé"
+    } else {
+        "❌ Aucun CV"
+    };
+
+    let status_display = get_status_display(status);
+
+    let mut embed = CreateEmbed::new()
+        .title("📊 SUIVI DE CANDIDATURE")
+        .colour(COLOR_TRACKING)
+        .field("🏢 Entreprise", company, true)
+        .field("💼 Poste", title, true)
+        .field("📍 Lieu", location, true)
+        .field(
+            "🎯 Score de compatibilité",
+            format!("{} {} **{}%**", score_emoji, score_bar, match_score),
+            false,
+        )
+        .field("📄 CV", cv_status, true)
+        .field("📌 Statut", status_display, true);
+
+    if let Some(tid) = thread_id {
+        embed = embed.field(
+            "📋 Résultats détaillés",
+            format!("👉 <#{}>", tid),
+            false,
+        );
+    }
+
+    embed.footer(serenity::all::CreateEmbedFooter::new(format!("ID: {}", application_id)))
+}
+
+/// Exporte la fonction pour construire les boutons (utilisée par le handler)
+pub fn get_status_buttons(application_id: i64, current_status: &str, job_url: Option<&str>) -> Vec<CreateActionRow> {
+    build_status_buttons(application_id, current_status, job_url)
+}
+
+// ============================================================================
+// Preview (confirmation avant les étapes coûteuses)
+// ============================================================================
+
+/// Durée de validité d'un aperçu en attente de confirmation
+const PENDING_ANALYSIS_TTL: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// Candidature créée après la synthèse, en attente des étapes d'analyse
+pub struct PendingAnalysis {
+    application_id: i64,
+    user_id: UserId,
+    channel_id: ChannelId,
+    synthesis: JobSynthesis,
+    final_company: String,
+    final_title: String,
+    job_description: String,
+    job_url: Option<String>,
+    user_cv: Option<BaseCv>,
+    fit_level: u8,
+    language: String,
+    currency: Option<String>,
+    notes: Option<String>,
+    link_to: Option<i64>,
+    pdf_style: PdfStyle,
+    /// Compétences et salaire déjà obtenus par l'appel combiné /analyze
+    /// (salaire absent si le lieu a été corrigé après l'appel)
+    precomputed: Option<(SkillsMatch, Option<SalaryAnalysis>)>,
+    skip_salary: bool,
+    suggest_roles: bool,
+    save_synthesis_pdf: bool,
+    private_thread: bool,
+    minimal_tracking: bool,
+    seniority: Option<String>,
+    anonymize: bool,
+    /// Thread d'une analyse précédente (bouton « Réessayer »), réutilisé s'il existe encore
+    previous_thread: Option<ChannelId>,
+    /// Relance d'une analyse : l'événement `created` a déjà été émis au premier lancement
+    retry: bool,
+    /// Étapes déjà chronométrées (synthèse ou appel combiné)
+    timings: PipelineTimings,
+    created_at: std::time::Instant,
+}
+
+/// Service ayant traité une étape, pour le rapport de durées
+const BACKEND_CLAUDE: &str = "Claude";
+const BACKEND_CLAUDE_COMBINED: &str = "Claude, appel combiné";
+const BACKEND_PDF: &str = "serveur PDF";
+
+/// Durées des étapes du pipeline /applyjob, rapportées dans le thread en fin d'analyse.
+/// Les étapes servies par l'appel combiné ou une synthèse réutilisée n'y figurent pas
+#[derive(Debug, Clone, Default)]
+pub struct PipelineTimings {
+    /// (étape, service, durée) dans l'ordre d'exécution
+    steps: Vec<(&'static str, &'static str, std::time::Duration)>,
+}
+
+impl PipelineTimings {
+    fn record(&mut self, step: &'static str, backend: &'static str, started: std::time::Instant) {
+        self.steps.push((step, backend, started.elapsed()));
+    }
+
+    fn total(&self) -> std::time::Duration {
+        self.steps.iter().map(|(_, _, duration)| *duration).sum()
+    }
+}
+
+/// Durée lisible : « 0.8 s », « 42.0 s », « 2 min 05 s »
+fn format_step_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1} s", secs)
+    } else {
+        let secs = duration.as_secs();
+        format!("{} min {:02} s", secs / 60, secs % 60)
+    }
+}
+
+/// Une ligne par étape chronométrée
+fn format_step_timings(timings: &PipelineTimings) -> String {
+    timings
+        .steps
+        .iter()
+        .map(|(step, backend, duration)| format!("• {} — {} _({})_", step, format_step_duration(*duration), backend))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Petit embed de fin d'analyse : durée de chaque étape et total (None si rien n'a été chronométré)
+fn build_timings_embed(timings: &PipelineTimings) -> Option<CreateEmbed> {
+    if timings.steps.is_empty() {
+        return None;
+    }
+    Some(
+        CreateEmbed::new()
+            .title("⏱️ Durée des étapes")
+            .colour(COLOR_TRACKING)
+            .description(field_value(&format_step_timings(timings)))
+            .footer(CreateEmbedFooter::new(format!("Total : {}", format_step_duration(timings.total())))),
+    )
+}
+
+/// Clé TypeMap des aperçus /applyjob en attente (par ID de candidature)
+pub struct PendingAnalysesKey;
+
+impl TypeMapKey for PendingAnalysesKey {
+    type Value = Arc<Mutex<HashMap<i64, PendingAnalysis>>>;
+}
+
+async fn pending_analyses(ctx: &Context) -> Result<Arc<Mutex<HashMap<i64, PendingAnalysis>>>, CommandError> {
+    ctx.data
+        .read()
+        .await
+        .get::<PendingAnalysesKey>()
+        .cloned()
+        .ok_or_else(|| CommandError::Internal("Pending analyses not found".to_string()))
+}
+
+/// Verrouille un `std::sync::Mutex` même empoisonné : une panique pendant que le verrou
+/// était tenu ne doit pas rendre la donnée inutilisable jusqu'au redémarrage du bot
+fn lock_recovering<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        warn!("Mutex for {} was poisoned by a panic, recovering", name);
+        poisoned.into_inner()
+    })
+}
+
+async fn store_pending_analysis(ctx: &Context, pending: PendingAnalysis) -> Result<(), CommandError> {
+    let store = pending_analyses(ctx).await?;
+    let mut store = lock_recovering(&store, "pending analyses");
+    store.retain(|_, p| p.created_at.elapsed() < PENDING_ANALYSIS_TTL);
+    store.insert(pending.application_id, pending);
+    Ok(())
+}
+
+/// Interaction qui porte l'embed de suivi : la commande elle-même, le bouton de confirmation
+/// ou le formulaire de /applyjobform
+#[derive(Clone, Copy)]
+enum TrackingInteraction<'a> {
+    Command(&'a CommandInteraction),
+    Component(&'a ComponentInteraction),
+    Modal(&'a ModalInteraction),
+}
+
+impl TrackingInteraction<'_> {
+    async fn edit_response(&self, http: &Http, response: EditInteractionResponse) -> serenity::Result<Message> {
+        match self {
+            TrackingInteraction::Command(interaction) => interaction.edit_response(http, response).await,
+            TrackingInteraction::Component(interaction) => interaction.edit_response(http, response).await,
+            TrackingInteraction::Modal(interaction) => interaction.edit_response(http, response).await,
+        }
+    }
+
+    async fn create_followup(
+        &self,
+        http: &Http,
+        followup: CreateInteractionResponseFollowup,
+    ) -> serenity::Result<Message> {
+        match self {
+            TrackingInteraction::Command(interaction) => interaction.create_followup(http, followup).await,
+            TrackingInteraction::Component(interaction) => interaction.create_followup(http, followup).await,
+            TrackingInteraction::Modal(interaction) => interaction.create_followup(http, followup).await,
+        }
+    }
+
+    fn id(&self) -> u64 {
+        match self {
+            TrackingInteraction::Command(interaction) => interaction.id.get(),
+            TrackingInteraction::Component(interaction) => interaction.id.get(),
+            TrackingInteraction::Modal(interaction) => interaction.id.get(),
+        }
+    }
+
+    fn user(&self) -> &User {
+        match self {
+            TrackingInteraction::Command(interaction) => &interaction.user,
+            TrackingInteraction::Component(interaction) => &interaction.user,
+            TrackingInteraction::Modal(interaction) => &interaction.user,
+        }
+    }
+
+    fn channel_id(&self) -> ChannelId {
+        match self {
+            TrackingInteraction::Command(interaction) => interaction.channel_id,
+            TrackingInteraction::Component(interaction) => interaction.channel_id,
+            TrackingInteraction::Modal(interaction) => interaction.channel_id,
+        }
+    }
+
+    fn guild_id(&self) -> Option<GuildId> {
+        match self {
+            TrackingInteraction::Command(interaction) => interaction.guild_id,
+            TrackingInteraction::Component(interaction) => interaction.guild_id,
+            TrackingInteraction::Modal(interaction) => interaction.guild_id,
+        }
+    }
+}
+
+impl<'a> From<&'a CommandInteraction> for TrackingInteraction<'a> {
+    fn from(interaction: &'a CommandInteraction) -> Self {
+        TrackingInteraction::Command(interaction)
+    }
+}
+
+fn build_preview_embed(synthesis: &JobSynthesis, company: &str, title: &str) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("👀 APERÇU AVANT ANALYSE")
+        .description("Vérifiez les informations détectées avant de lancer l'analyse complète (compétences, salaire, CV).")
+        .colour(COLOR_TRACKING)
+        .field("🏢 Entreprise", field_value(company), true)
+        .field("💼 Poste", field_value(title), true)
+        .field("📍 Localisation", field_value(&synthesis.location), true)
+}
+
+/// Aperçu en mode minimal (visibility:minimal) : ni entreprise, ni poste, ni lieu dans le salon
+fn build_preview_embed_minimal(application_id: i64) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("👀 APERÇU AVANT ANALYSE")
+        .description("Offre lue. Lancez l'analyse complète (compétences, salaire, CV) : les détails seront publiés dans le thread.")
+        .colour(COLOR_TRACKING)
+        .footer(CreateEmbedFooter::new(format!("ID: {}", application_id)))
+}
+
+/// Gère les boutons `applyjob_proceed_{id}` / `applyjob_cancel_{id}` de l'aperçu
+pub async fn handle_applyjob_preview_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let custom_id = component.data.custom_id.as_str();
+    let (proceed, id_str) = if let Some(id) = custom_id.strip_prefix("applyjob_proceed_") {
+        (true, id)
+    } else if let Some(id) = custom_id.strip_prefix("applyjob_cancel_") {
+        (false, id)
+    } else {
+        return Err(CommandError::InvalidInput(format!("Unknown button: {}", custom_id)));
+    };
+    let application_id: i64 = id_str
+        .parse()
+        .map_err(|_| CommandError::InvalidInput("Invalid application ID".to_string()))?;
+
+    let store = pending_analyses(ctx).await?;
+    let pending = {
+        let mut store = lock_recovering(&store, "pending analyses");
+        match store.get(&application_id) {
+            Some(p) if p.user_id != component.user.id => {
+                return Err(CommandError::Unauthorized("Cette analyse ne vous appartient pas".to_string()));
+            }
+            Some(p) if p.created_at.elapsed() >= PENDING_ANALYSIS_TTL => {
+                store.remove(&application_id);
+                None
+            }
+            Some(_) => store.remove(&application_id),
+            None => None,
+        }
+    };
+
+    let Some(pending) = pending else {
+        let response = CreateInteractionResponseMessage::new()
+            .content("⏱️ Cet aperçu a expiré. Relancez `/applyjob` pour analyser l'offre.")
+            .components(vec![]);
+        return component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+    };
+
+    if !proceed {
+        let db = get_database(ctx).await?;
+        if let Err(e) = db.delete_application(application_id, component.user.id.get() as i64).await {
+            warn!("Failed to delete cancelled application {}: {}", application_id, e);
+        }
+        info!("Application {} cancelled from preview", application_id);
+        let response = CreateInteractionResponseMessage::new()
+            .content("❌ Analyse annulée. Aucun appel supplémentaire n'a été effectué.")
+            .embeds(vec![])
+            .components(vec![]);
+        return component
+            .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+    }
+
+    // Accuser réception puis éditer le message de l'aperçu au fil des étapes
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    component
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(build_tracking_embed_progress(
+                    "Création du thread...",
+                    (!pending.minimal_tracking).then_some(&pending.synthesis),
+                    None,
+                ))
+                .components(vec![]),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+    run_analysis_with_retry(ctx, component, pending).await
+}
+
+const ANALYSIS_TIMEOUT_MESSAGE: &str = "⏱️ **Délai dépassé** — Le traitement a pris plus de 10 minutes.\n\
+    Le serveur Claude est peut-être surchargé. Réessayez dans quelques instants.";
+
+/// Lance l'analyse depuis un bouton (timeout de 10 minutes) ; en cas d'échec,
+/// l'erreur est affichée avec le bouton « Réessayer »
+async fn run_analysis_with_retry(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    pending: PendingAnalysis,
+) -> Result<(), CommandError> {
+    let application_id = pending.application_id;
+    let tracking = TrackingInteraction::Component(component);
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(600),
+        run_analysis(ctx, tracking, pending),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => {
+            error!("Analysis failed for application {}: {}", application_id, e);
+            show_retry(ctx, tracking, application_id, &format!("❌ **Erreur**: {}", e)).await
+        }
+        Err(_) => show_retry(ctx, tracking, application_id, ANALYSIS_TIMEOUT_MESSAGE).await,
+    }
+}
+
+/// Bouton `retryanalysis_{id}` : relance l'analyse depuis l'offre enregistrée
+fn build_retry_row(application_id: i64) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("retryanalysis_{}", application_id))
+            .label("Réessayer")
+            .emoji('🔄')
+            .style(ButtonStyle::Primary),
+    ])
+}
+
+/// Enregistre l'étape atteinte par le pipeline (un échec n'interrompt pas l'analyse)
+async fn record_pipeline_stage(db: &Database, application_id: i64, stage: Option<&str>) {
+    if let Err(e) = db.set_application_pipeline_stage(application_id, stage).await {
+        warn!("Failed to save pipeline stage of application {}: {}", application_id, e);
+    }
+}
+
+fn pipeline_stage_display(stage: &str) -> &'static str {
+    match stage {
+        db::PIPELINE_THREAD => "création du thread",
+        db::PIPELINE_SKILLS => "analyse des compétences",
+        db::PIPELINE_SALARY => "analyse salariale",
+        db::PIPELINE_CV => "génération du CV",
+        _ => "synthèse de l'offre",
+    }
+}
+
+/// Message envoyé au démarrage pour une analyse interrompue par un redémarrage du bot,
+/// avec le bouton « Réessayer »
+pub fn interrupted_pipeline_message(app: &JobApplication) -> CreateMessage {
+    let stage = app.pipeline_stage.as_deref().unwrap_or(db::PIPELINE_CREATED);
+    CreateMessage::new()
+        .content(format!(
+            "⚠️ **Analyse interrompue** — Le bot a redémarré pendant l'analyse de la candidature `#{}` \
+            ({} chez {}), après l'étape « {} ».\n\n\
+            L'offre est conservée : cliquez sur **Réessayer** pour relancer l'analyse.",
+            app.id,
+            app.job_title.as_deref().unwrap_or("N/A"),
+            app.company.as_deref().unwrap_or("N/A"),
+            pipeline_stage_display(stage),
+        ))
+        .components(vec![build_retry_row(app.id)])
+}
+
+/// Remplace l'embed de suivi par le message d'erreur et le bouton « Réessayer »
+async fn show_retry(
+    ctx: &Context,
+    interaction: TrackingInteraction<'_>,
+    application_id: i64,
+    message: &str,
+) -> Result<(), CommandError> {
+    // L'utilisateur est averti : le balayage au démarrage n'a pas à le refaire
+    let db = get_database(ctx).await?;
+    record_pipeline_stage(&db, application_id, Some(db::PIPELINE_STALLED)).await;
+    interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(format!(
+                    "{}\n\nL'offre est conservée (candidature `#{}`) : cliquez sur **Réessayer** pour relancer l'analyse.",
+                    message, application_id
+                ))
+                .embeds(vec![])
+                .components(vec![build_retry_row(application_id)]),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    Ok(())
+}
+
+/// Gère le bouton `retryanalysis_{id}` : nouvelle synthèse et analyse complète
+/// à partir de `raw_job_description`, sans ressaisir l'offre
+pub async fn handle_retry_analysis_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let application_id: i64 = component
+        .data
+        .custom_id
+        .strip_prefix("retryanalysis_")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| CommandError::InvalidInput("Invalid application ID".to_string()))?;
+
+    let db = get_database(ctx).await?;
+    let user_id = component.user.id;
+    let app = db.get_application(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| CommandError::NotFound(format!("Candidature #{} introuvable", application_id)))?;
+    if app.user_id != user_id.get() as i64 {
+        return Err(CommandError::Unauthorized("Cette candidature ne vous appartient pas".to_string()));
+    }
+
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    component
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content("")
+                .embed(build_tracking_embed_progress("Synthèse de l'offre...", None, None))
+                .components(vec![]),
+        )
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+    info!("User {} retrying analysis of application {}", user_id, application_id);
+
+    // Salon d'origine : le bouton peut venir d'un message privé (analyse interrompue)
+    let channel_id = app.origin_channel_id
+        .map(|id| ChannelId::new(id as u64))
+        .unwrap_or(component.channel_id);
+    if let Err(e) = db.start_application_pipeline(application_id, channel_id.get() as i64).await {
+        warn!("Failed to save pipeline start of application {}: {}", application_id, e);
+    }
+
+    let claude_client = get_claude_client(ctx).await?;
+    // Options du premier lancement ; langue détectée pour les candidatures antérieures à leur enregistrement
+    let options = app.analysis_options.clone();
+    let language = options.output_language.clone()
+        .or_else(|| app.language.clone())
+        .unwrap_or_else(|| "fr".to_string());
+    // EUR est la valeur par défaut de la colonne, pas forcément un choix de l'utilisateur
+    let currency = Some(app.salary_currency.clone()).filter(|c| c != DEFAULT_CURRENCY);
+    let mut timings = PipelineTimings::default();
+    let started = std::time::Instant::now();
+    let synthesis = claude_client.synthesize_job_offer(&app.raw_job_description, Some(&language)).await;
+    timings.record("Synthèse", BACKEND_CLAUDE, started);
+    let mut synthesis = match synthesis {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Retry synthesis failed for application {}: {}", application_id, e);
+            let tracking = TrackingInteraction::Component(component);
+            return show_retry(ctx, tracking, application_id, &format!("❌ **Erreur lors de la synthèse**: {}\n{}", e, e.user_hint())).await;
+        }
+    };
+    // Lieu enregistré (éventuellement corrigé avec l'option `location`) prioritaire
+    if let Some(location) = app.location.clone().filter(|l| !l.trim().is_empty()) {
+        synthesis.location = location;
+    }
+
+    // CV de la candidature d'origine, sinon CV actif
+    let user_cv = match app.base_cv_id {
+        Some(cv_id) => db.get_cv_by_id(cv_id).await.ok().flatten(),
+        None => None,
+    };
+    let user_cv = match user_cv {
+        Some(cv) => Some(cv),
+        None => db.get_active_cv(user_id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?,
+    };
+    let private_thread = db.get_settings(user_id.get() as i64).await
+        .map(|settings| settings.private_by_default)
+        .unwrap_or_else(|e| {
+            warn!("Failed to load settings for user {}: {}", user_id, e);
+            false
+        });
+
+    let pending = PendingAnalysis {
+        application_id,
+        user_id,
+        channel_id,
+        final_company: app.company.clone().unwrap_or_else(|| synthesis.company.clone()),
+        final_title: app.job_title.clone().unwrap_or_else(|| synthesis.title.clone()),
+        synthesis,
+        job_description: app.raw_job_description,
+        job_url: app.job_url,
+        user_cv,
+        fit_level: options.fit_level as u8,
+        language,
+        currency,
+        notes: app.notes,
+        link_to: None,
+        pdf_style: PdfStyle { template: options.pdf_template, accent_color: options.pdf_accent_color },
+        precomputed: None,
+        skip_salary: options.skip_salary,
+        suggest_roles: options.suggest_roles,
+        save_synthesis_pdf: options.save_synthesis_pdf,
+        private_thread,
+        minimal_tracking: app.minimal_tracking,
+        seniority: app.seniority,
+        anonymize: app.anonymize_cv,
+        previous_thread: app.thread_id.map(|id| ChannelId::new(id as u64)),
+        retry: true,
+        timings,
+        created_at: std::time::Instant::now(),
+    };
+    run_analysis_with_retry(ctx, component, pending).await
+}
+
+/// Normalise une couleur hexadécimale ("2C3E50", "#2c3e50" -> "#2c3e50")
+fn parse_accent_color(raw: &str) -> Option<String> {
+    let hex = raw.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", hex.to_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// Vérifie que le bot peut créer un thread public dans le salon cible (option `channel`)
+async fn check_thread_channel(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+) -> Result<(), String> {
+    let guild_id = guild_id
+        .ok_or_else(|| "L'option `channel` n'est disponible que sur un serveur.".to_string())?;
+
+    let channel = channel_id
+        .to_channel(&ctx.http)
+        .await
+        .ok()
+        .and_then(|c| c.guild())
+        .filter(|c| c.guild_id == guild_id)
+        .ok_or_else(|| format!("Le salon <#{}> est inaccessible pour le bot.", channel_id))?;
+
+    if !matches!(channel.kind, ChannelType::Text | ChannelType::News) {
+        return Err(format!("Le salon <#{}> n'accepte pas de threads.", channel_id));
+    }
+
+    let bot_id = ctx.cache.current_user().id;
+    let (guild, member) = match (
+        guild_id.to_partial_guild(&ctx.http).await,
+        guild_id.member(&ctx.http, bot_id).await,
+    ) {
+        (Ok(guild), Ok(member)) => (guild, member),
+        _ => return Err("Impossible de vérifier les permissions du bot.".to_string()),
+    };
+
+    let required = Permissions::VIEW_CHANNEL
+        | Permissions::CREATE_PUBLIC_THREADS
+        | Permissions::SEND_MESSAGES_IN_THREADS;
+    if !guild.user_permissions_in(&channel, &member).contains(required) {
+        return Err(format!(
+            "Le bot ne peut pas créer de thread dans <#{}> (permissions requises : voir le salon, créer des fils publics, envoyer des messages dans les fils).",
+            channel_id
+        ));
+    }
+
+    Ok(())
+}
+
+async fn send_error_response<'a>(
+    ctx: &Context,
+    interaction: impl Into<TrackingInteraction<'a>>,
+    message: &str,
+) -> Result<(), CommandError> {
+    interaction
+        .into()
         .edit_response(
             &ctx.http,
             EditInteractionResponse::new().content(format!("❌ **Erreur**: {}", message)),
@@ -982,6 +3462,334 @@ async fn send_error_response(
     Ok(())
 }
 
+// ============================================================================
+// Interviews Command - Applications currently in interview stage
+// ============================================================================
+
+const MAX_INTERVIEWS_DISPLAYED: usize = 10;
+
+pub struct InterviewsCommand;
+
+impl InterviewsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for InterviewsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for InterviewsCommand {
+    fn name(&self) -> &'static str {
+        "interviews"
+    }
+
+    fn description(&self) -> &'static str {
+        "List your applications in interview stage, soonest first"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    async fn execute(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        let user_id = interaction.user.id.get() as i64;
+        let db = get_database(ctx).await?;
+
+        let mut apps = db.list_applications(user_id, Some("interview"), 100).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        if apps.is_empty() {
+            return send_response(
+                ctx,
+                interaction,
+                self.is_ephemeral(interaction),
+                "🗓️ Aucune candidature au stade entretien. Utilisez le bouton **Entretien** sur une candidature pour la faire apparaître ici.",
+            ).await;
+        }
+
+        sort_by_upcoming_date(&mut apps);
+
+        let mut embed = CreateEmbed::new()
+            .title(format!("🗓️ Entretiens en cours ({})", apps.len()))
+            .colour(COLOR_TRACKING);
+
+        for app in apps.iter().take(MAX_INTERVIEWS_DISPLAYED) {
+            let reminder = app
+                .reminder_date
+                .as_deref()
+                .map(|d| format!("⏰ {}", safe_truncate_bytes(d, 16)));
+            let when = match (app.interview_at.as_deref(), reminder) {
+                (Some(at), Some(reminder)) => format!("🗓️ {} • {}", format_interview_at(at), reminder),
+                (Some(at), None) => format!("🗓️ {}", format_interview_at(at)),
+                (None, Some(reminder)) => reminder,
+                (None, None) => "⏰ Pas de date".to_string(),
+            };
+            let thread = app
+                .thread_id
+                .map(|t| format!(" • <#{}>", t))
+                .unwrap_or_default();
+            embed = embed.field(
+                format!(
+                    "#{} — {} ({})",
+                    app.id,
+                    app.job_title.as_deref().unwrap_or("N/A"),
+                    app.company.as_deref().unwrap_or("N/A")
+                ),
+                format!("{}{}", when, thread),
+                false,
+            );
+        }
+
+        if apps.len() > MAX_INTERVIEWS_DISPLAYED {
+            embed = embed.footer(serenity::all::CreateEmbedFooter::new(format!(
+                "... et {} autre(s)",
+                apps.len() - MAX_INTERVIEWS_DISPLAYED
+            )));
+        }
+
+        // Boutons d'accès direct aux threads (5 boutons max par ligne)
+        let buttons: Vec<CreateButton> = match interaction.guild_id {
+            Some(guild_id) => apps
+                .iter()
+                .take(MAX_INTERVIEWS_DISPLAYED)
+                .filter_map(|app| {
+                    app.thread_id.map(|t| {
+                        CreateButton::new_link(format!("https://discord.com/channels/{}/{}", guild_id, t))
+                            .label(format!("🧵 #{}", app.id))
+                    })
+                })
+                .collect(),
+            None => vec![],
+        };
+        let rows: Vec<CreateActionRow> = buttons
+            .chunks(5)
+            .map(|chunk| CreateActionRow::Buttons(chunk.to_vec()))
+            .collect();
+
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .ephemeral(self.is_ephemeral(interaction))
+                        .embed(embed)
+                        .components(rows),
+                ),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Trie par date d'entretien (sinon de rappel) croissante ; les candidatures sans date passent en dernier
+fn sort_by_upcoming_date(apps: &mut [crate::db::JobApplication]) {
+    fn date(app: &crate::db::JobApplication) -> Option<&String> {
+        app.interview_at.as_ref().or(app.reminder_date.as_ref())
+    }
+    apps.sort_by(|a, b| match (date(a), date(b)) {
+        (Some(da), Some(db)) => da.cmp(db),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b.updated_at.cmp(&a.updated_at),
+    });
+}
+
+/// Liste /status : un champ par candidature (statut, id, poste, entreprise, score, dates)
+fn build_status_embed(apps: &[JobApplication], criteria: &str) -> CreateEmbed {
+    let fields = apps
+        .iter()
+        .map(|app| {
+            let score = app
+                .match_score
+                .map(|s| format!("{}%", s))
+                .unwrap_or_else(|| "N/A".to_string());
+            let interview = app
+                .interview_at
+                .as_deref()
+                .map(|at| format!("\n🗓️ Entretien: {}", format_interview_at(at)))
+                .unwrap_or_default();
+            let name = format!(
+                "{} #{} {} @ {}{}",
+                get_status_display(&app.status),
+                app.id,
+                app.job_title.as_deref().unwrap_or("N/A"),
+                app.company.as_deref().unwrap_or("N/A"),
+                if app.archived_at.is_some() { " 🗄️" } else { "" },
+            );
+            let value = format!("Match: {} | Créée: {}{}", score, safe_truncate_bytes(&app.created_at, 10), interview);
+            (name, value, false)
+        })
+        .collect();
+
+    let embed = CreateEmbed::new()
+        .title("📊 Your Applications")
+        .description(criteria)
+        .colour(COLOR_TRACKING);
+    add_capped_fields(embed, fields, MAX_EMBED_FIELDS)
+}
+
+/// Date d'entretien stockée (`YYYY-MM-DD HH:MM:SS`) au format affiché « 15/03/2025 à 14:00 »
+fn format_interview_at(raw: &str) -> String {
+    match chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S") {
+        Ok(dt) => dt.format("%d/%m/%Y à %H:%M").to_string(),
+        Err(_) => safe_truncate_bytes(raw, 16).to_string(),
+    }
+}
+
+// ============================================================================
+// SetInterview Command - Date d'entretien programmée
+// ============================================================================
+
+pub struct SetInterviewCommand;
+
+impl SetInterviewCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SetInterviewCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for SetInterviewCommand {
+    fn name(&self) -> &'static str {
+        "setinterview"
+    }
+
+    fn description(&self) -> &'static str {
+        "Record the scheduled interview date of an application"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "application_id",
+                    "Application ID (from /status)",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "when",
+                    "Interview date (tomorrow 14:00, monday 10:30, 2025-03-15 09:00...)",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "remind_day_before",
+                    "Create a reminder the day before the interview (default: true)",
+                )
+                .required(false),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        let user_id = interaction.user.id.get() as i64;
+        let application_id = get_int_option(interaction, "application_id")?;
+        let when = get_string_option(interaction, "when")?;
+        let remind_day_before = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "remind_day_before")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(true);
+
+        let now = chrono::Utc::now().naive_utc();
+        let interview_at = parse_natural_datetime(&when, now)?;
+        let interview_at_str = interview_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let db = get_database(ctx).await?;
+        let app = db.get_application(application_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .filter(|app| app.user_id == user_id)
+            .ok_or_else(|| CommandError::NotFound(format!("Application #{} not found", application_id)))?;
+
+        db.set_application_interview(application_id, user_id, &interview_at_str).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        info!("Interview of application {} set to {} by user {}", application_id, interview_at_str, user_id);
+
+        let mut lines = vec![format!(
+            "🗓️ Entretien enregistré pour la candidature **#{}** — {} chez {} : **{}**",
+            application_id,
+            app.job_title.as_deref().unwrap_or("N/A"),
+            app.company.as_deref().unwrap_or("N/A"),
+            format_interview_at(&interview_at_str),
+        )];
+
+        // Rappel la veille, à la même heure, s'il n'est pas déjà passé
+        let reminder_at = interview_at - chrono::Duration::days(1);
+        if remind_day_before {
+            if reminder_at > now {
+                let message = format!(
+                    "Entretien demain pour la candidature #{} ({} chez {})",
+                    application_id,
+                    app.job_title.as_deref().unwrap_or("N/A"),
+                    app.company.as_deref().unwrap_or("N/A"),
+                );
+                let reminder_id = db
+                    .create_reminder(
+                        user_id,
+                        Some(application_id),
+                        interaction.channel_id.get() as i64,
+                        &reminder_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        &message,
+                    )
+                    .await
+                    .map_err(|e| CommandError::Internal(format!("Failed to create reminder: {}", e)))?;
+                lines.push(format!(
+                    "⏰ Rappel **#{}** programmé la veille ({}).",
+                    reminder_id,
+                    reminder_at.format("%d/%m/%Y à %H:%M")
+                ));
+            } else {
+                lines.push("⏰ Entretien dans moins de 24 h : pas de rappel la veille.".to_string());
+            }
+        }
+        if app.status != "interview" {
+            lines.push("💡 Passez la candidature au statut **Entretien** pour la voir dans `/interviews`.".to_string());
+        }
+
+        send_response(ctx, interaction, self.is_ephemeral(interaction), &lines.join("\n")).await
+    }
+}
+
 // ============================================================================
 // Status Command
 // ============================================================================
@@ -1010,6 +3818,10 @@ impl SlashCommand for StatusCommand {
         "View your job application statuses"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -1034,6 +3846,35 @@ impl SlashCommand for StatusCommand {
                 .min_int_value(1)
                 .max_int_value(25),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "minscore",
+                    "Minimum match score (0-100)",
+                )
+                .required(false)
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "maxscore",
+                    "Maximum match score (0-100)",
+                )
+                .required(false)
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "archived",
+                    "Include auto-archived applications (default: false)",
+                )
+                .required(false),
+            )
+            .add_option(private_option())
     }
 
     async fn execute(
@@ -1041,18 +3882,66 @@ impl SlashCommand for StatusCommand {
         ctx: &Context,
         interaction: &CommandInteraction,
     ) -> Result<(), CommandError> {
-        let _user_id = interaction.user.id;
+        let user_id = interaction.user.id.get() as i64;
         let filter = get_optional_string_option(interaction, "filter").unwrap_or_else(|| "all".to_string());
         let limit = get_optional_int_option(interaction, "limit").unwrap_or(10);
+        let min_score = get_optional_int_option(interaction, "minscore");
+        let max_score = get_optional_int_option(interaction, "maxscore");
+        let include_archived = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "archived")
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or(false);
+
+        if let (Some(min), Some(max)) = (min_score, max_score) {
+            if min > max {
+                return Err(CommandError::InvalidInput(
+                    "minscore doit être inférieur ou égal à maxscore".to_string(),
+                ));
+            }
+        }
 
-        let response = format!(
-            "📊 **Your Applications** (filter: {}, limit: {})\n\n\
-            _Aucune candidature enregistrée_\n\n\
-            Utilisez `/applyjob` pour analyser une offre d'emploi.",
-            filter, limit
-        );
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let db = get_database(ctx).await?;
+        let status_filter = (filter != "all").then_some(filter.as_str());
+        let apps = db
+            .list_applications_filtered(user_id, status_filter, min_score, max_score, include_archived, limit)
+            .await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        let mut criteria = format!("filter: {}, limit: {}", filter, limit);
+        match (min_score, max_score) {
+            (Some(min), Some(max)) => criteria.push_str(&format!(", score: {}-{}%", min, max)),
+            (Some(min), None) => criteria.push_str(&format!(", score ≥ {}%", min)),
+            (None, Some(max)) => criteria.push_str(&format!(", score ≤ {}%", max)),
+            (None, None) => {}
+        }
+        if include_archived {
+            criteria.push_str(", archives incluses");
+        }
+
+        if apps.is_empty() {
+            let response = format!(
+                "📊 **Your Applications** ({})\n\n\
+                _Aucune candidature ne correspond_\n\n\
+                Utilisez `/applyjob` pour analyser une offre d'emploi.",
+                criteria
+            );
+            return interaction
+                .edit_response(&ctx.http, EditInteractionResponse::new().content(response))
+                .await
+                .map(|_| ())
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+        }
 
-        send_response(ctx, interaction, &response).await
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(build_status_embed(&apps, &criteria)))
+            .await
+            .map(|_| ())
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
     }
 }
 
@@ -1084,6 +3973,10 @@ impl SlashCommand for UpdateStatusCommand {
         "Update the status of a job application"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -1109,6 +4002,7 @@ impl SlashCommand for UpdateStatusCommand {
                 CreateCommandOption::new(CommandOptionType::String, "note", "Add a note (optional)")
                     .required(false),
             )
+            .add_option(private_option())
     }
 
     async fn execute(
@@ -1136,10 +4030,28 @@ impl SlashCommand for UpdateStatusCommand {
             status_emoji,
             application_id,
             new_status,
-            note.map(|n| format!("📝 Note: {}", n)).unwrap_or_default()
+            note.as_deref().map(|n| format!("📝 Note: {}", n)).unwrap_or_default()
         );
 
-        send_response(ctx, interaction, &response).await
+        // Vérifie la propriété, enregistre le statut et la note, reconstruit l'embed de suivi
+        let tracking = apply_status_update(ctx, interaction.user.id, application_id, &new_status, note.as_deref()).await?;
+
+        // L'embed de suivi et ses boutons n'accompagnent la confirmation que si l'analyse a un thread
+        let db = get_database(ctx).await?;
+        let has_thread = db
+            .get_application(application_id)
+            .await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+            .is_some_and(|app| app.thread_id.is_some());
+        let msg = if has_thread { tracking } else { CreateInteractionResponseMessage::new() };
+
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(msg.content(response).ephemeral(self.is_ephemeral(interaction))),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
     }
 }
 
@@ -1171,8 +4083,14 @@ impl SlashCommand for MyStatsCommand {
         "View your application statistics"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
-        CreateCommand::new(self.name()).description(self.description())
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
     }
 
     async fn execute(
@@ -1181,18 +4099,137 @@ impl SlashCommand for MyStatsCommand {
         interaction: &CommandInteraction,
     ) -> Result<(), CommandError> {
         let user_id = interaction.user.id;
+        let db = get_database(ctx).await?;
 
-        let response = format!(
-            "📈 **Your Statistics** <@{}>\n\n\
-            _Aucune statistique disponible_\n\n\
-            Utilisez `/applyjob` pour commencer à tracker vos candidatures.",
-            user_id
-        );
+        let stats = db.get_user_stats(user_id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        if stats.total_applications == 0 {
+            let response = format!(
+                "📈 **Your Statistics** <@{}>\n\n\
+                _Aucune statistique disponible_\n\n\
+                Utilisez `/applyjob` pour commencer à tracker vos candidatures.",
+                user_id
+            );
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), &response).await;
+        }
+
+        let streak = db.get_application_streak(user_id.get() as i64).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        let msg = CreateInteractionResponseMessage::new()
+            .content(format!("📈 **Your Statistics** <@{}>", user_id))
+            .embed(build_stats_embed(&stats, streak))
+            .ephemeral(self.is_ephemeral(interaction));
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+    }
+}
+
+// ============================================================================
+// MyCommandsCommand — /mycommands
+// ============================================================================
+
+pub struct MyCommandsCommand;
+
+impl MyCommandsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MyCommandsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for MyCommandsCommand {
+    fn name(&self) -> &'static str {
+        "mycommands"
+    }
+
+    fn description(&self) -> &'static str {
+        "View your recent command usage"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "limit",
+                    "Number of entries (default: 15)",
+                )
+                .required(false)
+                .min_int_value(1)
+                .max_int_value(50),
+            )
+            .add_option(private_option())
+    }
+
+    async fn execute(
+        &self,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        let user_id = interaction.user.id.get() as i64;
+        let limit = get_optional_int_option(interaction, "limit").unwrap_or(15);
+
+        let db = get_database(ctx).await?;
+        // La commande en cours vient d'être enregistrée : l'exclure de la liste
+        let usage = db.list_command_usage(user_id, limit + 1).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let lines: Vec<String> = usage.iter().skip(1).map(format_command_usage).collect();
+
+        if lines.is_empty() {
+            return send_response(
+                ctx,
+                interaction,
+                self.is_ephemeral(interaction),
+                "🕘 Aucune commande enregistrée pour l'instant. Utilisez `/applyjob` pour analyser une offre d'emploi.",
+            )
+            .await;
+        }
 
-        send_response(ctx, interaction, &response).await
+        let embed = CreateEmbed::new()
+            .title("🕘 Vos dernières commandes")
+            .description(lines.join("\n"))
+            .colour(COLOR_TRACKING)
+            .footer(CreateEmbedFooter::new(format!(
+                "{} dernières commandes conservées · supprimées avec /purgeme",
+                db::MAX_COMMAND_USAGE_PER_USER
+            )));
+        let msg = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .ephemeral(self.is_ephemeral(interaction));
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
     }
 }
 
+/// Ligne de /mycommands : horodatage Discord (heure locale de l'utilisateur), commande, candidature
+fn format_command_usage(usage: &db::CommandUsage) -> String {
+    let when = chrono::NaiveDateTime::parse_from_str(&usage.created_at, "%Y-%m-%d %H:%M:%S")
+        .map(|at| {
+            let ts = at.and_utc().timestamp();
+            format!("<t:{}:f> (<t:{}:R>)", ts, ts)
+        })
+        .unwrap_or_else(|_| usage.created_at.clone());
+    let application = usage.application_id.map(|id| format!(" — candidature #{}", id)).unwrap_or_default();
+    format!("{} `/{}`{}", when, usage.command_name, application)
+}
+
 // ============================================================================
 // ApplicationHistoryCommand — /history
 // ============================================================================
@@ -1221,6 +4258,10 @@ impl SlashCommand for ApplicationHistoryCommand {
         "View status change history for an application"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -1232,6 +4273,7 @@ impl SlashCommand for ApplicationHistoryCommand {
                 )
                 .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(
@@ -1250,14 +4292,14 @@ impl SlashCommand for ApplicationHistoryCommand {
             .ok_or_else(|| CommandError::NotFound(format!("Application #{} not found", application_id)))?;
 
         if app.user_id != user_id {
-            return send_response(ctx, interaction, "❌ Cette candidature ne vous appartient pas.").await;
+            return send_response(ctx, interaction, self.is_ephemeral(interaction), "❌ Cette candidature ne vous appartient pas.").await;
         }
 
         let history = db.get_application_status_history(application_id).await
             .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
 
         if history.is_empty() {
-            return send_response(ctx, interaction,
+            return send_response(ctx, interaction, self.is_ephemeral(interaction),
                 &format!("📋 Aucun changement de statut pour la candidature #{}.", application_id)).await;
         }
 
@@ -1272,7 +4314,7 @@ impl SlashCommand for ApplicationHistoryCommand {
         }
 
         let response = lines.join("\n");
-        send_response(ctx, interaction, safe_truncate_bytes(&response, 1900)).await
+        send_response(ctx, interaction, self.is_ephemeral(interaction), safe_truncate_bytes(&response, 1900)).await
     }
 }
 
@@ -1324,26 +4366,10 @@ async fn get_optional_attachment_content(
     interaction: &CommandInteraction,
     name: &str,
 ) -> Result<Option<String>, String> {
-    // Get attachment ID from options
-    let attachment_id = match interaction
-        .data
-        .options
-        .iter()
-        .find(|opt| opt.name == name)
-        .and_then(|opt| opt.value.as_attachment_id())
-    {
-        Some(id) => id,
-        None => return Ok(None),
+    let Some(attachment) = attachment_option(interaction, name)? else {
+        return Ok(None);
     };
 
-    // Get attachment info from resolved data
-    let attachment = interaction
-        .data
-        .resolved
-        .attachments
-        .get(&attachment_id)
-        .ok_or_else(|| "Fichier non trouvé".to_string())?;
-
     // Validate file type (only text files for job descriptions)
     let content_type = attachment.content_type.as_deref().unwrap_or("");
     let filename = &attachment.filename;
@@ -1372,12 +4398,80 @@ async fn get_optional_attachment_content(
     Ok(Some(content))
 }
 
+/// Captures d'écran acceptées pour `description_image`
+const IMAGE_MIME_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp", "image/gif"];
+
+/// Taille maximale d'une capture d'écran d'offre
+const MAX_IMAGE_BYTES: u32 = 5 * 1024 * 1024;
+
+/// Type MIME d'une capture d'écran acceptée (paramètres du Content-Type ignorés)
+fn validate_image_attachment(content_type: Option<&str>, size: u32) -> Result<String, String> {
+    let mime = content_type
+        .and_then(|c| c.split(';').next())
+        .map(|c| c.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if !IMAGE_MIME_TYPES.contains(&mime.as_str()) {
+        return Err(format!(
+            "Type d'image non supporté: `{}`. Utilisez une capture PNG, JPEG, WebP ou GIF.",
+            if mime.is_empty() { "inconnu" } else { &mime }
+        ));
+    }
+    if size > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "Image trop volumineuse ({:.1} Mo, 5 Mo max).",
+            size as f64 / (1024.0 * 1024.0)
+        ));
+    }
+    Ok(mime)
+}
+
+/// Texte d'une capture d'écran d'offre, transcrit par le serveur Claude (/extract-image)
+async fn get_optional_image_text(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let Some(attachment) = attachment_option(interaction, name)? else {
+        return Ok(None);
+    };
+    let mime = validate_image_attachment(attachment.content_type.as_deref(), attachment.size)?;
+
+    if let Err(e) = interaction
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new().embed(build_tracking_embed_progress("Lecture de la capture d'écran...", None, None)),
+        )
+        .await
+    {
+        warn!("Failed to show screenshot progress: {}", e);
+    }
+
+    let image_bytes = attachment
+        .download()
+        .await
+        .map_err(|e| format!("Erreur de téléchargement: {}", e))?;
+    let claude_client = get_claude_client(ctx).await.map_err(|e| e.to_string())?;
+    let text = claude_client
+        .extract_image_text(&BASE64.encode(image_bytes), &mime)
+        .await
+        .map_err(|e| {
+            warn!("Screenshot transcription failed: {}", e);
+            format!(
+                "Impossible de lire le texte de la capture ({}).\n\
+                 Collez le texte de l'offre dans l'option `description` ou joignez un fichier .txt.",
+                e
+            )
+        })?;
+    Ok(Some(text))
+}
+
 async fn send_response(
     ctx: &Context,
     interaction: &CommandInteraction,
+    ephemeral: bool,
     content: &str,
 ) -> Result<(), CommandError> {
-    let msg = CreateInteractionResponseMessage::new().content(content);
+    let msg = CreateInteractionResponseMessage::new().content(content).ephemeral(ephemeral);
 
     interaction
         .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
@@ -1385,6 +4479,125 @@ async fn send_response(
         .map_err(|e| CommandError::ResponseFailed(e.to_string()))
 }
 
+/// Embed /mystats : total, répartition par statut, score moyen (barre de progression), top entreprises
+fn build_stats_embed(stats: &db::UserStats, streak: u32) -> CreateEmbed {
+    let by_status = stats
+        .by_status
+        .iter()
+        .map(|(status, count)| format!("{}: **{}**", get_status_display(status), count))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut embed = CreateEmbed::new()
+        .title("📈 Statistiques de candidature")
+        .colour(COLOR_TRACKING)
+        .field("📊 Total candidatures", stats.total_applications.to_string(), true)
+        .field("📤 Réellement envoyées", stats.submitted_applications.to_string(), true)
+        .field(
+            "🔍 Seulement analysées",
+            (stats.total_applications - stats.submitted_applications).to_string(),
+            true,
+        )
+        .field("📋 Par statut", field_value(&by_status), false);
+
+    if let Some(avg) = stats.avg_match_score {
+        let bar = build_progress_bar(avg.round().clamp(0.0, 100.0) as u32, 100);
+        embed = embed.field("🎯 Score moyen de matching", format!("{} {:.1}%", bar, avg), false);
+    }
+    if !stats.top_companies.is_empty() {
+        let companies = stats
+            .top_companies
+            .iter()
+            .enumerate()
+            .map(|(i, (company, count))| format!("{}. {} ({})", i + 1, company, count))
+            .collect();
+        embed = embed.field("🏆 Top entreprises", list_field_value(companies, "\n", 5), false);
+    }
+    embed.footer(CreateEmbedFooter::new(format_streak(streak)))
+}
+
+/// Ligne de motivation pour la série de jours consécutifs avec une candidature
+fn format_streak(streak: u32) -> String {
+    match streak {
+        0 => "🔥 Série: 0 jour — une candidature aujourd'hui relance la série !".to_string(),
+        1 => "🔥 Série: 1 jour".to_string(),
+        n => format!("🔥 Série: {} jours consécutifs", n),
+    }
+}
+
+/// Longueur maximale d'un nom de thread Discord (en caractères)
+const MAX_THREAD_NAME_CHARS: usize = 100;
+
+/// Placeholders du modèle de nom de thread qui changent après sa création
+const DYNAMIC_THREAD_PLACEHOLDERS: [&str; 3] = ["{score}", "{status}", "{emoji}"];
+
+/// Le nom du thread dépend du score ou du statut : il doit être renommé quand ils changent
+fn thread_name_is_dynamic(template: &str) -> bool {
+    DYNAMIC_THREAD_PLACEHOLDERS.iter().any(|p| template.contains(p))
+}
+
+/// Nom du thread d'analyse selon THREAD_NAME_TEMPLATE (défaut "📋 {label}", soit
+/// "📋 Entreprise - Poste"), accepté par Discord : caractères de contrôle retirés, espaces
+/// regroupés, 100 caractères au plus (coupure sur un caractère). Un libellé vide ou de
+/// remplacement ("N/A", "Non spécifié"...) est omis ; un score inconnu s'affiche "?".
+fn format_thread_name(template: &str, company: &str, title: &str, match_score: Option<u32>, status: &str) -> String {
+    let clean = |value: &str| {
+        let value: String = value.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        matching::meaningful_label(&value).map(str::to_string)
+    };
+    let (company, title) = (clean(company), clean(title));
+    let label = match (&company, &title) {
+        (Some(company), Some(title)) => format!("{} - {}", company, title),
+        (Some(label), None) | (None, Some(label)) => label.clone(),
+        (None, None) => "Nouvelle candidature".to_string(),
+    };
+    let (emoji, status_label) = get_status_display(status).split_once(' ').unwrap_or(("❓", status));
+    let name = template
+        .replace("{label}", &label)
+        .replace("{company}", company.as_deref().unwrap_or("N/A"))
+        .replace("{title}", title.as_deref().unwrap_or("N/A"))
+        .replace("{score}", &match_score.map(|s| s.to_string()).unwrap_or_else(|| "?".to_string()))
+        .replace("{status}", status_label)
+        .replace("{emoji}", emoji);
+    let name: String = name.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.chars().count() <= MAX_THREAD_NAME_CHARS {
+        return name;
+    }
+    let truncated: String = name.chars().take(MAX_THREAD_NAME_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Renomme le thread d'une candidature après un changement de score ou de statut, si
+/// THREAD_NAME_TEMPLATE les affiche
+pub(crate) async fn refresh_thread_name(ctx: &Context, app: &JobApplication) {
+    let Some(thread_id) = app.thread_id else {
+        return;
+    };
+    let Ok(config) = get_config(ctx).await else {
+        return;
+    };
+    if !thread_name_is_dynamic(&config.thread_name_template) {
+        return;
+    }
+    let name = format_thread_name(
+        &config.thread_name_template,
+        app.company.as_deref().unwrap_or(""),
+        app.job_title.as_deref().unwrap_or(""),
+        app.match_score.map(|s| s as u32),
+        &app.status,
+    );
+    rename_thread(ctx, ChannelId::new(thread_id as u64), &name).await;
+}
+
+/// Renomme un thread d'analyse ; échec non bloquant (thread supprimé, archivé ou limite de
+/// Discord sur les renommages)
+async fn rename_thread(ctx: &Context, thread_id: ChannelId, name: &str) {
+    if let Err(e) = thread_id.edit_thread(&ctx.http, EditThread::new().name(name)).await {
+        warn!("Failed to rename thread {}: {}", thread_id, e);
+    }
+}
+
 /// Tronque une chaîne à `max_bytes` octets sur une frontière UTF-8 valide.
 fn safe_truncate_bytes(s: &str, max_bytes: usize) -> &str {
     if s.len() <= max_bytes {
@@ -1396,3 +4609,308 @@ fn safe_truncate_bytes(s: &str, max_bytes: usize) -> &str {
     }
     &s[..boundary]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_THREAD_NAME_TEMPLATE;
+    use crate::db::ApplicationVersion;
+
+    #[test]
+    fn test_format_command_usage() {
+        let usage = db::CommandUsage {
+            command_name: "updatestatus".to_string(),
+            application_id: Some(12),
+            created_at: "2030-03-15 14:00:00".to_string(),
+        };
+        assert_eq!(
+            format_command_usage(&usage),
+            "<t:1899813600:f> (<t:1899813600:R>) `/updatestatus` — candidature #12"
+        );
+        let usage = db::CommandUsage { application_id: None, created_at: "invalide".to_string(), ..usage };
+        assert_eq!(format_command_usage(&usage), "invalide `/updatestatus`");
+    }
+
+    #[test]
+    fn test_build_stats_embed() {
+        let stats = db::UserStats {
+            total_applications: 4,
+            submitted_applications: 3,
+            by_status: vec![("applied".to_string(), 3), ("interview".to_string(), 1)],
+            avg_match_score: Some(72.34),
+            top_companies: vec![("Acme".to_string(), 2), ("Globex".to_string(), 1)],
+        };
+        let embed = serde_json::to_value(build_stats_embed(&stats, 2)).unwrap();
+        let fields = embed["fields"].as_array().unwrap();
+        assert_eq!(fields[0]["value"], "4");
+        assert_eq!(fields[2]["value"], "1");
+        assert_eq!(fields[4]["value"], format!("{} 72.3%", build_progress_bar(72, 100)));
+        assert_eq!(fields[5]["value"], "1. Acme (2)\n2. Globex (1)");
+        assert_eq!(embed["footer"]["text"], "🔥 Série: 2 jours consécutifs");
+    }
+
+    #[test]
+    fn test_format_interview_at() {
+        assert_eq!(format_interview_at("2030-03-15 14:00:00"), "15/03/2030 à 14:00");
+        assert_eq!(format_interview_at("2030-03-15T14:00"), "2030-03-15T14:00");
+    }
+
+    #[test]
+    fn test_format_step_timings() {
+        use std::time::Duration;
+        let timings = PipelineTimings {
+            steps: vec![
+                ("Synthèse", BACKEND_CLAUDE, Duration::from_millis(12_340)),
+                ("PDF du CV", BACKEND_PDF, Duration::from_secs(125)),
+            ],
+        };
+        assert_eq!(
+            format_step_timings(&timings),
+            "• Synthèse — 12.3 s _(Claude)_\n• PDF du CV — 2 min 05 s _(serveur PDF)_"
+        );
+        assert_eq!(format_step_duration(timings.total()), "2 min 17 s");
+        assert!(build_timings_embed(&PipelineTimings::default()).is_none());
+    }
+
+    #[test]
+    fn test_synthesis_markdown() {
+        let synthesis = JobSynthesis {
+            title: "Développeur Rust".to_string(),
+            company: "Acme".to_string(),
+            location: "Lyon".to_string(),
+            contract_type: "CDI".to_string(),
+            key_requirements: vec!["Rust".to_string(), "Tokio".to_string()],
+            responsibilities: vec![],
+            benefits: vec!["Télétravail".to_string()],
+            salary_range: None,
+            summary: "Backend asynchrone.".to_string(),
+        };
+        let md = synthesis_markdown(&synthesis);
+        assert!(md.starts_with("# Développeur Rust — Acme\n"));
+        assert!(md.contains("## Exigences clés\n\n- Rust\n- Tokio\n"));
+        assert!(md.contains("## Avantages\n\n- Télétravail\n"));
+        assert!(!md.contains("Responsabilités"));
+        assert!(!md.contains("Salaire"));
+        assert_eq!(safe_file_stem("Dév. C++ / Rust"), "Dév_C__Rust");
+    }
+
+    #[test]
+    fn test_append_outcome_notes() {
+        assert_eq!(append_outcome_notes(None, "rejected", " Profil trop junior "), "❌ Refusée : Profil trop junior");
+        assert_eq!(append_outcome_notes(Some("  "), "accepted", "55k€"), "✅ Acceptée : 55k€");
+        assert_eq!(
+            append_outcome_notes(Some("Contact : Marie"), "accepted", "Début en mars"),
+            "Contact : Marie\n\n✅ Acceptée : Début en mars"
+        );
+        assert!(is_terminal_status("rejected"));
+        assert!(!is_terminal_status("offer"));
+    }
+
+    #[test]
+    fn test_format_thread_name_default_template() {
+        let thread_name = |company: &str, title: &str| format_thread_name(DEFAULT_THREAD_NAME_TEMPLATE, company, title, None, "generated");
+        assert_eq!(thread_name("Société Générale", "Ingénieur Qualité"), "📋 Société Générale - Ingénieur Qualité");
+        assert_eq!(thread_name("  Acme\n\tCorp ", "Dev\u{0}  Rust 🦀"), "📋 Acme Corp - Dev Rust 🦀");
+
+        // Libellés vides ou de remplacement
+        assert_eq!(thread_name("N/A", "Développeur"), "📋 Développeur");
+        assert_eq!(thread_name("Acme", "  "), "📋 Acme");
+        assert_eq!(thread_name("Non spécifié", ""), "📋 Nouvelle candidature");
+
+        // Trop long : 100 caractères au plus, coupure entre deux caractères multi-octets
+        let name = thread_name(&"é".repeat(59), &"Dév 🦀".repeat(20));
+        assert_eq!(name.chars().count(), MAX_THREAD_NAME_CHARS);
+        assert!(name.ends_with("Dév 🦀…"));
+        assert!(name.len() > MAX_THREAD_NAME_CHARS);
+    }
+
+    #[test]
+    fn test_format_thread_name_template() {
+        let template = "{emoji} {company} - {title} ({score}%)";
+        assert!(thread_name_is_dynamic(template));
+        assert!(!thread_name_is_dynamic(DEFAULT_THREAD_NAME_TEMPLATE));
+        assert_eq!(format_thread_name(template, "Acme", "Dev", Some(82), "interview"), "🗓️ Acme - Dev (82%)");
+        assert_eq!(format_thread_name(template, "N/A", "Dev", None, "generated"), "📝 N/A - Dev (?%)");
+        assert_eq!(
+            format_thread_name("{label} · {status}", "Acme", "", Some(40), "offer"),
+            "Acme · Offre reçue"
+        );
+    }
+
+    fn version(id: i64, match_score: Option<i32>) -> ApplicationVersion {
+        ApplicationVersion {
+            id,
+            match_score,
+            status: "generated".to_string(),
+            created_at: "2025-03-01 10:00:00".to_string(),
+        }
+    }
+
+    fn salary(offered_min: Option<u32>, offered_max: Option<u32>, market_median: u32) -> SalaryAnalysis {
+        SalaryAnalysis {
+            offered_min,
+            offered_max,
+            market_low: 0,
+            market_median,
+            market_high: 0,
+            currency: "EUR".to_string(),
+            analysis: String::new(),
+            negotiation_tips: vec![],
+        }
+    }
+
+    #[test]
+    fn test_lock_recovering_after_panic() {
+        let mutex = Arc::new(Mutex::new(HashMap::from([(1_i64, "pending")])));
+        let poisoner = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("panic while holding the lock");
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+
+        let mut store = lock_recovering(&mutex, "test");
+        assert_eq!(store.get(&1), Some(&"pending"));
+        store.insert(2, "new");
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_low_fit_warning() {
+        let missing = |skill: &str, importance: &str| crate::services::claude_client::MissingSkill {
+            skill: skill.to_string(),
+            importance: importance.to_string(),
+        };
+        let skills = SkillsMatch {
+            match_score: 25,
+            matched_skills: vec![],
+            missing_skills: vec![
+                missing("GraphQL", "nice-to-have"),
+                missing("Kubernetes", "required"),
+                missing("Go", "required"),
+                missing("Terraform", "required"),
+            ],
+            highlights: vec![],
+            recommendations: vec![],
+        };
+
+        let warning = low_fit_warning(&skills, 40).unwrap();
+        assert!(warning.contains("• Kubernetes\n• Go\n• Terraform"));
+        assert!(!warning.contains("GraphQL"));
+
+        assert!(low_fit_warning(&skills, 25).is_none());
+        assert!(low_fit_warning(&skills, 0).is_none());
+    }
+
+    #[test]
+    fn test_validate_image_attachment() {
+        assert_eq!(validate_image_attachment(Some("image/png"), 1024).unwrap(), "image/png");
+        assert_eq!(validate_image_attachment(Some("IMAGE/JPEG; charset=binary"), 1024).unwrap(), "image/jpeg");
+        assert!(validate_image_attachment(Some("application/pdf"), 1024).is_err());
+        assert!(validate_image_attachment(None, 1024).is_err());
+        assert!(validate_image_attachment(Some("image/webp"), MAX_IMAGE_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_estimate_applyjob_cost() {
+        assert_eq!(estimate_applyjob_cost(0), (0, 90));
+        assert_eq!(estimate_applyjob_cost(4001), (1001, 110));
+        // Un document de ~50 pages dépasse le délai de 10 minutes
+        let (tokens, secs) = estimate_applyjob_cost(150_000);
+        assert_eq!(tokens, 37_500);
+        assert!(secs > 600);
+    }
+
+    #[test]
+    fn test_match_verdict() {
+        let matched = |skill: &str, is_match: bool| crate::services::claude_client::MatchedSkill {
+            skill: skill.to_string(),
+            cv_level: String::new(),
+            required: String::new(),
+            is_match,
+        };
+        let missing = |skill: &str, importance: &str| MissingSkill {
+            skill: skill.to_string(),
+            importance: importance.to_string(),
+        };
+        let mut skills = SkillsMatch {
+            match_score: 82,
+            matched_skills: vec![matched("Rust", true), matched("SQL", true), matched("Docker", false)],
+            missing_skills: vec![missing("GraphQL", "nice-to-have"), missing("Kubernetes", "required")],
+            highlights: vec![],
+            recommendations: vec![],
+        };
+        assert_eq!(
+            match_verdict(&skills),
+            "Bonne adéquation — vous couvrez 2/5 exigences clés, principale lacune : Kubernetes"
+        );
+
+        skills.match_score = 55;
+        skills.missing_skills.clear();
+        assert_eq!(
+            match_verdict(&skills),
+            "Adéquation partielle — vous couvrez 2/3 exigences clés, aucune lacune identifiée"
+        );
+
+        skills.match_score = 10;
+        skills.matched_skills.clear();
+        assert_eq!(match_verdict(&skills), "Faible adéquation");
+    }
+
+    #[test]
+    fn test_format_salary_k() {
+        assert_eq!(format_salary_k(45000, "EUR"), "45k€");
+        assert_eq!(format_salary_k(120000, "usd"), "120k$");
+        assert_eq!(format_salary_k(60000, "GBP"), "60k£");
+        assert_eq!(format_salary_k(95000, "CHF"), "95k CHF");
+    }
+
+    #[test]
+    fn test_compare_salary_to_target() {
+        let (below, text) = compare_salary_to_target(&salary(Some(38000), Some(42000), 50000), 45000, None);
+        assert!(below);
+        assert!(text.contains("Offre sous votre objectif (42k€ max)"));
+        assert!(text.contains("au niveau de votre objectif"));
+
+        let (below, text) = compare_salary_to_target(&salary(Some(40000), Some(55000), 44000), 45000, Some(60000));
+        assert!(!below);
+        assert!(text.contains("Bas de fourchette (40k€)"));
+        assert!(text.contains("Médiane du marché (44k€) sous votre objectif"));
+
+        let (below, text) = compare_salary_to_target(&salary(Some(70000), None, 0), 45000, Some(60000));
+        assert!(!below);
+        assert!(text.contains("au-dessus"));
+
+        // Salaire non annoncé : jugé sur la médiane du marché
+        assert!(compare_salary_to_target(&salary(None, None, 40000), 45000, None).0);
+        assert!(!compare_salary_to_target(&salary(None, None, 0), 45000, None).0);
+    }
+
+    #[test]
+    fn test_parse_accent_color() {
+        assert_eq!(parse_accent_color("#2C3E50"), Some("#2c3e50".to_string()));
+        assert_eq!(parse_accent_color(" 2c3e50 "), Some("#2c3e50".to_string()));
+        assert_eq!(parse_accent_color("#abc"), None);
+        assert_eq!(parse_accent_color("blue"), None);
+    }
+
+    #[test]
+    fn test_format_score_progression() {
+        let versions = vec![version(3, Some(45)), version(7, None), version(9, Some(60))];
+        let lines: Vec<String> = format_score_progression(&versions, 9).lines().map(String::from).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("**v1** `#3` — 45%"));
+        assert!(lines[1].contains("—") && !lines[1].contains("📈"));
+        assert!(lines[2].contains("60% (📈 +15)"));
+        assert!(lines[2].ends_with("← actuelle"));
+    }
+
+    #[test]
+    fn test_apply_link() {
+        assert_eq!(apply_link(Some(" https://jobs.example.com/42 ")), Some("https://jobs.example.com/42"));
+        assert_eq!(apply_link(Some("jobs.example.com/42")), None);
+        assert_eq!(apply_link(Some("https://example.com/a b")), None);
+        assert_eq!(apply_link(None), None);
+    }
+}
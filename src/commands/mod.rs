@@ -1,31 +1,64 @@
 mod admin;
+mod attachments;
+mod benefits;
+mod compare;
+mod context_menu;
 mod cv;
+mod datetime;
+mod embeds;
 mod generation;
 mod help;
+mod import;
 mod jobs;
+mod privacy;
 mod reminders;
+mod settings;
 
-pub use admin::{ClearAllCvsCommand, GetCvCommand, ListCvsCommand};
-pub use cv::{DeleteCvCommand, ListMyCvsCommand, SendCvCommand};
+pub use admin::{
+    ClearAllCvsCommand, ExportUserCommand, GetCvCommand, GetCvUserMenuCommand, ListCvsCommand,
+    BackfillCommand, RestoreBackupCommand, ToggleCommandCommand,
+};
+pub use attachments::{AttachCommand, AttachmentsCommand};
+pub use benefits::BenefitsCommand;
+pub use compare::CompareCvCommand;
+pub use context_menu::AnalyzeMessageCommand;
+pub use cv::{
+    CvProfileCommand, DeleteCvCommand, LinkCvCommand, ListMyCvsCommand, RestoreCvCommand, SendCvCommand, TrashCommand,
+    handle_cv_rescore_component, handle_linkcv_component,
+};
 pub use generation::{
     GenerateCoverLetterCommand, GenerateMarketAnalysisCommand, GenerateResumeCommand,
     SynthesizeOfferCommand,
 };
 pub use help::HelpCommand;
+pub use import::ImportApplicationsCommand;
 pub use jobs::{
-    ApplyJobCommand, ApplicationHistoryCommand, MyStatsCommand, StatusCommand, UpdateStatusCommand,
-    get_status_buttons, rebuild_tracking_embed_from_status,
+    ApplyJobCommand, ApplyJobFormCommand, ApplicationHistoryCommand, CloneApplicationCommand, InterviewsCommand, SetInterviewCommand, MyCommandsCommand, MyStatsCommand, StatusCommand,
+    UpdateStatusCommand,
+    PendingAnalysesKey, PendingApplyJobsKey, handle_applyjob_cost_component,
+    handle_applyjob_preview_component, handle_retry_analysis_component,
+    interrupted_pipeline_message,
+    ApplyJobFormModal, OutcomeNotesModal, apply_status_update, build_outcome_notes_modal, is_terminal_status,
 };
+pub use privacy::{PurgeMeCommand, handle_purgeme_component};
 pub use reminders::{
-    SetReminderCommand, ListRemindersCommand, ClearReminderCommand,
-    CreateReminderCommand, DeleteReminderCommand,
+    SetReminderCommand, ListRemindersCommand, ClearReminderCommand, CalendarCommand,
+    CreateReminderCommand, DeleteReminderCommand, RemindMeCommand, handle_quick_reminder_component,
+};
+pub use settings::{
+    SetTargetCommand, SettingsCommand, apply_settings_component, build_settings_components, build_settings_embed,
 };
 
 use async_trait::async_trait;
-use serenity::all::{CommandInteraction, Context, CreateCommand};
+use serenity::all::{
+    Attachment, AttachmentId, CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    CreateInteractionResponse, CreateInteractionResponseMessage, GuildId, ModalInteraction,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::warn;
 
+use crate::config::Config;
 use crate::db::{Database, BaseCv};
 use crate::services::ClaudeClient;
 use crate::ClaudeClientKey;
@@ -42,6 +75,27 @@ pub trait SlashCommand: Send + Sync {
     /// Construit la définition de la commande pour Discord
     fn register(&self) -> CreateCommand;
 
+    /// Noms courts enregistrés comme commandes distinctes, traités par le même handler
+    fn aliases(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Réponse visible uniquement par l'auteur par défaut (CV, statistiques, rappels...)
+    fn default_ephemeral(&self) -> bool {
+        false
+    }
+
+    /// Visibilité de la réponse : option `private` si la commande la propose, sinon `default_ephemeral`
+    fn is_ephemeral(&self, interaction: &CommandInteraction) -> bool {
+        interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == PRIVATE_OPTION)
+            .and_then(|opt| opt.value.as_bool())
+            .unwrap_or_else(|| self.default_ephemeral())
+    }
+
     /// Exécute la commande
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError>;
 }
@@ -82,41 +136,142 @@ impl std::fmt::Display for CommandError {
 
 impl std::error::Error for CommandError {}
 
+/// Nom de l'option commune qui surcharge `SlashCommand::default_ephemeral`
+pub const PRIVATE_OPTION: &str = "private";
+
+/// Option `private` à ajouter aux commandes dont la visibilité peut être choisie à l'appel
+pub fn private_option() -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::Boolean,
+        PRIVATE_OPTION,
+        "Réponse visible par vous seul (défaut selon la commande)",
+    )
+    .required(false)
+}
+
+/// Message quand un fichier a été joint mais que Discord ne l'a pas transmis avec la commande
+pub const UNRESOLVED_ATTACHMENT_MESSAGE: &str =
+    "Le fichier joint n'a pas été transmis par Discord (envoi interrompu ou expiré). Relancez la commande en joignant à nouveau le fichier.";
+
+/// Pièce jointe de l'option `name` : `Ok(None)` si aucun fichier n'a été fourni, erreur explicite
+/// si le fichier est absent des données résolues. Discord n'expose aucun endpoint pour récupérer
+/// un fichier de commande par son ID : seul un nouvel envoi permet alors de le lire
+pub fn attachment_option<'a>(interaction: &'a CommandInteraction, name: &str) -> Result<Option<&'a Attachment>, String> {
+    let attachment_id = interaction
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| opt.value.as_attachment_id());
+    resolve_attachment(attachment_id, &interaction.data.resolved.attachments, name)
+}
+
+fn resolve_attachment<'a>(
+    attachment_id: Option<AttachmentId>,
+    resolved: &'a HashMap<AttachmentId, Attachment>,
+    name: &str,
+) -> Result<Option<&'a Attachment>, String> {
+    let Some(attachment_id) = attachment_id else {
+        return Ok(None);
+    };
+    match resolved.get(&attachment_id) {
+        Some(attachment) => Ok(Some(attachment)),
+        None => {
+            warn!("Attachment {} of option `{}` missing from resolved data", attachment_id, name);
+            Err(UNRESOLVED_ATTACHMENT_MESSAGE.to_string())
+        }
+    }
+}
+
+/// Diffère la réponse (délai de 3s de Discord) avec la visibilité choisie,
+/// en général `self.is_ephemeral(interaction)`
+pub async fn defer_response(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    ephemeral: bool,
+) -> Result<(), CommandError> {
+    let deferred = if ephemeral {
+        interaction.defer_ephemeral(&ctx.http).await
+    } else {
+        interaction.defer(&ctx.http).await
+    };
+    deferred.map_err(|e| CommandError::ResponseFailed(e.to_string()))
+}
+
+/// Commande qui active ou désactive les autres : jamais désactivable (sinon impossible d'annuler)
+pub const TOGGLE_COMMAND_NAME: &str = "togglecommand";
+
 /// Registre centralisé de toutes les commandes
 pub struct CommandRegistry {
     commands: HashMap<&'static str, Box<dyn SlashCommand>>,
+    /// Alias -> nom de la commande
+    aliases: HashMap<&'static str, &'static str>,
     order: Vec<&'static str>,
 }
 
 impl CommandRegistry {
     pub fn new() -> Self {
-        Self { commands: HashMap::new(), order: Vec::new() }
+        Self { commands: HashMap::new(), aliases: HashMap::new(), order: Vec::new() }
     }
 
-    /// Enregistre une nouvelle commande
+    /// Enregistre une nouvelle commande et ses alias
     pub fn register<C: SlashCommand + 'static>(&mut self, command: C) -> &mut Self {
         let name = command.name();
+        for alias in command.aliases() {
+            self.aliases.insert(alias, name);
+        }
         self.order.push(name);
         self.commands.insert(name, Box::new(command));
         self
     }
 
-    /// Retourne toutes les définitions de commandes pour l'enregistrement Discord
+    /// Retourne toutes les définitions de commandes pour l'enregistrement Discord.
+    /// Chaque alias est une commande distincte côté Discord, avec les mêmes options.
     pub fn build_commands(&self) -> Vec<CreateCommand> {
         self.order.iter()
             .filter_map(|name| self.commands.get(name))
-            .map(|cmd| cmd.register())
+            .flat_map(|cmd| {
+                std::iter::once(cmd.register())
+                    .chain(cmd.aliases().iter().map(|alias| cmd.register().name(*alias)))
+            })
             .collect()
     }
 
-    /// Trouve et exécute une commande par son nom (O(1) lookup)
+    /// Trouve une commande par son nom ou l'un de ses alias (O(1) lookup)
+    fn resolve(&self, name: &str) -> Option<&dyn SlashCommand> {
+        let name = self.aliases.get(name).copied().unwrap_or(name);
+        self.commands.get(name).map(|cmd| cmd.as_ref())
+    }
+
+    /// Nom principal d'une commande à partir de son nom ou de l'un de ses alias
+    pub fn canonical_name(&self, name: &str) -> Option<&'static str> {
+        self.resolve(name).map(|cmd| cmd.name())
+    }
+
+    /// Trouve et exécute une commande par son nom ou alias.
+    /// Les commandes désactivées sur le serveur (/togglecommand) sont refusées ici : leur
+    /// enregistrement Discord reste global.
     pub async fn dispatch(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
         let command_name = interaction.data.name.as_str();
-        if let Some(cmd) = self.commands.get(command_name) {
-            cmd.execute(ctx, interaction).await
-        } else {
-            Err(CommandError::Internal(format!("Unknown command: {}", command_name)))
+        let Some(cmd) = self.resolve(command_name) else {
+            return Err(CommandError::Internal(format!("Unknown command: {}", command_name)));
+        };
+        if let Some(guild_id) = interaction.guild_id {
+            if !command_enabled_in_guild(ctx, guild_id, cmd.name()).await {
+                let msg = CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "🚫 La commande `/{}` est désactivée sur ce serveur par les administrateurs.",
+                        command_name
+                    ))
+                    .ephemeral(true);
+                return interaction
+                    .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+                    .await
+                    .map_err(|e| CommandError::ResponseFailed(e.to_string()));
+            }
         }
+        record_command_usage(ctx, interaction, cmd.name()).await;
+        cmd.execute(ctx, interaction).await
     }
 
     /// Retourne les informations d'aide pour toutes les commandes
@@ -134,6 +289,89 @@ impl Default for CommandRegistry {
     }
 }
 
+/// Handler de modal, sélectionné par le préfixe du `custom_id` du modal validé
+#[async_trait]
+pub trait ModalHandler: Send + Sync {
+    /// Préfixe des `custom_id` traités (ex. `outcome_` pour `outcome_{id}_{statut}`)
+    fn custom_id_prefix(&self) -> &'static str;
+
+    /// Traite la validation du modal
+    async fn handle(&self, ctx: &Context, modal: &ModalInteraction) -> Result<(), CommandError>;
+}
+
+/// Registre des handlers de modals, pendant de `CommandRegistry`
+pub struct ModalRegistry {
+    handlers: Vec<Box<dyn ModalHandler>>,
+}
+
+impl ModalRegistry {
+    pub fn new() -> Self {
+        Self { handlers: Vec::new() }
+    }
+
+    /// Enregistre un handler de modal
+    pub fn register<H: ModalHandler + 'static>(&mut self, handler: H) -> &mut Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Handler dont le préfixe correspond au `custom_id` (le premier enregistré en cas d'ambiguïté)
+    fn resolve(&self, custom_id: &str) -> Option<&dyn ModalHandler> {
+        self.handlers
+            .iter()
+            .find(|handler| custom_id.starts_with(handler.custom_id_prefix()))
+            .map(|handler| handler.as_ref())
+    }
+
+    /// Trouve et exécute le handler du modal validé
+    pub async fn dispatch(&self, ctx: &Context, modal: &ModalInteraction) -> Result<(), CommandError> {
+        let custom_id = modal.data.custom_id.as_str();
+        match self.resolve(custom_id) {
+            Some(handler) => handler.handle(ctx, modal).await,
+            None => Err(CommandError::Internal(format!("Unknown modal: {}", custom_id))),
+        }
+    }
+}
+
+impl Default for ModalRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Journal de /mycommands ; un échec d'écriture n'empêche pas l'exécution de la commande
+async fn record_command_usage(ctx: &Context, interaction: &CommandInteraction, command_name: &str) {
+    let Ok(db) = get_database(ctx).await else {
+        return;
+    };
+    let application_id = interaction
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "application_id")
+        .and_then(|opt| opt.value.as_i64());
+    if let Err(e) = db
+        .record_command_usage(interaction.user.id.get() as i64, command_name, application_id)
+        .await
+    {
+        warn!("Failed to record usage of /{} for user {}: {}", command_name, interaction.user.id, e);
+    }
+}
+
+/// Une erreur de lecture de la configuration laisse la commande disponible
+async fn command_enabled_in_guild(ctx: &Context, guild_id: GuildId, command_name: &str) -> bool {
+    if command_name == TOGGLE_COMMAND_NAME {
+        return true;
+    }
+    let Ok(db) = get_database(ctx).await else {
+        return true;
+    };
+    db.is_command_enabled(guild_id.get() as i64, command_name).await.unwrap_or_else(|e| {
+        warn!("Failed to read command config for guild {}: {}", guild_id, e);
+        true
+    })
+}
+
 // ============================================================================
 // Dependency-Injection helpers (évitent le boilerplate dans chaque commande)
 // ============================================================================
@@ -158,6 +396,16 @@ pub async fn get_database(ctx: &Context) -> Result<Database, CommandError> {
         .ok_or_else(|| CommandError::Internal("Database not found".to_string()))
 }
 
+/// Récupère la configuration depuis le TypeMap de Serenity.
+pub async fn get_config(ctx: &Context) -> Result<Arc<Config>, CommandError> {
+    ctx.data
+        .read()
+        .await
+        .get::<Config>()
+        .cloned()
+        .ok_or_else(|| CommandError::Internal("Config not found".to_string()))
+}
+
 /// Retourne le texte du CV : priorité à extracted_text, sinon lecture du fichier.
 pub async fn get_cv_text(cv: &BaseCv) -> String {
     if let Some(ref text) = cv.extracted_text {
@@ -168,4 +416,93 @@ pub async fn get_cv_text(cv: &BaseCv) -> String {
     tokio::fs::read_to_string(&cv.file_path)
         .await
         .unwrap_or_else(|_| format!("CV: {} (texte non disponible)", cv.original_name))
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MarketCommand;
+
+    #[async_trait]
+    impl SlashCommand for MarketCommand {
+        fn name(&self) -> &'static str {
+            "generatemarketanalysis"
+        }
+
+        fn description(&self) -> &'static str {
+            "Market analysis"
+        }
+
+        fn register(&self) -> CreateCommand {
+            CreateCommand::new(self.name()).description(self.description())
+        }
+
+        fn aliases(&self) -> &[&'static str] {
+            &["market"]
+        }
+
+        async fn execute(&self, _ctx: &Context, _interaction: &CommandInteraction) -> Result<(), CommandError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_aliases_resolve_to_the_same_command() {
+        let mut registry = CommandRegistry::new();
+        registry.register(MarketCommand);
+
+        let names: Vec<String> = registry
+            .build_commands()
+            .iter()
+            .map(|cmd| serde_json::to_value(cmd).unwrap()["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["generatemarketanalysis", "market"]);
+
+        assert_eq!(registry.resolve("market").map(|cmd| cmd.name()), Some("generatemarketanalysis"));
+        assert_eq!(registry.resolve("generatemarketanalysis").map(|cmd| cmd.name()), Some("generatemarketanalysis"));
+        assert!(registry.resolve("cover").is_none());
+        assert_eq!(registry.canonical_name("market"), Some("generatemarketanalysis"));
+        // L'aide ne liste que le nom principal
+        assert_eq!(registry.help_info().len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_attachment_distinguishes_missing_and_unresolved() {
+        let id = AttachmentId::new(7);
+        let mut resolved = HashMap::new();
+        assert!(resolve_attachment(None, &resolved, "cv").unwrap().is_none());
+        assert_eq!(resolve_attachment(Some(id), &resolved, "cv").unwrap_err(), UNRESOLVED_ATTACHMENT_MESSAGE);
+
+        let attachment: Attachment = serde_json::from_value(serde_json::json!({
+            "id": "7", "filename": "cv.pdf", "size": 10,
+            "url": "https://cdn.discordapp.com/cv.pdf", "proxy_url": "https://media.discordapp.net/cv.pdf"
+        }))
+        .unwrap();
+        resolved.insert(id, attachment);
+        assert_eq!(resolve_attachment(Some(id), &resolved, "cv").unwrap().map(|a| a.filename.as_str()), Some("cv.pdf"));
+    }
+
+    struct PrefixModal(&'static str);
+
+    #[async_trait]
+    impl ModalHandler for PrefixModal {
+        fn custom_id_prefix(&self) -> &'static str {
+            self.0
+        }
+
+        async fn handle(&self, _ctx: &Context, _modal: &ModalInteraction) -> Result<(), CommandError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_modal_handlers_resolve_by_prefix() {
+        let mut registry = ModalRegistry::new();
+        registry.register(PrefixModal("outcome_")).register(PrefixModal("applyjobform_"));
+
+        let prefix = |custom_id: &str| registry.resolve(custom_id).map(|handler| handler.custom_id_prefix());
+        assert_eq!(prefix("outcome_42_rejected"), Some("outcome_"));
+        assert_eq!(prefix("applyjobform_private"), Some("applyjobform_"));
+        assert_eq!(prefix("applyjob_proceed_1"), None);
+    }
+}
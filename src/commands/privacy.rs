@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use serenity::all::{
+    ButtonStyle, CommandInteraction, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateCommand, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use super::{CommandError, SlashCommand, get_database};
+
+// ============================================================================
+// PurgeMe Command - Supprime toutes les données de l'utilisateur (RGPD)
+// ============================================================================
+
+pub struct PurgeMeCommand;
+
+impl PurgeMeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PurgeMeCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for PurgeMeCommand {
+    fn name(&self) -> &'static str {
+        "purgeme"
+    }
+
+    fn description(&self) -> &'static str {
+        "Permanently delete all your data (CVs, applications, reminders, settings)"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name()).description(self.description())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        let confirm_btn = CreateButton::new("purgeme_confirm")
+            .label("Tout supprimer")
+            .style(ButtonStyle::Danger);
+        let cancel_btn = CreateButton::new("purgeme_cancel")
+            .label("Annuler")
+            .style(ButtonStyle::Secondary);
+        let row = CreateActionRow::Buttons(vec![confirm_btn, cancel_btn]);
+
+        let msg = CreateInteractionResponseMessage::new()
+            .content(
+                "⚠️ **Supprimer toutes vos données ?**\n\
+                 CVs, candidatures (historique, pièces jointes, CVs générés), rappels et préférences \
+                 seront définitivement effacés. Cette action est irréversible.",
+            )
+            .components(vec![row])
+            .ephemeral(self.is_ephemeral(interaction));
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+    }
+}
+
+/// Boutons de confirmation de /purgeme : purgeme_confirm / purgeme_cancel.
+/// Le message est éphémère : seul l'auteur de la commande peut cliquer.
+pub async fn handle_purgeme_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let content = if component.data.custom_id == "purgeme_confirm" {
+        let db = get_database(ctx).await?;
+        let user_id = component.user.id.get() as i64;
+        let summary = db.purge_user_data(user_id).await
+            .map_err(|e| CommandError::Internal(format!("DB error: {}", e)))?;
+
+        // Fichiers supprimés après validation de la transaction
+        for path in &summary.file_paths {
+            remove_file_if_exists(Path::new(path)).await;
+        }
+        for dir in summary.file_paths.iter().filter_map(|p| Path::new(p).parent()) {
+            // Dossiers data/attachments/{id} désormais vides (échec silencieux sinon)
+            if dir.starts_with("data/attachments") && dir != Path::new("data/attachments") {
+                let _ = tokio::fs::remove_dir(PathBuf::from(dir)).await;
+            }
+        }
+
+        info!(
+            "User {} purged their data: {} CV(s), {} application(s), {} reminder(s)",
+            user_id, summary.cvs, summary.applications, summary.reminders
+        );
+        format!(
+            "🗑️ **Vos données ont été supprimées.**\n\
+             • CVs : {}\n\
+             • Candidatures : {}\n\
+             • Changements de statut : {}\n\
+             • Pièces jointes : {}\n\
+             • Rappels : {}\n\
+             • Préférences et profil : supprimés",
+            summary.cvs, summary.applications, summary.status_history, summary.attachments, summary.reminders
+        )
+    } else {
+        "❌ Suppression annulée. Vos données sont conservées.".to_string()
+    };
+
+    let response = CreateInteractionResponseMessage::new()
+        .content(content)
+        .components(vec![]);
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(response))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+}
+
+async fn remove_file_if_exists(path: &Path) {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => info!("Deleted file: {:?}", path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!("Failed to delete file {:?} (DB entry already removed): {}", path, e),
+    }
+}
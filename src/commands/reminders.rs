@@ -1,14 +1,21 @@
 use async_trait::async_trait;
 use serenity::all::{
-    Colour, CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateEmbed, EditInteractionResponse,
+    Colour, CommandInteraction, CommandOptionType, ComponentInteraction, Context, CreateCommand,
+    CreateAttachment, CreateCommandOption, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EditInteractionResponse,
 };
 use tracing::info;
 use chrono::{NaiveDateTime, Utc, Duration};
 
-use super::{CommandError, SlashCommand, get_database};
+use super::datetime::{compute_reminder_datetime, parse_natural_datetime, parse_time_option};
+use super::{CommandError, SlashCommand, defer_response, get_database, private_option};
+use crate::services::calendar::{CalendarEvent, build_ics};
 
 const COLOR_REMINDER: Colour = Colour::from_rgb(241, 196, 15);
+/// Longueur maximale du message personnalisé d'un rappel de candidature
+const MAX_REMINDER_NOTE_LENGTH: u16 = 300;
+/// Délai de relance par défaut (jours) quand aucun n'est configuré dans /settings
+const DEFAULT_FOLLOW_UP_DAYS: i64 = 7;
 
 // ============================================================================
 // SetReminder Command - Set a reminder for an application
@@ -38,6 +45,10 @@ impl SlashCommand for SetReminderCommand {
         "Set a follow-up reminder for a job application"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -63,7 +74,7 @@ impl SlashCommand for SetReminderCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "date",
-                    "Specific date for reminder (YYYY-MM-DD format)",
+                    "Specific date for reminder (YYYY-MM-DD or YYYY/MM/DD)",
                 )
                 .required(false),
             )
@@ -71,15 +82,24 @@ impl SlashCommand for SetReminderCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "time",
-                    "Time for the reminder (HH:MM format, default: 09:00)",
+                    "Time for the reminder (HH:MM format, default: your /settings reminder time)",
                 )
                 .required(false),
             )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "message",
+                    "Custom note included in the reminder (e.g. ask about the relocation package)",
+                )
+                .required(false)
+                .max_length(MAX_REMINDER_NOTE_LENGTH),
+            )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        interaction.defer(&ctx.http).await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id.get() as i64;
 
@@ -116,11 +136,23 @@ impl SlashCommand for SetReminderCommand {
             .and_then(|opt| opt.value.as_str())
             .map(|s| s.to_string());
 
-        let (hour, minute) = parse_time_option(time_str)?;
+        let note = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "message")
+            .and_then(|opt| opt.value.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
 
         // Get database
         let db = get_database(ctx).await?;
 
+        // Heure par défaut : celle configurée dans /settings
+        let settings = db.get_settings(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let (hour, minute) = parse_time_option(time_str.or(Some(settings.default_reminder_time)))?;
+
         // Verify application exists and belongs to user
         let app = db.get_application(application_id).await
             .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
@@ -131,24 +163,23 @@ impl SlashCommand for SetReminderCommand {
         }
 
         // Calculate reminder date
-        let reminder_datetime = if let Some(date) = date_str {
-            NaiveDateTime::parse_from_str(&format!("{} {:02}:{:02}:00", date, hour, minute), "%Y-%m-%d %H:%M:%S")
-                .map_err(|_| CommandError::InvalidInput("Invalid date format. Use YYYY-MM-DD".to_string()))?
-        } else {
-            let days_offset = days.unwrap_or(7);
-            let base = (Utc::now() + Duration::days(days_offset)).naive_utc();
-            base.date().and_hms_opt(hour, minute, 0).unwrap_or(base)
-        };
+        let reminder_datetime = compute_reminder_datetime(
+            days.unwrap_or(DEFAULT_FOLLOW_UP_DAYS),
+            date_str.as_deref(),
+            hour,
+            minute,
+            Utc::now().naive_utc(),
+        )?;
 
         let reminder_date_str = reminder_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
 
         // Set reminder
-        db.set_application_reminder(application_id, &reminder_date_str).await
+        db.set_application_reminder(application_id, &reminder_date_str, note.as_deref()).await
             .map_err(|e| CommandError::Internal(format!("Failed to set reminder: {}", e)))?;
 
         info!("Set reminder for application {} on {}", application_id, reminder_date_str);
 
-        let embed = CreateEmbed::new()
+        let mut embed = CreateEmbed::new()
             .title("Rappel programme")
             .colour(COLOR_REMINDER)
             .field("Candidature", format!("#{} - {} chez {}",
@@ -161,6 +192,9 @@ impl SlashCommand for SetReminderCommand {
             .footer(serenity::all::CreateEmbedFooter::new(
                 "Vous recevrez une notification automatique a cette date"
             ));
+        if let Some(ref note) = note {
+            embed = embed.field("Message", note, false);
+        }
 
         interaction
             .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
@@ -171,6 +205,72 @@ impl SlashCommand for SetReminderCommand {
     }
 }
 
+/// Bouton « Rappel de relance » de l'embed de suivi : quickreminder_{application_id}.
+/// Délai : `auto_reminder_days` des préférences s'il est activé, sinon 7 jours.
+pub async fn handle_quick_reminder_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let application_id: i64 = component
+        .data
+        .custom_id
+        .strip_prefix("quickreminder_")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| CommandError::InvalidInput("Invalid reminder button".to_string()))?;
+    let user_id = component.user.id.get() as i64;
+
+    let db = get_database(ctx).await?;
+    let app = db.get_application(application_id).await
+        .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?
+        .ok_or_else(|| CommandError::NotFound("Application not found".to_string()))?;
+    if app.user_id != user_id {
+        return Err(CommandError::Unauthorized("This application does not belong to you".to_string()));
+    }
+
+    let content = match app.reminder_date.as_deref().filter(|_| !app.reminder_sent) {
+        // Ne pas écraser un rappel déjà programmé (/setreminder permet de le modifier)
+        Some(existing) => {
+            let formatted = NaiveDateTime::parse_from_str(existing, "%Y-%m-%d %H:%M:%S")
+                .map(|dt| dt.format("%d/%m/%Y à %H:%M").to_string())
+                .unwrap_or_else(|_| existing.to_string());
+            format!(
+                "ℹ️ Un rappel est déjà programmé le **{}** pour la candidature #{}.\n\
+                 Utilisez `/setreminder` pour le modifier.",
+                formatted, application_id
+            )
+        }
+        None => {
+            let settings = db.get_settings(user_id).await
+                .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+            let days = if settings.auto_reminder_days > 0 {
+                settings.auto_reminder_days
+            } else {
+                DEFAULT_FOLLOW_UP_DAYS
+            };
+            let (hour, minute) = parse_time_option(Some(settings.default_reminder_time))?;
+            let reminder_datetime = compute_reminder_datetime(days, None, hour, minute, Utc::now().naive_utc())?;
+            let reminder_date_str = reminder_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
+
+            db.set_application_reminder(application_id, &reminder_date_str, None).await
+                .map_err(|e| CommandError::Internal(format!("Failed to set reminder: {}", e)))?;
+            info!("Quick reminder for application {} on {}", application_id, reminder_date_str);
+
+            format!(
+                "⏰ Rappel de relance programmé le **{}** (dans {} jour(s)) pour la candidature #{}.",
+                reminder_datetime.format("%d/%m/%Y à %H:%M"),
+                days,
+                application_id
+            )
+        }
+    };
+
+    let msg = CreateInteractionResponseMessage::new().content(content).ephemeral(true);
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Message(msg))
+        .await
+        .map_err(|e| CommandError::ResponseFailed(e.to_string()))
+}
+
 // ============================================================================
 // ListReminders Command - List all pending reminders
 // ============================================================================
@@ -199,13 +299,18 @@ impl SlashCommand for ListRemindersCommand {
         "List all your pending reminders"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
-        CreateCommand::new(self.name()).description(self.description())
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        interaction.defer(&ctx.http).await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id.get() as i64;
 
@@ -252,6 +357,9 @@ impl SlashCommand for ListRemindersCommand {
                     app.company.as_deref().unwrap_or("N/A"),
                     formatted_date
                 ));
+                if let Some(ref note) = app.reminder_note {
+                    description.push_str(&format!("  > {}\n", note));
+                }
             }
             description.push('\n');
         }
@@ -292,6 +400,149 @@ impl SlashCommand for ListRemindersCommand {
     }
 }
 
+// ============================================================================
+// Calendar Command - Export .ics des entretiens et rappels à venir
+// ============================================================================
+
+/// Durée affichée d'un entretien (la durée réelle n'est pas connue)
+const INTERVIEW_EVENT_MINUTES: i64 = 60;
+/// Durée d'un rappel dans le calendrier
+const REMINDER_EVENT_MINUTES: i64 = 15;
+
+pub struct CalendarCommand;
+
+impl CalendarCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CalendarCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for CalendarCommand {
+    fn name(&self) -> &'static str {
+        "calendar"
+    }
+
+    fn description(&self) -> &'static str {
+        "Export your upcoming interviews and reminders as an .ics calendar file"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(private_option())
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let now = Utc::now().naive_utc();
+        let db = get_database(ctx).await?;
+
+        let interviews = db.list_upcoming_interviews(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let app_reminders = db.list_user_application_reminders(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let standalone_reminders = db.list_user_reminders(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        let parse = |date: &str| NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").ok().filter(|dt| *dt >= now);
+        let mut events = Vec::new();
+        for app in &interviews {
+            if let Some(start) = app.interview_at.as_deref().and_then(parse) {
+                events.push(CalendarEvent {
+                    uid: format!("interview-{}@automate-cv-discordbot", app.id),
+                    start,
+                    duration: Duration::minutes(INTERVIEW_EVENT_MINUTES),
+                    summary: format!(
+                        "Entretien — {} chez {}",
+                        app.job_title.as_deref().unwrap_or("N/A"),
+                        app.company.as_deref().unwrap_or("N/A")
+                    ),
+                    description: Some(format!("Candidature #{}", app.id)),
+                    url: app.job_url.clone(),
+                });
+            }
+        }
+        for app in &app_reminders {
+            if let Some(start) = app.reminder_date.as_deref().and_then(parse) {
+                let mut description = format!("Rappel de suivi de la candidature #{}", app.id);
+                if let Some(ref note) = app.reminder_note {
+                    description.push_str(&format!("\n{}", note));
+                }
+                events.push(CalendarEvent {
+                    uid: format!("application-reminder-{}@automate-cv-discordbot", app.id),
+                    start,
+                    duration: Duration::minutes(REMINDER_EVENT_MINUTES),
+                    summary: format!(
+                        "Relance — {} chez {}",
+                        app.job_title.as_deref().unwrap_or("N/A"),
+                        app.company.as_deref().unwrap_or("N/A")
+                    ),
+                    description: Some(description),
+                    url: app.job_url.clone(),
+                });
+            }
+        }
+        for reminder in &standalone_reminders {
+            if let Some(start) = parse(&reminder.reminder_date) {
+                events.push(CalendarEvent {
+                    uid: format!("reminder-{}@automate-cv-discordbot", reminder.id),
+                    start,
+                    duration: Duration::minutes(REMINDER_EVENT_MINUTES),
+                    summary: format!("Rappel — {}", reminder.message),
+                    description: reminder.application_id.map(|id| format!("Candidature #{}", id)),
+                    url: None,
+                });
+            }
+        }
+
+        if events.is_empty() {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(
+                        "📅 Aucun entretien ni rappel à venir. Utilisez `/setinterview` ou `/setreminder` pour en programmer.",
+                    ),
+                )
+                .await
+                .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+            return Ok(());
+        }
+        events.sort_by_key(|event| event.start);
+
+        let ics = build_ics(&events, now);
+        info!("Exported {} calendar event(s) for user {}", events.len(), user_id);
+
+        let attachment = CreateAttachment::bytes(ics.into_bytes(), "candidatures.ics");
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .content(format!(
+                        "📅 **{}** événement(s) à venir. Importez le fichier dans Google Calendar, Outlook ou Apple Calendar (heures en UTC, converties par votre agenda).",
+                        events.len()
+                    ))
+                    .new_attachment(attachment),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
 // ============================================================================
 // ClearReminder Command - Remove a reminder
 // ============================================================================
@@ -320,6 +571,10 @@ impl SlashCommand for ClearReminderCommand {
         "Clear a reminder from an application"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -331,11 +586,11 @@ impl SlashCommand for ClearReminderCommand {
                 )
                 .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        interaction.defer(&ctx.http).await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id.get() as i64;
 
@@ -411,6 +666,10 @@ impl SlashCommand for CreateReminderCommand {
         "Create a custom reminder (not linked to an application)"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -436,7 +695,7 @@ impl SlashCommand for CreateReminderCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "date",
-                    "Specific date (YYYY-MM-DD format)",
+                    "Specific date (YYYY-MM-DD or YYYY/MM/DD)",
                 )
                 .required(false),
             )
@@ -444,15 +703,15 @@ impl SlashCommand for CreateReminderCommand {
                 CreateCommandOption::new(
                     CommandOptionType::String,
                     "time",
-                    "Time for the reminder (HH:MM format, default: 09:00)",
+                    "Time for the reminder (HH:MM format, default: your /settings reminder time)",
                 )
                 .required(false),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        interaction.defer(&ctx.http).await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id.get() as i64;
         let channel_id = interaction.channel_id.get() as i64;
@@ -489,19 +748,21 @@ impl SlashCommand for CreateReminderCommand {
             .and_then(|opt| opt.value.as_str())
             .map(|s| s.to_string());
 
-        let (hour, minute) = parse_time_option(time_str)?;
-
         let db = get_database(ctx).await?;
 
+        // Heure par défaut : celle configurée dans /settings
+        let settings = db.get_settings(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        let (hour, minute) = parse_time_option(time_str.or(Some(settings.default_reminder_time)))?;
+
         // Calculate reminder date
-        let reminder_datetime = if let Some(date) = date_str {
-            NaiveDateTime::parse_from_str(&format!("{} {:02}:{:02}:00", date, hour, minute), "%Y-%m-%d %H:%M:%S")
-                .map_err(|_| CommandError::InvalidInput("Invalid date format. Use YYYY-MM-DD".to_string()))?
-        } else {
-            let days_offset = days.unwrap_or(1);
-            let base = (Utc::now() + Duration::days(days_offset)).naive_utc();
-            base.date().and_hms_opt(hour, minute, 0).unwrap_or(base)
-        };
+        let reminder_datetime = compute_reminder_datetime(
+            days.unwrap_or(1),
+            date_str.as_deref(),
+            hour,
+            minute,
+            Utc::now().naive_utc(),
+        )?;
 
         let reminder_date_str = reminder_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
 
@@ -558,6 +819,10 @@ impl SlashCommand for DeleteReminderCommand {
         "Delete a custom reminder by its ID"
     }
 
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
     fn register(&self) -> CreateCommand {
         CreateCommand::new(self.name())
             .description(self.description())
@@ -569,11 +834,11 @@ impl SlashCommand for DeleteReminderCommand {
                 )
                 .required(true),
             )
+            .add_option(private_option())
     }
 
     async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
-        interaction.defer_ephemeral(&ctx.http).await
-            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
 
         let user_id = interaction.user.id.get() as i64;
 
@@ -607,21 +872,108 @@ impl SlashCommand for DeleteReminderCommand {
 }
 
 // ============================================================================
-// Helper: parse time string (HH:MM) into (hour, minute)
+// RemindMe Command - Natural-language shortcut over createreminder
 // ============================================================================
 
-fn parse_time_option(time_str: Option<String>) -> Result<(u32, u32), CommandError> {
-    let t = time_str.unwrap_or_else(|| "09:00".to_string());
-    let parts: Vec<&str> = t.splitn(2, ':').collect();
-    if parts.len() != 2 {
-        return Err(CommandError::InvalidInput("Invalid time format. Use HH:MM (e.g. 14:30)".to_string()));
+pub struct RemindMeCommand;
+
+impl RemindMeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RemindMeCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for RemindMeCommand {
+    fn name(&self) -> &'static str {
+        "remindme"
+    }
+
+    fn description(&self) -> &'static str {
+        "Quick reminder in natural language (e.g. \"in 2 days\", \"tomorrow 14:00\")"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "when",
+                    "When to remind you (in 2 days, tomorrow 14:00, monday, 2025-03-15 09:00...)",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "message",
+                    "Reminder message",
+                )
+                .required(true),
+            )
+            .add_option(private_option())
     }
-    let hour: u32 = parts[0].parse()
-        .map_err(|_| CommandError::InvalidInput("Invalid hour in time".to_string()))?;
-    let minute: u32 = parts[1].parse()
-        .map_err(|_| CommandError::InvalidInput("Invalid minute in time".to_string()))?;
-    if hour > 23 || minute > 59 {
-        return Err(CommandError::InvalidInput("Time out of range (HH: 0-23, MM: 0-59)".to_string()));
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let channel_id = interaction.channel_id.get() as i64;
+
+        let when = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "when")
+            .and_then(|opt| opt.value.as_str())
+            .ok_or_else(|| CommandError::MissingParameter("when".to_string()))?
+            .to_string();
+
+        let message = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "message")
+            .and_then(|opt| opt.value.as_str())
+            .ok_or_else(|| CommandError::MissingParameter("message".to_string()))?
+            .to_string();
+
+        let reminder_datetime = parse_natural_datetime(&when, Utc::now().naive_utc())?;
+        let reminder_date_str = reminder_datetime.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let db = get_database(ctx).await?;
+
+        let reminder_id = db.create_reminder(user_id, None, channel_id, &reminder_date_str, &message).await
+            .map_err(|e| CommandError::Internal(format!("Failed to create reminder: {}", e)))?;
+
+        info!("Created reminder {} for user {} via /remindme ({})", reminder_id, user_id, when);
+
+        let embed = CreateEmbed::new()
+            .title("Rappel cree")
+            .colour(COLOR_REMINDER)
+            .field("ID", format!("#{}", reminder_id), true)
+            .field("Date", reminder_datetime.format("%d/%m/%Y a %H:%M").to_string(), true)
+            .field("Message", &message, false)
+            .footer(serenity::all::CreateEmbedFooter::new(
+                "Vous serez notifie dans ce canal a la date prevue"
+            ));
+
+        interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
     }
-    Ok((hour, minute))
 }
@@ -0,0 +1,405 @@
+use async_trait::async_trait;
+use serenity::all::{
+    ButtonStyle, Colour, CommandInteraction, CommandOptionType, Context, CreateActionRow,
+    CreateButton, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+};
+use tracing::info;
+
+use super::{CommandError, SlashCommand, defer_response, get_database};
+use crate::db::{Database, UserSettings};
+
+const COLOR_SETTINGS: Colour = Colour::from_rgb(149, 165, 166);
+
+const REMINDER_TIMES: &[&str] = &["08:00", "09:00", "10:00", "12:00", "14:00", "17:00", "18:00"];
+
+const AUTO_REMINDER_DAYS: &[i64] = &[0, 3, 5, 7, 10, 14];
+
+// ============================================================================
+// Settings Command - Hub for per-user preferences
+// ============================================================================
+
+pub struct SettingsCommand;
+
+impl SettingsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SettingsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for SettingsCommand {
+    fn name(&self) -> &'static str {
+        "settings"
+    }
+
+    fn description(&self) -> &'static str {
+        "View or change your personal preferences"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "auto_reminder_days",
+                    "Auto follow-up reminder N days after marking an application as applied (0 = off)",
+                )
+                .required(false)
+                .min_int_value(0)
+                .max_int_value(90),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "auto_archive_days",
+                    "Archive accepted/rejected applications after N days without activity (0 = off)",
+                )
+                .required(false)
+                .min_int_value(0)
+                .max_int_value(365),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "min_fit_warn",
+                    "Warn in /applyjob when the match score is below this value (0 = off, default 40)",
+                )
+                .required(false)
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+
+        let auto_reminder_days = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "auto_reminder_days")
+            .and_then(|opt| opt.value.as_i64());
+        let auto_archive_days = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "auto_archive_days")
+            .and_then(|opt| opt.value.as_i64());
+        let min_fit_warn = interaction
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == "min_fit_warn")
+            .and_then(|opt| opt.value.as_i64());
+
+        let db = get_database(ctx).await?;
+
+        // La table user_settings référence users(id)
+        db.upsert_user(user_id, &interaction.user.name).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        let mut settings = db.get_settings(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        if let Some(days) = auto_reminder_days {
+            settings.auto_reminder_days = days;
+            info!("User {} set auto_reminder_days = {}", user_id, days);
+        }
+        if let Some(days) = auto_archive_days {
+            settings.auto_archive_days = days;
+            info!("User {} set auto_archive_days = {}", user_id, days);
+        }
+        if let Some(score) = min_fit_warn {
+            settings.min_fit_warn = score;
+            info!("User {} set min_fit_warn = {}", user_id, score);
+        }
+        if auto_reminder_days.is_some() || auto_archive_days.is_some() || min_fit_warn.is_some() {
+            db.update_settings(&settings).await
+                .map_err(|e| CommandError::Internal(format!("Failed to save settings: {}", e)))?;
+        }
+
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(build_settings_embed(&settings))
+                    .components(build_settings_components(&settings)),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// SetTarget Command - Objectif salarial comparé aux offres analysées
+// ============================================================================
+
+pub struct SetTargetCommand;
+
+impl SetTargetCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SetTargetCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SlashCommand for SetTargetCommand {
+    fn name(&self) -> &'static str {
+        "settarget"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set your target salary, compared to each analyzed offer"
+    }
+
+    fn default_ephemeral(&self) -> bool {
+        true
+    }
+
+    fn register(&self) -> CreateCommand {
+        CreateCommand::new(self.name())
+            .description(self.description())
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "min",
+                    "Minimum gross yearly salary in thousands (e.g. 45 for 45k€, 0 = remove the target)",
+                )
+                .required(true)
+                .min_int_value(0)
+                .max_int_value(1000),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "max", "Maximum gross yearly salary in thousands")
+                    .required(false)
+                    .min_int_value(1)
+                    .max_int_value(1000),
+            )
+    }
+
+    async fn execute(&self, ctx: &Context, interaction: &CommandInteraction) -> Result<(), CommandError> {
+        let int_option = |name: &str| {
+            interaction
+                .data
+                .options
+                .iter()
+                .find(|opt| opt.name == name)
+                .and_then(|opt| opt.value.as_i64())
+        };
+        let min = int_option("min").ok_or_else(|| CommandError::MissingParameter("min".to_string()))?;
+        let max = int_option("max");
+        if let Some(max) = max {
+            if min > 0 && max < min {
+                return Err(CommandError::InvalidInput("`max` doit être supérieur ou égal à `min`".to_string()));
+            }
+        }
+
+        defer_response(ctx, interaction, self.is_ephemeral(interaction)).await?;
+
+        let user_id = interaction.user.id.get() as i64;
+        let db = get_database(ctx).await?;
+
+        // La table user_settings référence users(id)
+        db.upsert_user(user_id, &interaction.user.name).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+
+        let mut settings = db.get_settings(user_id).await
+            .map_err(|e| CommandError::Internal(format!("Database error: {}", e)))?;
+        if min == 0 {
+            settings.target_salary_min = None;
+            settings.target_salary_max = None;
+        } else {
+            settings.target_salary_min = Some(min * 1000);
+            settings.target_salary_max = max.map(|max| max * 1000);
+        }
+        db.update_settings(&settings).await
+            .map_err(|e| CommandError::Internal(format!("Failed to save settings: {}", e)))?;
+        info!("User {} set salary target {:?}", user_id, settings.salary_target());
+
+        let content = match settings.salary_target() {
+            Some(_) => "🎯 **Objectif salarial enregistré.**\n\
+                        Les prochaines analyses salariales de `/applyjob` le compareront à l'offre et au marché.",
+            None => "🎯 Objectif salarial supprimé.",
+        };
+        interaction
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().content(content).embed(build_settings_embed(&settings)),
+            )
+            .await
+            .map_err(|e| CommandError::ResponseFailed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Settings hub helpers (embed, components, component handling)
+// ============================================================================
+
+/// Construit l'embed récapitulant les préférences de l'utilisateur
+pub fn build_settings_embed(settings: &UserSettings) -> CreateEmbed {
+    let notification_display = match settings.notification_channel_id {
+        Some(channel_id) => format!("<#{}>", channel_id),
+        None => "📩 Message privé".to_string(),
+    };
+
+    let auto_reminder_display = if settings.auto_reminder_days > 0 {
+        format!("✅ {} jour(s) après le passage en « Postulée »", settings.auto_reminder_days)
+    } else {
+        "❌ Désactivé".to_string()
+    };
+
+    let min_fit_display = if settings.min_fit_warn > 0 {
+        format!("⚠️ Score sous {}%", settings.min_fit_warn)
+    } else {
+        "❌ Désactivé".to_string()
+    };
+
+    let salary_target_display = match settings.salary_target() {
+        Some((min, Some(max))) => format!("💰 {}k€ - {}k€", min / 1000, max / 1000),
+        Some((min, None)) => format!("💰 ≥ {}k€", min / 1000),
+        None => "❌ Non défini (`/settarget`)".to_string(),
+    };
+
+    let auto_archive_display = if settings.auto_archive_days > 0 {
+        format!("✅ Acceptées / refusées après {} jour(s) sans activité", settings.auto_archive_days)
+    } else {
+        "❌ Désactivé".to_string()
+    };
+
+    CreateEmbed::new()
+        .title("⚙️ Mes préférences")
+        .colour(COLOR_SETTINGS)
+        .field("🔔 Notifications", notification_display, true)
+        .field("⏰ Heure des rappels par défaut (UTC)", &settings.default_reminder_time, true)
+        .field("📅 Rappel automatique", auto_reminder_display, true)
+        .field("🗄️ Archivage automatique", auto_archive_display, true)
+        .field("🔒 Réponses privées par défaut", on_off(settings.private_by_default), true)
+        .field("🎯 Objectif salarial", salary_target_display, true)
+        .field("📉 Alerte correspondance faible", min_fit_display, true)
+        .field("🗓️ Relance des entretiens", on_off(settings.interview_nudges), true)
+        .footer(CreateEmbedFooter::new("Modifiez vos préférences avec les menus ci-dessous"))
+}
+
+/// Construit les menus et boutons du hub /settings
+pub fn build_settings_components(settings: &UserSettings) -> Vec<CreateActionRow> {
+    let time_options = REMINDER_TIMES
+        .iter()
+        .map(|t| {
+            CreateSelectMenuOption::new(format!("Rappels à {} (UTC)", t), *t)
+                .default_selection(*t == settings.default_reminder_time)
+        })
+        .collect();
+
+    let auto_reminder_options = AUTO_REMINDER_DAYS
+        .iter()
+        .map(|days| {
+            let label = if *days == 0 {
+                "Rappel automatique désactivé".to_string()
+            } else {
+                format!("Rappel automatique après {} jours", days)
+            };
+            CreateSelectMenuOption::new(label, days.to_string())
+                .default_selection(*days == settings.auto_reminder_days)
+        })
+        .collect();
+
+    let toggle_style = |enabled: bool| if enabled { ButtonStyle::Success } else { ButtonStyle::Secondary };
+
+    vec![
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("settings_remindertime", CreateSelectMenuKind::String { options: time_options })
+                .placeholder("Heure des rappels par défaut"),
+        ),
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("settings_autoreminder", CreateSelectMenuKind::String { options: auto_reminder_options })
+                .placeholder("Rappel automatique"),
+        ),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new("settings_private")
+                .label("🔒 Privé")
+                .style(toggle_style(settings.private_by_default)),
+            CreateButton::new("settings_interviewnudge")
+                .label("🗓️ Relance entretiens")
+                .style(toggle_style(settings.interview_nudges)),
+            CreateButton::new("settings_notifhere")
+                .label("🔔 Notifier ici")
+                .style(ButtonStyle::Primary),
+            CreateButton::new("settings_notifdm")
+                .label("📩 Notifier en MP")
+                .style(ButtonStyle::Secondary),
+        ]),
+    ]
+}
+
+/// Applique le changement demandé par un composant du hub /settings.
+///
+/// `value` est la valeur choisie dans un menu déroulant (None pour les boutons),
+/// `channel_id` le canal où le hub est affiché (utilisé par « Notifier ici »).
+pub async fn apply_settings_component(
+    db: &Database,
+    user_id: i64,
+    custom_id: &str,
+    value: Option<String>,
+    channel_id: i64,
+) -> Result<(), CommandError> {
+    let db_err = |e: rusqlite::Error| CommandError::Internal(format!("Database error: {}", e));
+    let selected = || {
+        value
+            .clone()
+            .ok_or_else(|| CommandError::MissingParameter("selected value".to_string()))
+    };
+
+    let mut settings = db.get_settings(user_id).await.map_err(db_err)?;
+
+    match custom_id {
+        "settings_remindertime" => {
+            let time = selected()?;
+            if !REMINDER_TIMES.contains(&time.as_str()) {
+                return Err(CommandError::InvalidInput(format!("Unknown reminder time `{}`", time)));
+            }
+            settings.default_reminder_time = time;
+        }
+        "settings_autoreminder" => {
+            settings.auto_reminder_days = selected()?
+                .parse()
+                .map_err(|_| CommandError::InvalidInput("Invalid number of days".to_string()))?;
+        }
+        "settings_private" => settings.private_by_default = !settings.private_by_default,
+        "settings_interviewnudge" => settings.interview_nudges = !settings.interview_nudges,
+        "settings_notifhere" => settings.notification_channel_id = Some(channel_id),
+        "settings_notifdm" => settings.notification_channel_id = None,
+        other => {
+            return Err(CommandError::InvalidInput(format!("Unknown settings component `{}`", other)));
+        }
+    }
+
+    info!("User {} updated settings via {}", user_id, custom_id);
+    db.update_settings(&settings).await.map_err(db_err)
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "✅ Activé" } else { "❌ Désactivé" }
+}
@@ -0,0 +1,386 @@
+// Configuration du bot lue depuis l'environnement (.env / docker-compose)
+// Toutes les variables sont validées au démarrage : les erreurs sont regroupées
+// dans un seul message au lieu de paniquer sur la première variable manquante.
+use serenity::all::GatewayIntents;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::services::ClaudeTimeouts;
+use crate::services::matching::DEFAULT_DUPLICATE_THRESHOLD;
+
+const DEFAULT_CLAUDE_API_URL: &str = "http://claudecode:8080";
+const DEFAULT_BACKUP_DIR: &str = "dbLookout/backups";
+const DEFAULT_BOT_ACTIVITY: &str = "les offres d'emploi | /help · {applications} candidatures suivies";
+/// Nom des threads d'analyse : `{label}` = « Entreprise - Poste » (libellés manquants omis)
+pub const DEFAULT_THREAD_NAME_TEMPLATE: &str = "📋 {label}";
+/// Longueur maximale du nom d'une activité Discord
+const MAX_ACTIVITY_CHARS: usize = 128;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Token du bot Discord (obligatoire)
+    pub discord_token: String,
+    /// Serveur de développement : commandes enregistrées sur ce serveur uniquement
+    pub guild_id: Option<u64>,
+    /// URL du serveur Claude
+    pub claude_api_url: String,
+    /// Délai (secondes) des appels d'analyse : synthèse, compétences, salaire
+    pub synth_timeout_secs: i64,
+    /// Délai (secondes) des appels de génération : CV personnalisé, PDF, prompts libres
+    pub cvgen_timeout_secs: i64,
+    /// Webhook d'événements de candidature (optionnel)
+    pub event_webhook_url: Option<String>,
+    /// Seuil de similarité pour la détection des doublons
+    pub duplicate_threshold: f64,
+    /// Délai (jours) avant relance d'un rappel resté sans suite
+    pub reminder_escalation_days: i64,
+    /// Nombre maximum de relances par rappel
+    pub reminder_max_escalations: i64,
+    /// Nombre maximum de rappels envoyés par passage (5 min) ; le reste attend le passage suivant
+    pub reminder_max_per_tick: i64,
+    /// Nombre d'échecs d'envoi (DMs fermés, erreur Discord) avant d'abandonner un rappel
+    pub reminder_max_retries: i64,
+    /// Mode test : les rappels sont loggés mais ni envoyés ni marqués comme envoyés
+    pub reminder_dry_run: bool,
+    /// Jours sans activité avant la relance d'une candidature au stade entretien (INTERVIEW_NUDGE_DAYS) ;
+    /// seule l'activation des relances est une option de /settings
+    pub interview_nudge_days: i64,
+    /// Réagir aux messages (offre collée, CV déposé) : nécessite l'intent MESSAGE_CONTENT
+    pub message_triggers_enabled: bool,
+    /// Canal surveillé pour les déclencheurs par message (tous les canaux si absent)
+    pub watch_channel_id: Option<u64>,
+    /// /applyjob affiche un aperçu à confirmer avant les étapes coûteuses (défaut de l'option `preview`)
+    pub applyjob_preview: bool,
+    /// Taille d'offre (caractères) au-delà de laquelle /applyjob demande de confirmer le coût estimé (0 = jamais)
+    pub applyjob_confirm_chars: i64,
+    /// Corps complets des requêtes au serveur Claude dans les logs debug (CV, offres : données personnelles)
+    pub log_full_bodies: bool,
+    /// Intervalle (heures) entre deux sauvegardes de la base (0 = désactivé)
+    pub backup_interval_hours: i64,
+    /// Dossier des sauvegardes de la base
+    pub backup_dir: String,
+    /// Nombre de sauvegardes conservées (les plus anciennes sont supprimées)
+    pub backup_keep: i64,
+    /// Activité « Regarde … » du bot, `{applications}` remplacé par le nombre de candidatures
+    /// suivies (None = pas d'activité, `BOT_ACTIVITY=none`)
+    pub bot_activity: Option<String>,
+    /// Nom des threads d'analyse : `{label}`, `{company}`, `{title}`, `{score}`, `{status}`, `{emoji}`
+    pub thread_name_template: String,
+}
+
+/// Liste de toutes les variables manquantes ou invalides
+#[derive(Debug)]
+pub struct ConfigError(pub Vec<String>);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Invalid configuration ({} problem(s)):", self.0.len())?;
+        for problem in &self.0 {
+            writeln!(f, "  - {}", problem)?;
+        }
+        write!(f, "See .env.example for the expected variables.")
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Lit et valide la configuration depuis les variables d'environnement
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Lit la configuration via une fonction de lecture (injectable pour les tests)
+    pub fn from_lookup<F>(lookup: F) -> Result<Self, ConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut errors = Vec::new();
+        // Les valeurs vides sont traitées comme absentes
+        let get = |key: &str| lookup(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let discord_token = match get("DISCORD_BOT_TOKEN") {
+            Some(token) if token.contains(char::is_whitespace) => {
+                errors.push("DISCORD_BOT_TOKEN must not contain spaces".to_string());
+                String::new()
+            }
+            Some(token) => token,
+            None => {
+                errors.push("DISCORD_BOT_TOKEN is required (Discord developer portal > Bot > Token)".to_string());
+                String::new()
+            }
+        };
+
+        let guild_id = parse_id(&get, "GUILD_ID", &mut errors);
+
+        let claude_api_url = get("CLAUDE_API_URL").unwrap_or_else(|| DEFAULT_CLAUDE_API_URL.to_string());
+        if !is_http_url(&claude_api_url) {
+            errors.push(format!("CLAUDE_API_URL must start with http:// or https:// (got `{}`)", claude_api_url));
+        }
+
+        let synth_timeout_secs = parse_int(&get, "SYNTH_TIMEOUT", 120, 10, &mut errors);
+        let cvgen_timeout_secs = parse_int(&get, "CVGEN_TIMEOUT", 300, 10, &mut errors);
+
+        let event_webhook_url = get("EVENT_WEBHOOK_URL");
+        if let Some(ref url) = event_webhook_url {
+            if !is_http_url(url) {
+                errors.push(format!("EVENT_WEBHOOK_URL must start with http:// or https:// (got `{}`)", url));
+            }
+        }
+
+        let duplicate_threshold = match get("DUPLICATE_SIMILARITY_THRESHOLD") {
+            Some(v) => match v.parse::<f64>() {
+                Ok(t) if (0.0..=1.0).contains(&t) => t,
+                _ => {
+                    errors.push(format!("DUPLICATE_SIMILARITY_THRESHOLD must be between 0.0 and 1.0 (got `{}`)", v));
+                    DEFAULT_DUPLICATE_THRESHOLD
+                }
+            },
+            None => DEFAULT_DUPLICATE_THRESHOLD,
+        };
+
+        let reminder_escalation_days = parse_int(&get, "REMINDER_ESCALATION_DAYS", 3, 1, &mut errors);
+        let reminder_max_escalations = parse_int(&get, "REMINDER_MAX_ESCALATIONS", 2, 0, &mut errors);
+        let reminder_max_per_tick = parse_int(&get, "REMINDER_MAX_PER_TICK", 20, 1, &mut errors);
+        let reminder_max_retries = parse_int(&get, "REMINDER_MAX_RETRIES", 3, 1, &mut errors);
+        let reminder_dry_run = parse_bool(&get, "REMINDER_DRY_RUN", &mut errors);
+        let interview_nudge_days = parse_int(&get, "INTERVIEW_NUDGE_DAYS", 14, 1, &mut errors);
+
+        let message_triggers_enabled = parse_bool(&get, "MESSAGE_TRIGGERS_ENABLED", &mut errors);
+        let watch_channel_id = parse_id(&get, "WATCH_CHANNEL_ID", &mut errors);
+        let applyjob_preview = parse_bool(&get, "APPLYJOB_PREVIEW", &mut errors);
+        let applyjob_confirm_chars = parse_int(&get, "APPLYJOB_CONFIRM_CHARS", 20000, 0, &mut errors);
+        let log_full_bodies = parse_bool(&get, "LOG_FULL_BODIES", &mut errors);
+
+        let backup_interval_hours = parse_int(&get, "BACKUP_INTERVAL_HOURS", 0, 0, &mut errors);
+        let backup_dir = get("BACKUP_DIR").unwrap_or_else(|| DEFAULT_BACKUP_DIR.to_string());
+        let backup_keep = parse_int(&get, "BACKUP_KEEP", 7, 1, &mut errors);
+
+        let bot_activity = match get("BOT_ACTIVITY") {
+            Some(v) if v.eq_ignore_ascii_case("none") => None,
+            Some(v) => Some(v),
+            None => Some(DEFAULT_BOT_ACTIVITY.to_string()),
+        };
+
+        let thread_name_template = get("THREAD_NAME_TEMPLATE").unwrap_or_else(|| DEFAULT_THREAD_NAME_TEMPLATE.to_string());
+        if !["{label}", "{company}", "{title}"].iter().any(|p| thread_name_template.contains(p)) {
+            errors.push(format!(
+                "THREAD_NAME_TEMPLATE must contain {{label}}, {{company}} or {{title}} (got `{}`)",
+                thread_name_template
+            ));
+        }
+
+        if !errors.is_empty() {
+            return Err(ConfigError(errors));
+        }
+
+        Ok(Self {
+            discord_token,
+            guild_id,
+            claude_api_url,
+            synth_timeout_secs,
+            cvgen_timeout_secs,
+            event_webhook_url,
+            duplicate_threshold,
+            reminder_escalation_days,
+            reminder_max_escalations,
+            reminder_max_per_tick,
+            reminder_max_retries,
+            reminder_dry_run,
+            interview_nudge_days,
+            message_triggers_enabled,
+            watch_channel_id,
+            applyjob_preview,
+            applyjob_confirm_chars,
+            log_full_bodies,
+            backup_interval_hours,
+            backup_dir,
+            backup_keep,
+            bot_activity,
+            thread_name_template,
+        })
+    }
+
+    /// Délais des requêtes vers le serveur Claude
+    pub fn claude_timeouts(&self) -> ClaudeTimeouts {
+        ClaudeTimeouts {
+            synthesis: Duration::from_secs(self.synth_timeout_secs as u64),
+            generation: Duration::from_secs(self.cvgen_timeout_secs as u64),
+        }
+    }
+
+    /// Texte de l'activité affichée par le bot pour `applications` candidatures suivies
+    pub fn activity_text(&self, applications: i64) -> Option<String> {
+        let text = self.bot_activity.as_ref()?.replace("{applications}", &applications.to_string());
+        Some(text.chars().take(MAX_ACTIVITY_CHARS).collect())
+    }
+
+    /// Intents Gateway nécessaires selon les fonctionnalités activées
+    pub fn gateway_intents(&self) -> GatewayIntents {
+        if self.message_triggers_enabled {
+            GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT
+        } else {
+            GatewayIntents::empty()
+        }
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Booléen optionnel (défaut: false)
+fn parse_bool<F>(get: &F, key: &str, errors: &mut Vec<String>) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match get(key).map(|v| v.to_lowercase()) {
+        Some(v) if matches!(v.as_str(), "1" | "true" | "yes" | "on") => true,
+        Some(v) if matches!(v.as_str(), "0" | "false" | "no" | "off") => false,
+        Some(v) => {
+            errors.push(format!("{} must be true or false (got `{}`)", key, v));
+            false
+        }
+        None => false,
+    }
+}
+
+fn parse_id<F>(get: &F, key: &str, errors: &mut Vec<String>) -> Option<u64>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    get(key).and_then(|v| match v.parse::<u64>() {
+        Ok(id) if id > 0 => Some(id),
+        _ => {
+            errors.push(format!("{} must be a numeric Discord ID (got `{}`)", key, v));
+            None
+        }
+    })
+}
+
+fn parse_int<F>(get: &F, key: &str, default: i64, min: i64, errors: &mut Vec<String>) -> i64
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match get(key) {
+        Some(v) => match v.parse::<i64>() {
+            Ok(n) if n >= min => n,
+            _ => {
+                errors.push(format!("{} must be an integer >= {} (got `{}`)", key, min, v));
+                default
+            }
+        },
+        None => default,
+    }
+}
+
+// Pour l'injection dans Serenity TypeMap
+impl serenity::prelude::TypeMapKey for Config {
+    type Value = Arc<Config>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn test_minimal_config_uses_defaults() {
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def")]).unwrap();
+        assert_eq!(config.claude_api_url, DEFAULT_CLAUDE_API_URL);
+        assert_eq!(config.guild_id, None);
+        assert_eq!(config.reminder_escalation_days, 3);
+        assert_eq!(config.reminder_max_per_tick, 20);
+        assert_eq!(config.reminder_max_retries, 3);
+        assert_eq!(config.claude_timeouts().synthesis, Duration::from_secs(120));
+        assert_eq!(config.claude_timeouts().generation, Duration::from_secs(300));
+        assert!(!config.reminder_dry_run);
+        assert_eq!(config.interview_nudge_days, 14);
+        assert!(config.gateway_intents().is_empty());
+        assert_eq!(config.backup_interval_hours, 0);
+        assert_eq!(config.backup_dir, DEFAULT_BACKUP_DIR);
+        assert_eq!(config.backup_keep, 7);
+        assert_eq!(config.applyjob_confirm_chars, 20000);
+        assert!(!config.log_full_bodies);
+        assert_eq!(config.thread_name_template, DEFAULT_THREAD_NAME_TEMPLATE);
+    }
+
+    #[test]
+    fn test_bot_activity() {
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def")]).unwrap();
+        assert_eq!(
+            config.activity_text(42).as_deref(),
+            Some("les offres d'emploi | /help · 42 candidatures suivies")
+        );
+
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("BOT_ACTIVITY", "job offers | /help")]).unwrap();
+        assert_eq!(config.activity_text(42).as_deref(), Some("job offers | /help"));
+
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("BOT_ACTIVITY", "None")]).unwrap();
+        assert_eq!(config.activity_text(42), None);
+
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("BOT_ACTIVITY", &"x".repeat(200))]).unwrap();
+        assert_eq!(config.activity_text(0).unwrap().chars().count(), MAX_ACTIVITY_CHARS);
+    }
+
+    #[test]
+    fn test_thread_name_template() {
+        let template = "{emoji} {company} - {title} ({score}%)";
+        let config = config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("THREAD_NAME_TEMPLATE", template)]).unwrap();
+        assert_eq!(config.thread_name_template, template);
+
+        assert!(config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("THREAD_NAME_TEMPLATE", "{score}%")]).is_err());
+    }
+
+    #[test]
+    fn test_backup_settings() {
+        let config = config_from(&[
+            ("DISCORD_BOT_TOKEN", "abc.def"),
+            ("BACKUP_INTERVAL_HOURS", "24"),
+            ("BACKUP_DIR", "/backups"),
+            ("BACKUP_KEEP", "3"),
+        ])
+        .unwrap();
+        assert_eq!(config.backup_interval_hours, 24);
+        assert_eq!(config.backup_dir, "/backups");
+        assert_eq!(config.backup_keep, 3);
+
+        assert!(config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("BACKUP_KEEP", "0")]).is_err());
+    }
+
+    #[test]
+    fn test_message_triggers_enable_intents() {
+        let config = config_from(&[
+            ("DISCORD_BOT_TOKEN", "abc.def"),
+            ("MESSAGE_TRIGGERS_ENABLED", "true"),
+            ("WATCH_CHANNEL_ID", "123456"),
+        ])
+        .unwrap();
+        assert_eq!(config.watch_channel_id, Some(123456));
+        assert!(config.gateway_intents().contains(GatewayIntents::MESSAGE_CONTENT));
+
+        assert!(config_from(&[("DISCORD_BOT_TOKEN", "abc.def"), ("MESSAGE_TRIGGERS_ENABLED", "maybe")]).is_err());
+    }
+
+    #[test]
+    fn test_all_problems_are_reported_at_once() {
+        let err = config_from(&[
+            ("GUILD_ID", "my-server"),
+            ("CLAUDE_API_URL", "claudecode:8080"),
+            ("DUPLICATE_SIMILARITY_THRESHOLD", "90"),
+        ])
+        .unwrap_err();
+        assert_eq!(err.0.len(), 4);
+        let message = err.to_string();
+        assert!(message.contains("DISCORD_BOT_TOKEN"));
+        assert!(message.contains("GUILD_ID"));
+        assert!(message.contains("CLAUDE_API_URL"));
+        assert!(message.contains("DUPLICATE_SIMILARITY_THRESHOLD"));
+    }
+}
@@ -5,6 +5,8 @@ use std::path::Path;
 use std::fs;
 use tracing::info;
 
+use crate::services::matching;
+
 const DB_DIR: &str = "dbLookout";
 const DB_NAME: &str = "bot.db";
 
@@ -22,9 +24,16 @@ pub fn init_database() -> Result<Connection> {
     }
 
     let db_path = get_db_path();
-    let conn = Connection::open(&db_path)?;
+    let conn = open_database(Path::new(&db_path))?;
+    info!("Database initialized successfully");
+    Ok(conn)
+}
+
+/// Ouvre la base `db_path` et applique le schéma et les migrations
+pub fn open_database(db_path: &Path) -> Result<Connection> {
+    let conn = Connection::open(db_path)?;
     
-    info!("Connected to database: {}", db_path);
+    info!("Connected to database: {:?}", db_path);
 
     // Activer les foreign keys
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
@@ -32,7 +41,6 @@ pub fn init_database() -> Result<Connection> {
     // Créer les tables
     create_tables(&conn)?;
 
-    info!("Database initialized successfully");
     Ok(conn)
 }
 
@@ -263,6 +271,270 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         conn.execute("INSERT INTO schema_migrations (version) VALUES (2)", [])?;
     }
 
+    // Migration 3: Préférences utilisateur (rappel automatique après candidature)
+    if !is_applied(3)? {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS user_settings (
+                user_id             INTEGER PRIMARY KEY,
+                auto_reminder_days  INTEGER DEFAULT 0,  -- 0 = désactivé
+                updated_at          DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (3)", [])?;
+    }
+
+    // Migration 4: Préférences supplémentaires pour le hub /settings
+    if !is_applied(4)? {
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN locale TEXT DEFAULT 'fr'", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN timezone TEXT DEFAULT 'Europe/Paris'", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN notification_channel_id INTEGER", []);  // NULL = DM
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN default_reminder_time TEXT DEFAULT '09:00'", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN digest_enabled INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN private_by_default INTEGER DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (4)", [])?;
+    }
+
+    // Migration 5: Texte du CV généré et liste des adaptations (pour /comparecv)
+    if !is_applied(5)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN generated_cv_text TEXT", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN cv_adaptations TEXT", []);  // JSON
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (5)", [])?;
+    }
+
+    // Migration 6: Relances des rappels de candidature restés sans suite
+    if !is_applied(6)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN reminder_escalation_count INTEGER DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN last_reminded_at DATETIME", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN reminded_status TEXT", []);  // statut au moment du rappel
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (6)", [])?;
+    }
+
+    // Migration 7: Pièces jointes par candidature (emails recruteurs, offres...)
+    if !is_applied(7)? {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS application_attachments (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                application_id  INTEGER NOT NULL,
+                user_id         INTEGER NOT NULL,
+                filename        TEXT NOT NULL,      -- nom d'origine
+                path            TEXT NOT NULL,      -- chemin de stockage
+                file_size       INTEGER,
+                mime_type       TEXT,
+                created_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (application_id) REFERENCES job_applications(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_attachments_application ON application_attachments(application_id)",
+            [],
+        )?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (7)", [])?;
+    }
+
+    // Migration 8: Chaînage des versions d'une candidature (/applyjob link_to)
+    if !is_applied(8)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN parent_application_id INTEGER", []);
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_job_applications_parent ON job_applications(parent_application_id)",
+            [],
+        )?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (8)", [])?;
+    }
+
+    // Migration 9: Message personnalisé des rappels de candidature
+    if !is_applied(9)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN reminder_note TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (9)", [])?;
+    }
+
+    // Migration 10: Avantages extraits de l'offre (JSON), pour /benefits
+    if !is_applied(10)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN benefits TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (10)", [])?;
+    }
+
+    // Migration 11: Archivage automatique des candidatures terminées
+    if !is_applied(11)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN archived_at TEXT", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN auto_archive_days INTEGER DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (11)", [])?;
+    }
+
+    // Migration 12: corbeille des CVs (/deletecv, /trash, /restorecv)
+    if !is_applied(12)? {
+        let _ = conn.execute("ALTER TABLE base_cvs ADD COLUMN deleted_at TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (12)", [])?;
+    }
+
+    // Migration 13: Objectif salarial de l'utilisateur (/settarget)
+    if !is_applied(13)? {
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN target_salary_min INTEGER", []);
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN target_salary_max INTEGER", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (13)", [])?;
+    }
+
+    // Migration 14: Seuil d'avertissement du score de correspondance
+    if !is_applied(14)? {
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN min_fit_warn INTEGER DEFAULT 40", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (14)", [])?;
+    }
+
+    // Migration 15: Langue détectée de l'offre (/applyjob)
+    if !is_applied(15)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN language TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (15)", [])?;
+    }
+
+    // Migration 16: candidatures réellement envoyées (vs seulement analysées), pour /mystats.
+    // Les candidatures existantes passées par « Postulée » ou au-delà sont marquées.
+    if !is_applied(16)? {
+        let _ = conn.execute(
+            "ALTER TABLE job_applications ADD COLUMN applied_externally INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        conn.execute(
+            "UPDATE job_applications SET applied_externally = 1
+             WHERE applied_at IS NOT NULL OR status IN ('applied', 'interview', 'offer', 'accepted')",
+            [],
+        )?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (16)", [])?;
+    }
+
+    // Migration 17: étape atteinte par le pipeline /applyjob et salon d'origine,
+    // pour détecter au démarrage les analyses interrompues par un redémarrage.
+    if !is_applied(17)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN pipeline_stage TEXT", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN origin_channel_id INTEGER", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (17)", [])?;
+    }
+
+    // Migration 18: synthèse complète de l'offre en JSON (job_synthesis ne garde que le résumé),
+    // pour relancer /applyjob sans refaire la synthèse.
+    if !is_applied(18)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN synthesis_json TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (18)", [])?;
+    }
+
+    // Migration 19: entreprises / intitulés de remplacement ("Non spécifié", "N/A"...) remis à NULL
+    if !is_applied(19)? {
+        let placeholders: Vec<(i64, bool, bool)> = {
+            let mut stmt = conn.prepare(
+                "SELECT id, company, job_title FROM job_applications
+                 WHERE company IS NOT NULL OR job_title IS NOT NULL",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let company: Option<String> = row.get(1)?;
+                let title: Option<String> = row.get(2)?;
+                let is_placeholder = |v: &Option<String>| {
+                    v.as_deref().is_some_and(|v| matching::meaningful_label(v).is_none())
+                };
+                Ok((row.get(0)?, is_placeholder(&company), is_placeholder(&title)))
+            })?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        for (id, company, title) in placeholders {
+            if company {
+                conn.execute("UPDATE job_applications SET company = NULL WHERE id = ?1", (id,))?;
+            }
+            if title {
+                conn.execute("UPDATE job_applications SET job_title = NULL WHERE id = ?1", (id,))?;
+            }
+        }
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (19)", [])?;
+    }
+
+    // Migration 20: compteur d'échecs d'envoi des rappels (abandon après REMINDER_MAX_RETRIES)
+    if !is_applied(20)? {
+        let _ = conn.execute(
+            "ALTER TABLE job_applications ADD COLUMN reminder_retry_count INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = conn.execute(
+            "ALTER TABLE job_applications ADD COLUMN reminder_send_failed INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = conn.execute("ALTER TABLE reminders ADD COLUMN retry_count INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE reminders ADD COLUMN send_failed INTEGER NOT NULL DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (20)", [])?;
+    }
+
+    // Migration 21: commandes activées / désactivées par serveur (/togglecommand)
+    if !is_applied(21)? {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS guild_command_config (
+                guild_id      INTEGER NOT NULL,
+                command_name  TEXT NOT NULL,
+                enabled       INTEGER NOT NULL DEFAULT 1,
+                updated_at    DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (guild_id, command_name)
+            )",
+            [],
+        )?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (21)", [])?;
+    }
+
+    // Migration 22: relance des entretiens sans nouvelles (option de /settings)
+    if !is_applied(22)? {
+        let _ = conn.execute("ALTER TABLE user_settings ADD COLUMN interview_nudges INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN interview_nudged_at DATETIME", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (22)", [])?;
+    }
+
+    // Migration 23: date de l'entretien programmé (/setinterview)
+    if !is_applied(23)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN interview_at DATETIME", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (23)", [])?;
+    }
+
+    // Migration 24: embed de suivi réduit dans le salon (/applyjob visibility:minimal)
+    if !is_applied(24)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN minimal_tracking INTEGER NOT NULL DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (24)", [])?;
+    }
+
+    // Migration 25: niveau de séniorité visé (/applyjob seniority)
+    if !is_applied(25)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN seniority TEXT", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (25)", [])?;
+    }
+
+    // Migration 26: CV anonymisé avant envoi au serveur Claude (/applyjob anonymize)
+    if !is_applied(26)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN anonymize_cv INTEGER NOT NULL DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (26)", [])?;
+    }
+
+    // Migration 27: journal des commandes exécutées par chaque utilisateur (/mycommands)
+    if !is_applied(27)? {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS command_usage (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id         INTEGER NOT NULL,
+                command_name    TEXT NOT NULL,
+                application_id  INTEGER,
+                created_at      DATETIME DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )?;
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_command_usage_user ON command_usage(user_id, id)", [])?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (27)", [])?;
+    }
+
+    // Migration 28: options de /applyjob réutilisées par le bouton « Réessayer »
+    if !is_applied(28)? {
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN fit_level INTEGER NOT NULL DEFAULT 1", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN output_language TEXT", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN pdf_template TEXT", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN pdf_accent_color TEXT", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN skip_salary INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN suggest_roles INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE job_applications ADD COLUMN save_synthesis_pdf INTEGER NOT NULL DEFAULT 0", []);
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (28)", [])?;
+    }
+
     Ok(())
 }
 
@@ -318,5 +590,18 @@ mod tests {
         assert!(tables.contains(&"base_cvs".to_string()));
         assert!(tables.contains(&"job_applications".to_string()));
         assert!(tables.contains(&"application_status_history".to_string()));
+        assert!(tables.contains(&"user_settings".to_string()));
+        assert!(tables.contains(&"application_attachments".to_string()));
+        assert!(tables.contains(&"reminders".to_string()));
+
+        // Rappels en attente lus à chaque passage de reminder_check_task
+        let pending_index: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='index' AND name='idx_reminders_pending'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(pending_index, 1);
     }
 }
\ No newline at end of file
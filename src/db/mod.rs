@@ -8,8 +8,10 @@ pub use init::init_database;
 pub use utilities::*;
 
 use rusqlite::Connection;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tracing::error;
 
 /// Wrapper thread-safe pour la connexion SQLite
 /// Nécessaire car rusqlite::Connection n'est pas Sync
@@ -42,6 +44,8 @@ impl Database {
     }
 
     /// Exécute une opération avec la connexion (async, cède au scheduler entre tâches).
+    /// Le verrou tokio n'est pas empoisonné par une panique : il est libéré au déroulement
+    /// de la pile et une transaction en cours est annulée par son `Drop`.
     pub async fn with_conn<F, T>(&self, f: F) -> Result<T, rusqlite::Error>
     where
         F: FnOnce(&Connection) -> Result<T, rusqlite::Error> + Send,
@@ -50,6 +54,43 @@ impl Database {
         f(&conn)
     }
 
+    /// Ferme la connexion, laisse `replace_file` remplacer le fichier de la base (verrou tenu,
+    /// aucune requête concurrente) puis rouvre la base avec ses migrations. Si le remplacement
+    /// ou la réouverture échoue (migration impossible sur un ancien fichier...), `restore_file`
+    /// remet la base d'origine en place et elle est rouverte avant de renvoyer l'erreur.
+    pub async fn reopen<F, R, E>(&self, replace_file: F, restore_file: R) -> Result<(), E>
+    where
+        F: FnOnce(&Path) -> Result<(), E>,
+        R: FnOnce(&Path) -> Result<(), E>,
+        E: From<rusqlite::Error> + std::fmt::Display,
+    {
+        self.reopen_at(Path::new(&init::get_db_path()), replace_file, restore_file).await
+    }
+
+    async fn reopen_at<F, R, E>(&self, db_path: &Path, replace_file: F, restore_file: R) -> Result<(), E>
+    where
+        F: FnOnce(&Path) -> Result<(), E>,
+        R: FnOnce(&Path) -> Result<(), E>,
+        E: From<rusqlite::Error> + std::fmt::Display,
+    {
+        let mut conn = self.conn.lock().await;
+        drop(std::mem::replace(&mut *conn, Connection::open_in_memory()?));
+
+        let error = match replace_file(db_path).and_then(|()| init::open_database(db_path).map_err(E::from)) {
+            Ok(reopened) => {
+                *conn = reopened;
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+        error!("Failed to reopen the replaced database, restoring the previous one: {}", error);
+        if let Err(e) = restore_file(db_path) {
+            error!("Failed to restore the previous database file: {}", e);
+        }
+        *conn = init::open_database(db_path)?;
+        Err(error)
+    }
+
     // ========================================================================
     // USER METHODS
     // ========================================================================
@@ -84,12 +125,24 @@ impl Database {
         self.with_conn(|conn| utilities::get_active_cv(conn, user_id)).await
     }
 
+    pub async fn get_cv_by_id(&self, cv_id: i64) -> Result<Option<BaseCv>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_cv_by_id(conn, cv_id)).await
+    }
+
     pub async fn list_user_cvs(&self, user_id: i64) -> Result<Vec<BaseCv>, rusqlite::Error> {
         self.with_conn(|conn| utilities::list_user_cvs(conn, user_id)).await
     }
 
-    pub async fn delete_active_cv(&self, user_id: i64) -> Result<bool, rusqlite::Error> {
-        self.with_conn(|conn| utilities::delete_active_cv(conn, user_id)).await
+    pub async fn soft_delete_cv(&self, user_id: i64, cv_id: i64, trash_path: &str) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::soft_delete_cv(conn, user_id, cv_id, trash_path)).await
+    }
+
+    pub async fn list_deleted_cvs(&self, user_id: i64, limit: i64) -> Result<Vec<BaseCv>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_deleted_cvs(conn, user_id, limit)).await
+    }
+
+    pub async fn restore_cv(&self, user_id: i64, cv_id: i64, file_path: &str) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::restore_cv(conn, user_id, cv_id, file_path)).await
     }
 
     pub async fn update_cv_extracted_data(
@@ -123,6 +176,34 @@ impl Database {
         }).await
     }
 
+    pub async fn export_user_data(&self, user_id: i64) -> Result<UserDataExport, rusqlite::Error> {
+        self.with_conn(|conn| utilities::export_user_data(conn, user_id)).await
+    }
+
+    pub async fn purge_user_data(&self, user_id: i64) -> Result<PurgeSummary, rusqlite::Error> {
+        self.with_conn(|conn| utilities::purge_user_data(conn, user_id)).await
+    }
+
+    pub async fn delete_application(&self, application_id: i64, user_id: i64) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::delete_application(conn, application_id, user_id)).await
+    }
+
+    pub async fn set_application_interview(&self, application_id: i64, user_id: i64, interview_at: &str) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_application_interview(conn, application_id, user_id, interview_at)).await
+    }
+
+    pub async fn get_application_owner(&self, application_id: i64) -> Result<Option<i64>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_application_owner(conn, application_id)).await
+    }
+
+    pub async fn set_application_parent(&self, application_id: i64, parent_id: i64) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_application_parent(conn, application_id, parent_id)).await
+    }
+
+    pub async fn get_application_versions(&self, application_id: i64) -> Result<Vec<ApplicationVersion>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_application_versions(conn, application_id)).await
+    }
+
     pub async fn get_application(&self, application_id: i64) -> Result<Option<JobApplication>, rusqlite::Error> {
         self.with_conn(|conn| utilities::get_application(conn, application_id)).await
     }
@@ -136,6 +217,45 @@ impl Database {
         self.with_conn(|conn| utilities::list_applications(conn, user_id, status_filter, limit)).await
     }
 
+    pub async fn list_applications_filtered(
+        &self,
+        user_id: i64,
+        status_filter: Option<&str>,
+        min_score: Option<i64>,
+        max_score: Option<i64>,
+        include_archived: bool,
+        limit: i64,
+    ) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| {
+            utilities::list_applications_filtered(
+                conn, user_id, status_filter, min_score, max_score, include_archived, limit,
+            )
+        })
+        .await
+    }
+
+    pub async fn archive_closed_applications(&self) -> Result<usize, rusqlite::Error> {
+        self.with_conn(utilities::archive_closed_applications).await
+    }
+
+    pub async fn get_stale_interview_applications(&self, after_days: i64) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_stale_interview_applications(conn, after_days)).await
+    }
+
+    pub async fn mark_interview_nudged(&self, application_id: i64) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::mark_interview_nudged(conn, application_id)).await
+    }
+
+    pub async fn list_rescorable_applications(
+        &self,
+        user_id: i64,
+        since_days: i64,
+        limit: i64,
+    ) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_rescorable_applications(conn, user_id, since_days, limit))
+            .await
+    }
+
     pub async fn update_application_status(
         &self,
         application_id: i64,
@@ -148,6 +268,14 @@ impl Database {
         }).await
     }
 
+    pub async fn import_applications(
+        &self,
+        user_id: i64,
+        rows: &[ImportedApplication],
+    ) -> Result<usize, rusqlite::Error> {
+        self.with_conn(|conn| utilities::import_applications(conn, user_id, rows)).await
+    }
+
     pub async fn update_application_thread(
         &self,
         application_id: i64,
@@ -164,6 +292,84 @@ impl Database {
         self.with_conn(|conn| utilities::update_application_notes(conn, application_id, notes)).await
     }
 
+    pub async fn update_application_benefits(
+        &self,
+        application_id: i64,
+        benefits: &[String],
+    ) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_benefits(conn, application_id, benefits)).await
+    }
+
+    pub async fn update_application_synthesis_json(&self, application_id: i64, synthesis_json: &str) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_synthesis_json(conn, application_id, synthesis_json)).await
+    }
+
+    pub async fn update_application_language(&self, application_id: i64, language: &str) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_language(conn, application_id, language)).await
+    }
+
+    pub async fn set_application_minimal_tracking(&self, application_id: i64, minimal: bool) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_application_minimal_tracking(conn, application_id, minimal)).await
+    }
+
+    pub async fn set_application_anonymize_cv(&self, application_id: i64, anonymize: bool) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_application_anonymize_cv(conn, application_id, anonymize)).await
+    }
+
+    pub async fn update_application_seniority(&self, application_id: i64, seniority: &str) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_seniority(conn, application_id, seniority)).await
+    }
+
+    pub async fn update_application_analysis_options(&self, application_id: i64, options: &AnalysisOptions) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_analysis_options(conn, application_id, options)).await
+    }
+
+    pub async fn start_application_pipeline(&self, application_id: i64, channel_id: i64) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::start_application_pipeline(conn, application_id, channel_id)).await
+    }
+
+    pub async fn set_application_pipeline_stage(&self, application_id: i64, stage: Option<&str>) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_application_pipeline_stage(conn, application_id, stage)).await
+    }
+
+    pub async fn list_interrupted_applications(&self) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(utilities::list_interrupted_applications).await
+    }
+
+    pub async fn update_application_cv(
+        &self,
+        application_id: i64,
+        user_id: i64,
+        cv_id: i64,
+    ) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_cv(conn, application_id, user_id, cv_id)).await
+    }
+
+    pub async fn list_applications_missing_analysis(&self, after_id: i64, limit: i64) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_applications_missing_analysis(conn, after_id, limit)).await
+    }
+
+    pub async fn count_applications(&self) -> Result<i64, rusqlite::Error> {
+        self.with_conn(utilities::count_applications).await
+    }
+
+    pub async fn count_applications_missing_analysis(&self) -> Result<i64, rusqlite::Error> {
+        self.with_conn(utilities::count_applications_missing_analysis).await
+    }
+
+    pub async fn backfill_application_skills(
+        &self,
+        application_id: i64,
+        cv_id: i64,
+        matching_skills: &str,
+        missing_skills: &str,
+        match_score: i32,
+    ) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| {
+            utilities::backfill_application_skills(conn, application_id, cv_id, matching_skills, missing_skills, match_score)
+        }).await
+    }
+
     pub async fn update_application_analysis(
         &self,
         application_id: i64,
@@ -190,15 +396,20 @@ impl Database {
         market_salary_low: Option<i32>,
         market_salary_mid: Option<i32>,
         market_salary_high: Option<i32>,
+        salary_currency: &str,
     ) -> Result<(), rusqlite::Error> {
         self.with_conn(|conn| {
             utilities::update_application_salary(
                 conn, application_id, salary_min, salary_max, salary_analysis,
-                market_salary_low, market_salary_mid, market_salary_high
+                market_salary_low, market_salary_mid, market_salary_high, salary_currency
             )
         }).await
     }
 
+    pub async fn update_application_currency(&self, application_id: i64, currency: &str) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_application_currency(conn, application_id, currency)).await
+    }
+
     pub async fn update_application_generated_cv(
         &self,
         application_id: i64,
@@ -210,6 +421,18 @@ impl Database {
         }).await
     }
 
+    // ========================================================================
+    // USER SETTINGS METHODS
+    // ========================================================================
+
+    pub async fn get_settings(&self, user_id: i64) -> Result<UserSettings, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_settings(conn, user_id)).await
+    }
+
+    pub async fn update_settings(&self, settings: &UserSettings) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::update_settings(conn, settings)).await
+    }
+
     // ========================================================================
     // STATS METHODS
     // ========================================================================
@@ -218,6 +441,10 @@ impl Database {
         self.with_conn(|conn| utilities::get_user_stats(conn, user_id)).await
     }
 
+    pub async fn get_application_streak(&self, user_id: i64) -> Result<u32, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_application_streak(conn, user_id)).await
+    }
+
     // ========================================================================
     // ADMIN METHODS
     // ========================================================================
@@ -230,6 +457,10 @@ impl Database {
         self.with_conn(utilities::clear_all_cvs).await
     }
 
+    pub async fn backup_database(&self, path: &str) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::backup_database(conn, path)).await
+    }
+
     // ========================================================================
     // COVER LETTER METHODS
     // ========================================================================
@@ -254,6 +485,49 @@ impl Database {
         self.with_conn(|conn| utilities::list_applications_with_cover_letters(conn, user_id, limit)).await
     }
 
+    // ========================================================================
+    // ATTACHMENT METHODS
+    // ========================================================================
+
+    pub async fn add_attachment(
+        &self,
+        application_id: i64,
+        user_id: i64,
+        filename: &str,
+        path: &str,
+        file_size: i64,
+        mime_type: Option<&str>,
+    ) -> Result<i64, rusqlite::Error> {
+        self.with_conn(|conn| {
+            utilities::add_attachment(conn, application_id, user_id, filename, path, file_size, mime_type)
+        }).await
+    }
+
+    pub async fn list_attachments(&self, application_id: i64) -> Result<Vec<ApplicationAttachment>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_attachments(conn, application_id)).await
+    }
+
+    pub async fn get_attachment(&self, attachment_id: i64) -> Result<Option<ApplicationAttachment>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_attachment(conn, attachment_id)).await
+    }
+
+    // ========================================================================
+    // GENERATED CV METHODS
+    // ========================================================================
+
+    pub async fn save_generated_cv_text(
+        &self,
+        application_id: i64,
+        cv_text: &str,
+        adaptations: &[String],
+    ) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::save_generated_cv_text(conn, application_id, cv_text, adaptations)).await
+    }
+
+    pub async fn get_generated_cv(&self, application_id: i64) -> Result<Option<GeneratedCvRecord>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::get_generated_cv(conn, application_id)).await
+    }
+
     // ========================================================================
     // APPLICATION REMINDER METHODS
     // ========================================================================
@@ -262,8 +536,9 @@ impl Database {
         &self,
         application_id: i64,
         reminder_date: &str,
+        note: Option<&str>,
     ) -> Result<(), rusqlite::Error> {
-        self.with_conn(|conn| utilities::set_application_reminder(conn, application_id, reminder_date)).await
+        self.with_conn(|conn| utilities::set_application_reminder(conn, application_id, reminder_date, note)).await
     }
 
     pub async fn clear_application_reminder(&self, application_id: i64) -> Result<(), rusqlite::Error> {
@@ -274,10 +549,32 @@ impl Database {
         self.with_conn(|conn| utilities::mark_application_reminder_sent(conn, application_id)).await
     }
 
+    pub async fn record_application_reminder_failure(
+        &self,
+        application_id: i64,
+        max_retries: i64,
+    ) -> Result<bool, rusqlite::Error> {
+        self.with_conn(move |conn| utilities::record_application_reminder_failure(conn, application_id, max_retries)).await
+    }
+
     pub async fn get_pending_application_reminders(&self) -> Result<Vec<JobApplication>, rusqlite::Error> {
         self.with_conn(utilities::get_pending_application_reminders).await
     }
 
+    pub async fn get_unacknowledged_application_reminders(
+        &self,
+        after_days: i64,
+        max_escalations: i64,
+    ) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| {
+            utilities::get_unacknowledged_application_reminders(conn, after_days, max_escalations)
+        }).await
+    }
+
+    pub async fn record_reminder_escalation(&self, application_id: i64) -> Result<i64, rusqlite::Error> {
+        self.with_conn(|conn| utilities::record_reminder_escalation(conn, application_id)).await
+    }
+
     pub async fn list_user_application_reminders(&self, user_id: i64) -> Result<Vec<JobApplication>, rusqlite::Error> {
         self.with_conn(|conn| utilities::list_user_application_reminders(conn, user_id)).await
     }
@@ -307,18 +604,59 @@ impl Database {
         self.with_conn(|conn| utilities::list_user_reminders(conn, user_id)).await
     }
 
+    pub async fn list_upcoming_interviews(&self, user_id: i64) -> Result<Vec<JobApplication>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_upcoming_interviews(conn, user_id)).await
+    }
+
     pub async fn delete_reminder(&self, reminder_id: i64, user_id: i64) -> Result<bool, rusqlite::Error> {
         self.with_conn(|conn| utilities::delete_reminder(conn, reminder_id, user_id)).await
     }
 
+    pub async fn delete_reminders_for_application(&self, application_id: i64) -> Result<usize, rusqlite::Error> {
+        self.with_conn(|conn| utilities::delete_reminders_for_application(conn, application_id)).await
+    }
+
     pub async fn mark_reminder_sent(&self, reminder_id: i64) -> Result<(), rusqlite::Error> {
         self.with_conn(|conn| utilities::mark_reminder_sent(conn, reminder_id)).await
     }
 
+    pub async fn record_reminder_failure(&self, reminder_id: i64, max_retries: i64) -> Result<bool, rusqlite::Error> {
+        self.with_conn(move |conn| utilities::record_reminder_failure(conn, reminder_id, max_retries)).await
+    }
+
     pub async fn get_pending_reminders(&self) -> Result<Vec<Reminder>, rusqlite::Error> {
         self.with_conn(utilities::get_pending_reminders).await
     }
 
+    // ========================================================================
+    // GUILD COMMAND CONFIG METHODS
+    // ========================================================================
+
+    pub async fn set_command_enabled(
+        &self,
+        guild_id: i64,
+        command_name: &str,
+        enabled: bool,
+    ) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::set_command_enabled(conn, guild_id, command_name, enabled)).await
+    }
+
+    pub async fn record_command_usage(&self, user_id: i64, command_name: &str, application_id: Option<i64>) -> Result<(), rusqlite::Error> {
+        self.with_conn(|conn| utilities::record_command_usage(conn, user_id, command_name, application_id)).await
+    }
+
+    pub async fn list_command_usage(&self, user_id: i64, limit: i64) -> Result<Vec<CommandUsage>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_command_usage(conn, user_id, limit)).await
+    }
+
+    pub async fn is_command_enabled(&self, guild_id: i64, command_name: &str) -> Result<bool, rusqlite::Error> {
+        self.with_conn(|conn| utilities::is_command_enabled(conn, guild_id, command_name)).await
+    }
+
+    pub async fn list_disabled_commands(&self, guild_id: i64) -> Result<Vec<String>, rusqlite::Error> {
+        self.with_conn(|conn| utilities::list_disabled_commands(conn, guild_id)).await
+    }
+
     pub async fn get_application_status_history(
         &self,
         application_id: i64,
@@ -1,7 +1,10 @@
 // Utilitaires pour les opérations CRUD sur la base de données
 #![allow(dead_code)]
 use rusqlite::{Connection, Result, Row, OptionalExtension};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use crate::services::claude_client::{CvProfile, MatchedSkill, MissingSkill};
+use crate::services::matching;
 
 // ============================================================================
 // MODELS
@@ -29,13 +32,30 @@ pub struct BaseCv {
     pub parsed_data: Option<String>,  // JSON string
     pub is_active: bool,
     pub created_at: String,
+    /// Date de mise à la corbeille (/deletecv) ; None si le CV n'est pas supprimé
+    pub deleted_at: Option<String>,
+}
+
+impl BaseCv {
+    /// Profil structuré (colonne `parsed_data`) ; None si absent, vide ou invalide
+    pub fn profile(&self) -> Option<CvProfile> {
+        self.parsed_data
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<CvProfile>(raw).ok())
+            .filter(|profile| !profile.is_empty())
+    }
+
+    /// Compétences déclarées dans le profil structuré (vide si le profil n'est pas disponible)
+    pub fn declared_skills(&self) -> Vec<String> {
+        self.profile().map(|p| p.skills).unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobApplication {
     pub id: i64,
     pub user_id: i64,
-    pub base_cv_id: i64,
+    pub base_cv_id: Option<i64>,  // None : candidature sans CV uploadé
     pub job_title: Option<String>,
     pub company: Option<String>,
     pub location: Option<String>,
@@ -62,6 +82,88 @@ pub struct JobApplication {
     pub reminder_sent: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Message personnalisé du rappel (/setreminder message:)
+    pub reminder_note: Option<String>,
+    pub benefits: Option<String>,         // JSON
+    /// Archivée automatiquement (statut terminal) : masquée de /status par défaut
+    pub archived_at: Option<String>,
+    /// Langue détectée de l'offre (fr, en, es, de) ; None si non détectée
+    pub language: Option<String>,
+    /// Candidature réellement envoyée (« Marquer postulée », /updatestatus) et pas seulement analysée
+    pub applied_externally: bool,
+    /// Dernière étape atteinte par le pipeline /applyjob ; None une fois l'analyse terminée
+    pub pipeline_stage: Option<String>,
+    /// Salon où le pipeline crée son thread, pour le relancer après un redémarrage
+    pub origin_channel_id: Option<i64>,
+    /// Synthèse complète de l'offre (JSON de `JobSynthesis`), réutilisable par /applyjob from_application
+    pub synthesis_json: Option<String>,
+    /// Entretien programmé (UTC, `YYYY-MM-DD HH:MM:SS`), saisi avec /setinterview
+    pub interview_at: Option<String>,
+    /// Embed de suivi réduit au statut et au lien du thread (option `visibility:minimal`)
+    pub minimal_tracking: bool,
+    /// Niveau visé (junior, mid, senior, lead), transmis au matching des compétences
+    pub seniority: Option<String>,
+    /// Nom, emails et téléphones masqués dans le CV envoyé au serveur Claude (option `anonymize`)
+    pub anonymize_cv: bool,
+    /// Options de /applyjob réutilisées par le bouton « Réessayer »
+    pub analysis_options: AnalysisOptions,
+}
+
+/// Options de /applyjob enregistrées avec la candidature pour relancer l'analyse à l'identique
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisOptions {
+    /// 1=standard, 2=modéré, 3=laxiste
+    pub fit_level: i32,
+    /// Langue de sortie choisie ou détectée ; None pour les candidatures antérieures
+    pub output_language: Option<String>,
+    pub pdf_template: Option<String>,
+    pub pdf_accent_color: Option<String>,
+    pub skip_salary: bool,
+    pub suggest_roles: bool,
+    pub save_synthesis_pdf: bool,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            fit_level: 1,
+            output_language: None,
+            pdf_template: None,
+            pdf_accent_color: None,
+            skip_salary: false,
+            suggest_roles: false,
+            save_synthesis_pdf: false,
+        }
+    }
+}
+
+impl JobApplication {
+    /// Exigences clés de l'offre (colonne `required_skills`)
+    pub fn required_skills_parsed(&self) -> Vec<String> {
+        parse_json_column(self.required_skills.as_deref())
+    }
+
+    /// Avantages mentionnés dans l'offre (colonne `benefits`)
+    pub fn benefits_parsed(&self) -> Vec<String> {
+        parse_json_column(self.benefits.as_deref())
+    }
+
+    /// Compétences du CV correspondant à l'offre (colonne `matching_skills`)
+    pub fn matched_skills(&self) -> Vec<MatchedSkill> {
+        parse_json_column(self.matching_skills.as_deref())
+    }
+
+    /// Compétences manquantes (colonne `missing_skills`)
+    pub fn missing_skills_parsed(&self) -> Vec<MissingSkill> {
+        parse_json_column(self.missing_skills.as_deref())
+    }
+}
+
+/// Désérialise une colonne JSON ; vide si absente ou invalide
+fn parse_json_column<T: DeserializeOwned>(raw: Option<&str>) -> Vec<T> {
+    raw.filter(|r| !r.trim().is_empty())
+        .and_then(|r| serde_json::from_str(r).ok())
+        .unwrap_or_default()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,9 +188,141 @@ pub struct ApplicationStatusHistory {
     pub changed_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSettings {
+    pub user_id: i64,
+    pub locale: String,
+    pub timezone: String,
+    pub notification_channel_id: Option<i64>,  // None = DM
+    pub default_reminder_time: String,         // HH:MM
+    pub auto_reminder_days: i64,               // 0 = désactivé
+    pub digest_enabled: bool,
+    pub private_by_default: bool,
+    pub updated_at: String,
+    pub auto_archive_days: i64,                // 0 = désactivé
+    /// Objectif salarial brut annuel (/settarget), en unités de la devise
+    pub target_salary_min: Option<i64>,
+    pub target_salary_max: Option<i64>,
+    /// Score de correspondance sous lequel /applyjob affiche un avertissement (0 = désactivé)
+    pub min_fit_warn: i64,
+    /// Message privé pour les candidatures restées au stade entretien sans activité
+    pub interview_nudges: bool,
+}
+
+impl UserSettings {
+    /// Objectif salarial (minimum, maximum éventuel) ; None si non défini
+    pub fn salary_target(&self) -> Option<(i64, Option<i64>)> {
+        self.target_salary_min.map(|min| (min, self.target_salary_max))
+    }
+}
+
+impl Default for UserSettings {
+    /// Valeurs utilisées quand l'utilisateur n'a encore rien configuré
+    fn default() -> Self {
+        Self {
+            user_id: 0,
+            locale: "fr".to_string(),
+            timezone: "Europe/Paris".to_string(),
+            notification_channel_id: None,
+            default_reminder_time: "09:00".to_string(),
+            auto_reminder_days: 0,
+            digest_enabled: false,
+            private_by_default: false,
+            updated_at: String::new(),
+            auto_archive_days: 0,
+            target_salary_min: None,
+            target_salary_max: None,
+            min_fit_warn: 40,
+            interview_nudges: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationAttachment {
+    pub id: i64,
+    pub application_id: i64,
+    pub user_id: i64,
+    pub filename: String,
+    pub path: String,
+    pub file_size: Option<i64>,
+    pub mime_type: Option<String>,
+    pub created_at: String,
+}
+
+/// Une version d'une candidature dans une chaîne de relances /applyjob
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationVersion {
+    pub id: i64,
+    pub match_score: Option<i32>,
+    pub status: String,
+    pub created_at: String,
+}
+
+/// CV généré pour une candidature, avec les informations nécessaires à la comparaison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedCvRecord {
+    pub application_id: i64,
+    pub user_id: i64,
+    pub base_cv_id: Option<i64>,
+    pub company: Option<String>,
+    pub job_title: Option<String>,
+    pub generated_cv_text: Option<String>,
+    pub adaptations: Vec<String>,
+}
+
+/// Résultat d'une suppression complète des données d'un utilisateur (/purgeme)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeSummary {
+    pub cvs: usize,
+    pub applications: usize,
+    pub status_history: usize,
+    pub attachments: usize,
+    pub reminders: usize,
+    /// Fichiers à supprimer du disque une fois la transaction validée
+    pub file_paths: Vec<String>,
+}
+
+/// Toutes les données d'un utilisateur (/exportuser), sérialisées en JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataExport {
+    pub user: Option<User>,
+    pub settings: UserSettings,
+    pub cvs: Vec<BaseCv>,
+    /// Inclut les lettres de motivation (colonne cover_letter)
+    pub applications: Vec<JobApplication>,
+    pub status_history: Vec<ApplicationStatusHistory>,
+    pub attachments: Vec<ApplicationAttachment>,
+    pub reminders: Vec<Reminder>,
+}
+
+/// Candidature importée depuis un fichier CSV (sans champs d'analyse)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportedApplication {
+    pub company: String,
+    pub job_title: String,
+    pub location: Option<String>,
+    pub job_url: Option<String>,
+    pub status: String,
+    pub applied_at: Option<String>,
+    pub notes: Option<String>,
+    pub raw_job_description: Option<String>,
+}
+
+/// Commande exécutée par un utilisateur (journal de /mycommands)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandUsage {
+    pub command_name: String,
+    /// Option `application_id` de la commande, si elle en a une
+    pub application_id: Option<i64>,
+    pub created_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserStats {
     pub total_applications: i32,
+    /// Candidatures réellement envoyées ; les autres ont seulement été analysées
+    pub submitted_applications: i32,
     pub by_status: Vec<(String, i32)>,
     pub avg_match_score: Option<f64>,
     pub top_companies: Vec<(String, i32)>,
@@ -121,6 +355,7 @@ fn map_base_cv(row: &Row) -> rusqlite::Result<BaseCv> {
         parsed_data: row.get(8)?,
         is_active: row.get::<_, i32>(9)? == 1,
         created_at: row.get(10)?,
+        deleted_at: row.get(11)?,
     })
 }
 
@@ -155,6 +390,27 @@ fn map_job_application(row: &Row) -> rusqlite::Result<JobApplication> {
         reminder_sent: row.get::<_, i32>(26)? == 1,
         created_at: row.get(27)?,
         updated_at: row.get(28)?,
+        reminder_note: row.get(29)?,
+        benefits: row.get(30)?,
+        archived_at: row.get(31)?,
+        language: row.get(32)?,
+        applied_externally: row.get::<_, i32>(33)? == 1,
+        pipeline_stage: row.get(34)?,
+        origin_channel_id: row.get(35)?,
+        synthesis_json: row.get(36)?,
+        interview_at: row.get(37)?,
+        minimal_tracking: row.get::<_, i32>(38)? == 1,
+        seniority: row.get(39)?,
+        anonymize_cv: row.get::<_, i32>(40)? == 1,
+        analysis_options: AnalysisOptions {
+            fit_level: row.get(41)?,
+            output_language: row.get(42)?,
+            pdf_template: row.get(43)?,
+            pdf_accent_color: row.get(44)?,
+            skip_salary: row.get::<_, i32>(45)? == 1,
+            suggest_roles: row.get::<_, i32>(46)? == 1,
+            save_synthesis_pdf: row.get::<_, i32>(47)? == 1,
+        },
     })
 }
 
@@ -171,6 +427,38 @@ fn map_reminder(row: &Row) -> rusqlite::Result<Reminder> {
     })
 }
 
+fn map_attachment(row: &Row) -> rusqlite::Result<ApplicationAttachment> {
+    Ok(ApplicationAttachment {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        user_id: row.get(2)?,
+        filename: row.get(3)?,
+        path: row.get(4)?,
+        file_size: row.get(5)?,
+        mime_type: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+fn map_user_settings(row: &Row) -> rusqlite::Result<UserSettings> {
+    Ok(UserSettings {
+        user_id: row.get(0)?,
+        locale: row.get(1)?,
+        timezone: row.get(2)?,
+        notification_channel_id: row.get(3)?,
+        default_reminder_time: row.get(4)?,
+        auto_reminder_days: row.get(5)?,
+        digest_enabled: row.get::<_, i32>(6)? == 1,
+        private_by_default: row.get::<_, i32>(7)? == 1,
+        updated_at: row.get(8)?,
+        auto_archive_days: row.get(9)?,
+        target_salary_min: row.get(10)?,
+        target_salary_max: row.get(11)?,
+        min_fit_warn: row.get(12)?,
+        interview_nudges: row.get::<_, i32>(13)? == 1,
+    })
+}
+
 // ============================================================================
 // USER OPERATIONS
 // ============================================================================
@@ -232,7 +520,7 @@ pub fn save_cv(
 pub fn get_active_cv(conn: &Connection, user_id: i64) -> Result<Option<BaseCv>> {
     let mut stmt = conn.prepare(
         "SELECT id, user_id, filename, original_name, file_path, file_size, 
-                mime_type, extracted_text, parsed_data, is_active, created_at
+                mime_type, extracted_text, parsed_data, is_active, created_at, deleted_at
          FROM base_cvs 
          WHERE user_id = ?1 AND is_active = 1"
     )?;
@@ -241,13 +529,26 @@ pub fn get_active_cv(conn: &Connection, user_id: i64) -> Result<Option<BaseCv>>
     Ok(cv)
 }
 
-/// Liste tous les CVs d'un utilisateur
+/// Récupère un CV par son ID
+pub fn get_cv_by_id(conn: &Connection, cv_id: i64) -> Result<Option<BaseCv>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, user_id, filename, original_name, file_path, file_size,
+                mime_type, extracted_text, parsed_data, is_active, created_at, deleted_at
+         FROM base_cvs
+         WHERE id = ?1"
+    )?;
+
+    let cv = stmt.query_row((cv_id,), map_base_cv).optional()?;
+    Ok(cv)
+}
+
+/// Liste tous les CVs d'un utilisateur (hors corbeille)
 pub fn list_user_cvs(conn: &Connection, user_id: i64) -> Result<Vec<BaseCv>> {
     let mut stmt = conn.prepare(
         "SELECT id, user_id, filename, original_name, file_path, file_size,
-                mime_type, extracted_text, parsed_data, is_active, created_at
+                mime_type, extracted_text, parsed_data, is_active, created_at, deleted_at
          FROM base_cvs
-         WHERE user_id = ?1
+         WHERE user_id = ?1 AND deleted_at IS NULL
          ORDER BY created_at DESC"
     )?;
 
@@ -259,15 +560,61 @@ pub fn list_user_cvs(conn: &Connection, user_id: i64) -> Result<Vec<BaseCv>> {
     Ok(cvs)
 }
 
-/// Supprime le CV actif d'un utilisateur
-pub fn delete_active_cv(conn: &Connection, user_id: i64) -> Result<bool> {
+/// Met un CV à la corbeille : désactivé, daté et rattaché à son fichier dans la corbeille.
+/// Les candidatures qui y font référence le conservent.
+pub fn soft_delete_cv(conn: &Connection, user_id: i64, cv_id: i64, trash_path: &str) -> Result<bool> {
     let rows = conn.execute(
-        "DELETE FROM base_cvs WHERE user_id = ?1 AND is_active = 1",
-        (user_id,),
+        "UPDATE base_cvs SET is_active = 0, deleted_at = CURRENT_TIMESTAMP, file_path = ?3
+         WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NULL",
+        (cv_id, user_id, trash_path),
     )?;
     Ok(rows > 0)
 }
 
+/// CVs à la corbeille d'un utilisateur, les plus récemment supprimés d'abord
+pub fn list_deleted_cvs(conn: &Connection, user_id: i64, limit: i64) -> Result<Vec<BaseCv>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, user_id, filename, original_name, file_path, file_size,
+                mime_type, extracted_text, parsed_data, is_active, created_at, deleted_at
+         FROM base_cvs
+         WHERE user_id = ?1 AND deleted_at IS NOT NULL
+         ORDER BY deleted_at DESC, id DESC
+         LIMIT ?2"
+    )?;
+
+    let cvs = stmt
+        .query_map((user_id, limit), map_base_cv)?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(cvs)
+}
+
+/// Sort un CV de la corbeille et le marque comme actif (désactive les autres).
+/// Retourne false si le CV n'est pas à la corbeille ou n'appartient pas à l'utilisateur.
+pub fn restore_cv(conn: &Connection, user_id: i64, cv_id: i64, file_path: &str) -> Result<bool> {
+    let tx = conn.unchecked_transaction()?;
+    let exists = tx
+        .query_row(
+            "SELECT 1 FROM base_cvs WHERE id = ?1 AND user_id = ?2 AND deleted_at IS NOT NULL",
+            (cv_id, user_id),
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !exists {
+        return Ok(false);
+    }
+
+    tx.execute("UPDATE base_cvs SET is_active = 0 WHERE user_id = ?1", (user_id,))?;
+    tx.execute(
+        "UPDATE base_cvs SET is_active = 1, deleted_at = NULL, file_path = ?2 WHERE id = ?1",
+        (cv_id, file_path),
+    )?;
+    tx.commit()?;
+    Ok(true)
+}
+
 /// Met à jour les données extraites d'un CV
 pub fn update_cv_extracted_data(
     conn: &Connection,
@@ -298,6 +645,9 @@ pub fn create_application(
     job_url: Option<&str>,
     raw_job_description: &str,
 ) -> Result<i64> {
+    // "Non spécifié", "N/A"... sont enregistrés à NULL pour ne pas fausser les statistiques
+    let job_title = job_title.and_then(matching::meaningful_label);
+    let company = company.and_then(matching::meaningful_label);
     conn.execute(
         "INSERT INTO job_applications
          (user_id, base_cv_id, job_title, company, location, job_url, raw_job_description)
@@ -308,6 +658,39 @@ pub fn create_application(
     Ok(conn.last_insert_rowid())
 }
 
+/// Crée plusieurs candidatures importées en une seule transaction (tout ou rien)
+pub fn import_applications(
+    conn: &Connection,
+    user_id: i64,
+    rows: &[ImportedApplication],
+) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO job_applications
+             (user_id, job_title, company, location, job_url, raw_job_description, status, applied_at, notes,
+              applied_externally)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        )?;
+        for row in rows {
+            stmt.execute((
+                user_id,
+                matching::meaningful_label(&row.job_title),
+                matching::meaningful_label(&row.company),
+                &row.location,
+                &row.job_url,
+                row.raw_job_description.as_deref().unwrap_or("(importé depuis CSV)"),
+                &row.status,
+                &row.applied_at,
+                &row.notes,
+                is_submitted_status(&row.status) as i32,
+            ))?;
+        }
+    }
+    tx.commit()?;
+    Ok(rows.len())
+}
+
 /// Met à jour le thread_id d'une candidature
 pub fn update_application_thread(
     conn: &Connection,
@@ -345,6 +728,17 @@ pub fn update_application_analysis(
     Ok(())
 }
 
+/// Associe un CV à une candidature de l'utilisateur. Retourne false si la candidature
+/// n'existe pas ou appartient à un autre utilisateur.
+pub fn update_application_cv(conn: &Connection, application_id: i64, user_id: i64, cv_id: i64) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE job_applications SET base_cv_id = ?1, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?2 AND user_id = ?3",
+        (cv_id, application_id, user_id),
+    )?;
+    Ok(updated > 0)
+}
+
 /// Met à jour une candidature avec l'analyse salariale
 #[allow(clippy::too_many_arguments)]
 pub fn update_application_salary(
@@ -356,6 +750,7 @@ pub fn update_application_salary(
     market_salary_low: Option<i32>,
     market_salary_mid: Option<i32>,
     market_salary_high: Option<i32>,
+    salary_currency: &str,
 ) -> Result<()> {
     conn.execute(
         "UPDATE job_applications SET
@@ -365,9 +760,22 @@ pub fn update_application_salary(
             market_salary_low = ?4,
             market_salary_mid = ?5,
             market_salary_high = ?6,
+            salary_currency = ?7,
             updated_at = CURRENT_TIMESTAMP
-         WHERE id = ?7",
-        (salary_min, salary_max, salary_analysis, market_salary_low, market_salary_mid, market_salary_high, application_id),
+         WHERE id = ?8",
+        (
+            salary_min, salary_max, salary_analysis, market_salary_low, market_salary_mid, market_salary_high,
+            salary_currency, application_id,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Enregistre la devise choisie pour l'analyse salariale (option `currency` de /applyjob)
+pub fn update_application_currency(conn: &Connection, application_id: i64, currency: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET salary_currency = ?1 WHERE id = ?2",
+        (currency, application_id),
     )?;
     Ok(())
 }
@@ -403,6 +811,213 @@ pub fn update_application_notes(
     Ok(())
 }
 
+/// Enregistre les avantages extraits de l'offre (liste JSON)
+pub fn update_application_benefits(conn: &Connection, application_id: i64, benefits: &[String]) -> Result<()> {
+    let json = serde_json::to_string(benefits).unwrap_or_else(|_| "[]".to_string());
+    conn.execute(
+        "UPDATE job_applications SET benefits = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        (json, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre la synthèse complète de l'offre (JSON)
+pub fn update_application_synthesis_json(conn: &Connection, application_id: i64, synthesis_json: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET synthesis_json = ?1 WHERE id = ?2",
+        (synthesis_json, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre la langue détectée de l'offre
+pub fn update_application_language(conn: &Connection, application_id: i64, language: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET language = ?1 WHERE id = ?2",
+        (language, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre le choix d'un embed de suivi réduit, respecté lors des changements de statut
+pub fn set_application_minimal_tracking(conn: &Connection, application_id: i64, minimal: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET minimal_tracking = ?1 WHERE id = ?2",
+        (minimal as i32, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre le choix d'anonymiser le CV, respecté lors des relances du matching
+pub fn set_application_anonymize_cv(conn: &Connection, application_id: i64, anonymize: bool) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET anonymize_cv = ?1 WHERE id = ?2",
+        (anonymize as i32, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre le niveau de séniorité visé, réutilisé lors des relances du matching
+pub fn update_application_seniority(conn: &Connection, application_id: i64, seniority: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET seniority = ?1 WHERE id = ?2",
+        (seniority, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre les options de /applyjob, réutilisées par le bouton « Réessayer »
+pub fn update_application_analysis_options(conn: &Connection, application_id: i64, options: &AnalysisOptions) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET fit_level = ?1, output_language = ?2, pdf_template = ?3, pdf_accent_color = ?4,
+                skip_salary = ?5, suggest_roles = ?6, save_synthesis_pdf = ?7
+         WHERE id = ?8",
+        (
+            options.fit_level,
+            &options.output_language,
+            &options.pdf_template,
+            &options.pdf_accent_color,
+            options.skip_salary as i32,
+            options.suggest_roles as i32,
+            options.save_synthesis_pdf as i32,
+            application_id,
+        ),
+    )?;
+    Ok(())
+}
+
+/// Étapes du pipeline /applyjob enregistrées dans `pipeline_stage` (NULL une fois terminé)
+pub const PIPELINE_CREATED: &str = "created";
+pub const PIPELINE_THREAD: &str = "thread";
+pub const PIPELINE_SKILLS: &str = "skills";
+pub const PIPELINE_SALARY: &str = "salary";
+pub const PIPELINE_CV: &str = "cv";
+/// Interruption déjà signalée à l'utilisateur (bouton « Réessayer ») : ignorée au démarrage
+pub const PIPELINE_STALLED: &str = "stalled";
+
+/// Marque le début du pipeline et mémorise le salon de sortie
+pub fn start_application_pipeline(conn: &Connection, application_id: i64, channel_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET pipeline_stage = ?1, origin_channel_id = ?2 WHERE id = ?3",
+        (PIPELINE_CREATED, channel_id, application_id),
+    )?;
+    Ok(())
+}
+
+/// Enregistre l'étape atteinte (None = pipeline terminé)
+pub fn set_application_pipeline_stage(conn: &Connection, application_id: i64, stage: Option<&str>) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET pipeline_stage = ?1 WHERE id = ?2",
+        (stage, application_id),
+    )?;
+    Ok(())
+}
+
+/// Candidatures dont le pipeline s'est arrêté en cours de route sans que l'utilisateur
+/// en ait été averti (redémarrage du bot pendant l'analyse)
+pub fn list_interrupted_applications(conn: &Connection) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE pipeline_stage IS NOT NULL AND pipeline_stage != ?1 ORDER BY id",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps = stmt
+        .query_map((PIPELINE_STALLED,), map_job_application)?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(apps)
+}
+
+/// Supprime une candidature et ses rappels libres associés.
+/// Sans ce nettoyage, la clé étrangère (ON DELETE SET NULL) transformerait ces rappels
+/// en rappels orphelins envoyés pour une candidature qui n'existe plus.
+/// L'historique des statuts et les pièces jointes sont supprimés en cascade.
+pub fn delete_application(conn: &Connection, application_id: i64, user_id: i64) -> Result<bool> {
+    let tx = conn.unchecked_transaction()?;
+    let owned: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM job_applications WHERE id = ?1 AND user_id = ?2",
+            (application_id, user_id),
+            |row| row.get(0),
+        )
+        .optional()?;
+    if owned.is_none() {
+        return Ok(false);
+    }
+
+    delete_reminders_for_application(&tx, application_id)?;
+    tx.execute("DELETE FROM job_applications WHERE id = ?1", (application_id,))?;
+    tx.commit()?;
+    Ok(true)
+}
+
+/// Enregistre la date d'entretien d'une candidature de l'utilisateur (false si elle ne lui appartient pas)
+pub fn set_application_interview(conn: &Connection, application_id: i64, user_id: i64, interview_at: &str) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE job_applications SET interview_at = ?1, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?2 AND user_id = ?3",
+        (interview_at, application_id, user_id),
+    )?;
+    Ok(updated > 0)
+}
+
+/// Récupère le propriétaire d'une candidature (None si elle n'existe pas)
+pub fn get_application_owner(conn: &Connection, application_id: i64) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT user_id FROM job_applications WHERE id = ?1",
+        (application_id,),
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Rattache une candidature à une version précédente de la même offre
+pub fn set_application_parent(conn: &Connection, application_id: i64, parent_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET parent_application_id = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        (parent_id, application_id),
+    )?;
+    Ok(())
+}
+
+/// Récupère toutes les versions de la chaîne contenant cette candidature, de la plus ancienne
+/// à la plus récente. Une version parente supprimée coupe simplement la chaîne.
+pub fn get_application_versions(conn: &Connection, application_id: i64) -> Result<Vec<ApplicationVersion>> {
+    let mut stmt = conn.prepare(
+        "WITH RECURSIVE
+            ancestors(id, parent_application_id) AS (
+                SELECT id, parent_application_id FROM job_applications WHERE id = ?1
+                UNION
+                SELECT j.id, j.parent_application_id
+                FROM job_applications j JOIN ancestors a ON j.id = a.parent_application_id
+            ),
+            chain(id) AS (
+                SELECT id FROM ancestors
+                WHERE parent_application_id IS NULL
+                   OR parent_application_id NOT IN (SELECT id FROM job_applications)
+                UNION
+                SELECT j.id FROM job_applications j JOIN chain c ON j.parent_application_id = c.id
+            )
+         SELECT id, match_score, status, created_at
+         FROM job_applications
+         WHERE id IN (SELECT id FROM chain)
+         ORDER BY created_at ASC, id ASC"
+    )?;
+
+    let versions = stmt
+        .query_map((application_id,), |row| {
+            Ok(ApplicationVersion {
+                id: row.get(0)?,
+                match_score: row.get(1)?,
+                status: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(versions)
+}
+
 /// Récupère une candidature par son ID
 pub fn get_application(conn: &Connection, application_id: i64) -> Result<Option<JobApplication>> {
     let mut stmt = conn.prepare(
@@ -412,7 +1027,9 @@ pub fn get_application(conn: &Connection, application_id: i64) -> Result<Option<
                 salary_analysis, generated_cv_path, generated_cv_format,
                 cover_letter, cover_letter_generated_at, thread_id,
                 status, applied_at, notes, reminder_date, reminder_sent,
-                created_at, updated_at
+                created_at, updated_at, reminder_note, benefits, archived_at, language, applied_externally,
+                pipeline_stage, origin_channel_id, synthesis_json, interview_at, minimal_tracking, seniority, anonymize_cv,
+                fit_level, output_language, pdf_template, pdf_accent_color, skip_salary, suggest_roles, save_synthesis_pdf
          FROM job_applications WHERE id = ?1"
     )?;
 
@@ -426,9 +1043,32 @@ const JOB_APPLICATION_SELECT: &str = "SELECT id, user_id, base_cv_id, job_title,
         salary_analysis, generated_cv_path, generated_cv_format,
         cover_letter, cover_letter_generated_at, thread_id,
         status, applied_at, notes, reminder_date, reminder_sent,
-        created_at, updated_at
+        created_at, updated_at, reminder_note, benefits, archived_at, language, applied_externally,
+        pipeline_stage, origin_channel_id, synthesis_json, interview_at, minimal_tracking, seniority, anonymize_cv,
+        fit_level, output_language, pdf_template, pdf_accent_color, skip_salary, suggest_roles, save_synthesis_pdf
  FROM job_applications";
 
+/// Clause à placer après un motif `LIKE` construit avec [`like_contains_pattern`]
+pub const LIKE_ESCAPE_CLAUSE: &str = "ESCAPE '\\'";
+
+/// Échappe `%`, `_` et `\` pour une recherche littérale avec `LIKE ... ESCAPE '\'` :
+/// sans cela « C_C++ » ou « 100% » seraient lus comme des jokers
+pub fn escape_like(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Motif « contient » pour `col LIKE ?n ESCAPE '\'` (insensible à la casse ASCII, comme `LIKE`)
+pub fn like_contains_pattern(term: &str) -> String {
+    format!("%{}%", escape_like(term.trim()))
+}
+
 /// Liste les candidatures d'un utilisateur avec filtres
 pub fn list_applications(
     conn: &Connection,
@@ -464,37 +1104,159 @@ pub fn list_applications(
     }
 }
 
-/// Met à jour le statut d'une candidature
-pub fn update_application_status(
+/// Liste les candidatures filtrées par statut et/ou score de matching (bornes incluses).
+/// Les candidatures sans score sont exclues dès qu'une borne est donnée.
+pub fn list_applications_filtered(
     conn: &Connection,
-    application_id: i64,
     user_id: i64,
-    new_status: &str,
-    note: Option<&str>,
-) -> Result<bool> {
-    // Récupérer l'ancien statut
-    let mut stmt = conn.prepare(
-        "SELECT status FROM job_applications WHERE id = ?1 AND user_id = ?2"
-    )?;
-    let old_status: Option<String> = stmt
-        .query_row((application_id, user_id), |row: &Row| row.get(0))
-        .optional()?;
-
-    if old_status.is_none() {
-        return Ok(false);  // Application non trouvée ou pas à cet utilisateur
-    }
-
-    // Mettre à jour le statut
-    let applied_at_update = if new_status == "applied" {
-        ", applied_at = CURRENT_TIMESTAMP"
-    } else {
-        ""
-    };
+    status_filter: Option<&str>,
+    min_score: Option<i64>,
+    max_score: Option<i64>,
+    include_archived: bool,
+    limit: i64,
+) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE user_id = ?1
+         AND (?2 IS NULL OR status = ?2)
+         AND (?3 IS NULL OR match_score >= ?3)
+         AND (?4 IS NULL OR match_score <= ?4)
+         AND (?6 OR archived_at IS NULL)
+         ORDER BY created_at DESC LIMIT ?5",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map((user_id, status_filter, min_score, max_score, limit, include_archived), map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
 
-    conn.execute(
-        &format!(
-            "UPDATE job_applications SET status = ?1, updated_at = CURRENT_TIMESTAMP{} WHERE id = ?2",
-            applied_at_update
+/// Candidatures récentes encore ouvertes (ni acceptées ni refusées), à re-scorer après un nouveau CV
+pub fn list_rescorable_applications(
+    conn: &Connection,
+    user_id: i64,
+    since_days: i64,
+    limit: i64,
+) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE user_id = ?1
+         AND status NOT IN ('accepted', 'rejected')
+         AND created_at >= datetime('now', '-' || ?2 || ' days')
+         ORDER BY created_at DESC LIMIT ?3",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map((user_id, since_days, limit), map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
+
+/// Filtre des candidatures sans matching (créées avant les colonnes d'analyse ou par une
+/// version boguée) dont le CV de référence a un texte exploitable : le CV associé, sinon le CV actif
+const MISSING_ANALYSIS_FILTER: &str = "WHERE match_score IS NULL
+         AND raw_job_description != ''
+         AND EXISTS (
+             SELECT 1 FROM base_cvs c
+             WHERE c.user_id = job_applications.user_id
+             AND COALESCE(c.extracted_text, '') != ''
+             AND CASE WHEN job_applications.base_cv_id IS NULL
+                      THEN c.is_active = 1 AND c.deleted_at IS NULL
+                      ELSE c.id = job_applications.base_cv_id END
+         )";
+
+/// Candidatures à compléter par /backfill d'identifiant supérieur à `after_id`
+/// (reprise après la dernière traitée), des plus anciennes aux plus récentes
+pub fn list_applications_missing_analysis(conn: &Connection, after_id: i64, limit: i64) -> Result<Vec<JobApplication>> {
+    let sql = format!("{} {} AND id > ?1 ORDER BY id LIMIT ?2", JOB_APPLICATION_SELECT, MISSING_ANALYSIS_FILTER);
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map([after_id, limit], map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
+
+/// Nombre total de candidatures suivies, tous utilisateurs confondus (statut du bot)
+pub fn count_applications(conn: &Connection) -> Result<i64> {
+    conn.query_row("SELECT COUNT(*) FROM job_applications", [], |row| row.get(0))
+}
+
+pub fn count_applications_missing_analysis(conn: &Connection) -> Result<i64> {
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM job_applications {}", MISSING_ANALYSIS_FILTER),
+        [],
+        |row| row.get(0),
+    )
+}
+
+/// Complète le matching d'une candidature sans toucher à `updated_at` (pas une activité
+/// de l'utilisateur : l'archivage automatique n'est pas repoussé). Le CV utilisé est
+/// associé s'il n'y en avait pas. Retourne false si le matching a été rempli entre-temps.
+pub fn backfill_application_skills(
+    conn: &Connection,
+    application_id: i64,
+    cv_id: i64,
+    matching_skills: &str,
+    missing_skills: &str,
+    match_score: i32,
+) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE job_applications SET
+            matching_skills = ?1,
+            missing_skills = ?2,
+            match_score = ?3,
+            base_cv_id = COALESCE(base_cv_id, ?4)
+         WHERE id = ?5 AND match_score IS NULL",
+        (matching_skills, missing_skills, match_score, cv_id, application_id),
+    )?;
+    Ok(updated > 0)
+}
+
+/// Statuts qui impliquent que la candidature a réellement été envoyée
+fn is_submitted_status(status: &str) -> bool {
+    matches!(status, "applied" | "interview" | "offer" | "accepted")
+}
+
+/// Met à jour le statut d'une candidature
+pub fn update_application_status(
+    conn: &Connection,
+    application_id: i64,
+    user_id: i64,
+    new_status: &str,
+    note: Option<&str>,
+) -> Result<bool> {
+    // Récupérer l'ancien statut
+    let mut stmt = conn.prepare(
+        "SELECT status FROM job_applications WHERE id = ?1 AND user_id = ?2"
+    )?;
+    let old_status: Option<String> = stmt
+        .query_row((application_id, user_id), |row: &Row| row.get(0))
+        .optional()?;
+
+    if old_status.is_none() {
+        return Ok(false);  // Application non trouvée ou pas à cet utilisateur
+    }
+
+    // Mettre à jour le statut
+    let applied_at_update = if new_status == "applied" {
+        ", applied_at = CURRENT_TIMESTAMP"
+    } else {
+        ""
+    };
+    // Le marquage reste acquis même si la candidature passe ensuite à « Refusée »
+    let submitted_update = if is_submitted_status(new_status) {
+        ", applied_externally = 1"
+    } else {
+        ""
+    };
+
+    conn.execute(
+        &format!(
+            "UPDATE job_applications SET status = ?1, archived_at = NULL, updated_at = CURRENT_TIMESTAMP{}{} WHERE id = ?2",
+            applied_at_update, submitted_update
         ),
         (new_status, application_id),
     )?;
@@ -503,12 +1265,146 @@ pub fn update_application_status(
     conn.execute(
         "INSERT INTO application_status_history (application_id, old_status, new_status, note)
          VALUES (?1, ?2, ?3, ?4)",
-        (application_id, old_status, new_status, note),
+        (application_id, &old_status, new_status, note),
     )?;
 
+    // Rappel de suivi automatique lors du passage à "applied" (si activé par l'utilisateur)
+    if new_status == "applied" && old_status.as_deref() != Some("applied") {
+        let days = get_settings(conn, user_id)?.auto_reminder_days;
+        if days > 0 {
+            // Ne pas écraser un rappel déjà programmé manuellement
+            conn.execute(
+                "UPDATE job_applications SET
+                    reminder_date = datetime('now', '+' || ?1 || ' days'),
+                    reminder_sent = 0,
+                    reminder_retry_count = 0,
+                    reminder_send_failed = 0
+                 WHERE id = ?2
+                 AND (reminder_date IS NULL OR reminder_sent = 1)",
+                (days, application_id),
+            )?;
+        }
+    }
+
     Ok(true)
 }
 
+/// Archive les candidatures acceptées ou refusées depuis plus de `auto_archive_days` jours
+/// (sans activité), pour les utilisateurs ayant activé l'option. Retourne le nombre archivé.
+pub fn archive_closed_applications(conn: &Connection) -> Result<usize> {
+    let archived = conn.execute(
+        "UPDATE job_applications SET archived_at = CURRENT_TIMESTAMP
+         WHERE archived_at IS NULL
+         AND status IN ('accepted', 'rejected')
+         AND EXISTS (
+             SELECT 1 FROM user_settings s
+             WHERE s.user_id = job_applications.user_id
+             AND s.auto_archive_days > 0
+             AND job_applications.updated_at <= datetime('now', '-' || s.auto_archive_days || ' days')
+         )",
+        [],
+    )?;
+    Ok(archived)
+}
+
+/// Candidatures au stade entretien sans activité depuis `after_days` jours, pour les utilisateurs
+/// ayant activé la relance des entretiens : une seule relance par période d'inactivité
+pub fn get_stale_interview_applications(conn: &Connection, after_days: i64) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE status = 'interview'
+         AND archived_at IS NULL
+         AND datetime(updated_at) <= datetime('now', '-' || ?1 || ' days')
+         AND (interview_nudged_at IS NULL OR datetime(interview_nudged_at) < datetime(updated_at))
+         AND EXISTS (
+             SELECT 1 FROM user_settings s
+             WHERE s.user_id = job_applications.user_id AND s.interview_nudges = 1
+         )
+         ORDER BY updated_at ASC",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map((after_days,), map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
+
+/// Enregistre la relance d'un entretien (à appeler AVANT l'envoi). `updated_at` n'est pas
+/// modifié : la prochaine activité sur la candidature ouvre une nouvelle période
+pub fn mark_interview_nudged(conn: &Connection, application_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE job_applications SET interview_nudged_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        (application_id,),
+    )?;
+    Ok(())
+}
+
+// ============================================================================
+// USER SETTINGS
+// ============================================================================
+
+/// Récupère les préférences d'un utilisateur (valeurs par défaut si aucune ligne)
+pub fn get_settings(conn: &Connection, user_id: i64) -> Result<UserSettings> {
+    let settings = conn
+        .query_row(
+            "SELECT user_id, locale, timezone, notification_channel_id, default_reminder_time,
+                    auto_reminder_days, digest_enabled, private_by_default, updated_at,
+                    auto_archive_days, target_salary_min, target_salary_max, min_fit_warn,
+                    interview_nudges
+             FROM user_settings WHERE user_id = ?1",
+            (user_id,),
+            map_user_settings,
+        )
+        .optional()?;
+
+    Ok(settings.unwrap_or_else(|| UserSettings {
+        user_id,
+        ..UserSettings::default()
+    }))
+}
+
+/// Enregistre toutes les préférences d'un utilisateur (insert ou mise à jour)
+pub fn update_settings(conn: &Connection, settings: &UserSettings) -> Result<()> {
+    conn.execute(
+        "INSERT INTO user_settings (
+            user_id, locale, timezone, notification_channel_id, default_reminder_time,
+            auto_reminder_days, digest_enabled, private_by_default, auto_archive_days,
+            target_salary_min, target_salary_max, min_fit_warn, interview_nudges, updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, CURRENT_TIMESTAMP)
+        ON CONFLICT(user_id) DO UPDATE SET
+            locale = excluded.locale,
+            timezone = excluded.timezone,
+            notification_channel_id = excluded.notification_channel_id,
+            default_reminder_time = excluded.default_reminder_time,
+            auto_reminder_days = excluded.auto_reminder_days,
+            digest_enabled = excluded.digest_enabled,
+            private_by_default = excluded.private_by_default,
+            auto_archive_days = excluded.auto_archive_days,
+            target_salary_min = excluded.target_salary_min,
+            target_salary_max = excluded.target_salary_max,
+            min_fit_warn = excluded.min_fit_warn,
+            interview_nudges = excluded.interview_nudges,
+            updated_at = CURRENT_TIMESTAMP",
+        (
+            settings.user_id,
+            &settings.locale,
+            &settings.timezone,
+            settings.notification_channel_id,
+            &settings.default_reminder_time,
+            settings.auto_reminder_days,
+            settings.digest_enabled as i32,
+            settings.private_by_default as i32,
+            settings.auto_archive_days,
+            settings.target_salary_min,
+            settings.target_salary_max,
+            settings.min_fit_warn,
+            settings.interview_nudges as i32,
+        ),
+    )?;
+    Ok(())
+}
+
 // ============================================================================
 // STATISTICS
 // ============================================================================
@@ -519,6 +1415,12 @@ pub fn get_user_stats(conn: &Connection, user_id: i64) -> Result<UserStats> {
     let mut stmt = conn.prepare("SELECT COUNT(*) FROM job_applications WHERE user_id = ?1")?;
     let total: i32 = stmt.query_row((user_id,), |row: &Row| row.get(0))?;
 
+    let submitted: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM job_applications WHERE user_id = ?1 AND applied_externally = 1",
+        (user_id,),
+        |row: &Row| row.get(0),
+    )?;
+
     // By status
     let mut stmt = conn.prepare(
         "SELECT status, COUNT(*) FROM job_applications WHERE user_id = ?1 GROUP BY status"
@@ -537,25 +1439,62 @@ pub fn get_user_stats(conn: &Connection, user_id: i64) -> Result<UserStats> {
         .optional()?
         .flatten();
 
-    // Top companies
+    // Top companies (regroupées par nom normalisé : "Google Cloud" et "Google Cloud France" comptent ensemble)
     let mut stmt = conn.prepare(
-        "SELECT company, COUNT(*) as cnt FROM job_applications 
-         WHERE user_id = ?1 AND company IS NOT NULL 
-         GROUP BY company ORDER BY cnt DESC LIMIT 5"
+        "SELECT company FROM job_applications WHERE user_id = ?1 AND company IS NOT NULL"
     )?;
-    let top_companies: Vec<(String, i32)> = stmt
-        .query_map((user_id,), |row: &Row| Ok((row.get(0)?, row.get(1)?)))?
+    let companies: Vec<String> = stmt
+        .query_map((user_id,), |row: &Row| row.get(0))?
         .filter_map(|r| r.ok())
         .collect();
+    let mut top_companies =
+        matching::aggregate_by_key(companies.iter().map(|c| c.as_str()), matching::company_group_key);
+    top_companies.truncate(5);
 
     Ok(UserStats {
         total_applications: total,
+        submitted_applications: submitted,
         by_status,
         avg_match_score: avg_score,
         top_companies,
     })
 }
 
+/// Nombre de jours consécutifs (jusqu'à aujourd'hui, ou hier si rien encore aujourd'hui)
+/// avec au moins une candidature créée. Dates en UTC, comme `created_at`.
+pub fn get_application_streak(conn: &Connection, user_id: i64) -> Result<u32> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT date(created_at) FROM job_applications
+         WHERE user_id = ?1 AND created_at IS NOT NULL
+         ORDER BY 1 DESC",
+    )?;
+    let dates: Vec<chrono::NaiveDate> = stmt
+        .query_map((user_id,), |row| row.get::<_, String>(0))?
+        .filter_map(|r| r.ok())
+        .filter_map(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+        .collect();
+    Ok(streak_from_dates(&dates, chrono::Utc::now().date_naive()))
+}
+
+/// Série en cours à partir de dates distinctes triées de la plus récente à la plus ancienne
+fn streak_from_dates(dates: &[chrono::NaiveDate], today: chrono::NaiveDate) -> u32 {
+    let Some(&latest) = dates.first() else {
+        return 0;
+    };
+    // La série reste active tant que la journée n'est pas terminée
+    if (today - latest).num_days() > 1 {
+        return 0;
+    }
+    let mut streak = 1;
+    for pair in dates.windows(2) {
+        if (pair[0] - pair[1]).num_days() != 1 {
+            break;
+        }
+        streak += 1;
+    }
+    streak
+}
+
 // ============================================================================
 // ADMIN OPERATIONS
 // ============================================================================
@@ -565,7 +1504,7 @@ pub fn list_all_cvs(conn: &Connection) -> Result<Vec<(i64, String, BaseCv)>> {
     let mut stmt = conn.prepare(
         "SELECT u.id, u.username, c.id, c.user_id, c.filename, c.original_name, 
                 c.file_path, c.file_size, c.mime_type, c.extracted_text, 
-                c.parsed_data, c.is_active, c.created_at
+                c.parsed_data, c.is_active, c.created_at, c.deleted_at
          FROM base_cvs c
          JOIN users u ON c.user_id = u.id
          WHERE c.is_active = 1
@@ -588,6 +1527,7 @@ pub fn list_all_cvs(conn: &Connection) -> Result<Vec<(i64, String, BaseCv)>> {
                 parsed_data: row.get(10)?,
                 is_active: row.get::<_, i32>(11)? == 1,
                 created_at: row.get(12)?,
+                deleted_at: row.get(13)?,
             };
             Ok((user_id, username, cv))
         })?
@@ -603,6 +1543,90 @@ pub fn clear_all_cvs(conn: &Connection) -> Result<usize> {
     Ok(count)
 }
 
+/// Copie cohérente de la base vers `path` (VACUUM INTO, le fichier ne doit pas exister)
+pub fn backup_database(conn: &Connection, path: &str) -> Result<()> {
+    conn.execute("VACUUM INTO ?1", [path])?;
+    Ok(())
+}
+
+// ============================================================================
+// PRIVACY OPERATIONS
+// ============================================================================
+
+/// Supprime toutes les données d'un utilisateur en une transaction (tout ou rien).
+/// Les fichiers référencés sont retournés pour être supprimés après validation.
+pub fn purge_user_data(conn: &Connection, user_id: i64) -> Result<PurgeSummary> {
+    let tx = conn.unchecked_transaction()?;
+    let mut summary = PurgeSummary::default();
+
+    let collect_paths = |sql: &str| -> Result<Vec<String>> {
+        let mut stmt = tx.prepare(sql)?;
+        let paths = stmt
+            .query_map((user_id,), |row| row.get::<_, Option<String>>(0))?
+            .filter_map(|r| r.ok().flatten())
+            .collect();
+        Ok(paths)
+    };
+    summary.file_paths.extend(collect_paths("SELECT file_path FROM base_cvs WHERE user_id = ?1")?);
+    summary.file_paths.extend(collect_paths(
+        "SELECT generated_cv_path FROM job_applications WHERE user_id = ?1",
+    )?);
+    summary.file_paths.extend(collect_paths(
+        "SELECT a.path FROM application_attachments a
+         JOIN job_applications j ON a.application_id = j.id
+         WHERE j.user_id = ?1",
+    )?);
+
+    // Historique et pièces jointes sont supprimés en cascade avec les candidatures : les compter avant
+    summary.status_history = tx.query_row(
+        "SELECT COUNT(*) FROM application_status_history h
+         JOIN job_applications j ON h.application_id = j.id
+         WHERE j.user_id = ?1",
+        (user_id,),
+        |row| row.get::<_, i64>(0),
+    )? as usize;
+    summary.attachments = tx.query_row(
+        "SELECT COUNT(*) FROM application_attachments a
+         JOIN job_applications j ON a.application_id = j.id
+         WHERE j.user_id = ?1",
+        (user_id,),
+        |row| row.get::<_, i64>(0),
+    )? as usize;
+
+    summary.reminders = tx.execute("DELETE FROM reminders WHERE user_id = ?1", (user_id,))?;
+    // Les candidatures référencent les CVs (sans ON DELETE) : les supprimer d'abord
+    summary.applications = tx.execute("DELETE FROM job_applications WHERE user_id = ?1", (user_id,))?;
+    summary.cvs = tx.execute("DELETE FROM base_cvs WHERE user_id = ?1", (user_id,))?;
+    tx.execute("DELETE FROM user_settings WHERE user_id = ?1", (user_id,))?;
+    tx.execute("DELETE FROM command_usage WHERE user_id = ?1", (user_id,))?;
+    tx.execute("DELETE FROM users WHERE id = ?1", (user_id,))?;
+
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// Rassemble toutes les données d'un utilisateur (demande d'accès, support)
+pub fn export_user_data(conn: &Connection, user_id: i64) -> Result<UserDataExport> {
+    let applications = list_applications(conn, user_id, None, i64::MAX)?;
+
+    let mut status_history = Vec::new();
+    let mut attachments = Vec::new();
+    for app in &applications {
+        status_history.extend(get_application_status_history(conn, app.id)?);
+        attachments.extend(list_attachments(conn, app.id)?);
+    }
+
+    Ok(UserDataExport {
+        user: get_user(conn, user_id)?,
+        settings: get_settings(conn, user_id)?,
+        cvs: list_user_cvs(conn, user_id)?,
+        applications,
+        status_history,
+        attachments,
+        reminders: list_user_reminders(conn, user_id)?,
+    })
+}
+
 // ============================================================================
 // COVER LETTER OPERATIONS
 // ============================================================================
@@ -654,23 +1678,125 @@ pub fn list_applications_with_cover_letters(
     Ok(apps)
 }
 
+// ============================================================================
+// ATTACHMENT OPERATIONS
+// ============================================================================
+
+/// Enregistre une pièce jointe pour une candidature
+pub fn add_attachment(
+    conn: &Connection,
+    application_id: i64,
+    user_id: i64,
+    filename: &str,
+    path: &str,
+    file_size: i64,
+    mime_type: Option<&str>,
+) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO application_attachments (application_id, user_id, filename, path, file_size, mime_type)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        (application_id, user_id, filename, path, file_size, mime_type),
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Liste les pièces jointes d'une candidature
+pub fn list_attachments(conn: &Connection, application_id: i64) -> Result<Vec<ApplicationAttachment>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, user_id, filename, path, file_size, mime_type, created_at
+         FROM application_attachments
+         WHERE application_id = ?1
+         ORDER BY created_at ASC, id ASC"
+    )?;
+    let attachments = stmt
+        .query_map((application_id,), map_attachment)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(attachments)
+}
+
+/// Récupère une pièce jointe par ID
+pub fn get_attachment(conn: &Connection, attachment_id: i64) -> Result<Option<ApplicationAttachment>> {
+    conn.query_row(
+        "SELECT id, application_id, user_id, filename, path, file_size, mime_type, created_at
+         FROM application_attachments WHERE id = ?1",
+        (attachment_id,),
+        map_attachment,
+    )
+    .optional()
+}
+
+// ============================================================================
+// GENERATED CV OPERATIONS
+// ============================================================================
+
+/// Sauvegarde le texte du CV généré et la liste des adaptations
+pub fn save_generated_cv_text(
+    conn: &Connection,
+    application_id: i64,
+    cv_text: &str,
+    adaptations: &[String],
+) -> Result<()> {
+    let adaptations_json = serde_json::to_string(adaptations).unwrap_or_else(|_| "[]".to_string());
+    conn.execute(
+        "UPDATE job_applications SET
+            generated_cv_text = ?1,
+            cv_adaptations = ?2,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?3",
+        (cv_text, adaptations_json, application_id),
+    )?;
+    Ok(())
+}
+
+/// Récupère le CV généré d'une candidature
+pub fn get_generated_cv(conn: &Connection, application_id: i64) -> Result<Option<GeneratedCvRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, user_id, base_cv_id, company, job_title, generated_cv_text, cv_adaptations
+         FROM job_applications WHERE id = ?1"
+    )?;
+    let record = stmt
+        .query_row((application_id,), |row| {
+            let adaptations: Option<String> = row.get(6)?;
+            Ok(GeneratedCvRecord {
+                application_id: row.get(0)?,
+                user_id: row.get(1)?,
+                base_cv_id: row.get(2)?,
+                company: row.get(3)?,
+                job_title: row.get(4)?,
+                generated_cv_text: row.get(5)?,
+                adaptations: adaptations
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+            })
+        })
+        .optional()?;
+    Ok(record)
+}
+
 // ============================================================================
 // REMINDER OPERATIONS
 // ============================================================================
 
-/// Crée un rappel pour une candidature
+/// Crée un rappel pour une candidature (avec un message personnalisé optionnel)
 pub fn set_application_reminder(
     conn: &Connection,
     application_id: i64,
     reminder_date: &str,
+    note: Option<&str>,
 ) -> Result<()> {
     conn.execute(
         "UPDATE job_applications SET
             reminder_date = ?1,
+            reminder_note = ?3,
             reminder_sent = 0,
+            reminder_retry_count = 0,
+            reminder_send_failed = 0,
+            reminder_escalation_count = 0,
+            last_reminded_at = NULL,
             updated_at = CURRENT_TIMESTAMP
          WHERE id = ?2",
-        (reminder_date, application_id),
+        (reminder_date, application_id, note),
     )?;
     Ok(())
 }
@@ -680,7 +1806,12 @@ pub fn clear_application_reminder(conn: &Connection, application_id: i64) -> Res
     conn.execute(
         "UPDATE job_applications SET
             reminder_date = NULL,
+            reminder_note = NULL,
             reminder_sent = 0,
+            reminder_retry_count = 0,
+            reminder_send_failed = 0,
+            reminder_escalation_count = 0,
+            last_reminded_at = NULL,
             updated_at = CURRENT_TIMESTAMP
          WHERE id = ?1",
         (application_id,),
@@ -688,11 +1819,13 @@ pub fn clear_application_reminder(conn: &Connection, application_id: i64) -> Res
     Ok(())
 }
 
-/// Marque un rappel de candidature comme envoyé
+/// Marque un rappel de candidature comme envoyé (et mémorise le statut pour les relances)
 pub fn mark_application_reminder_sent(conn: &Connection, application_id: i64) -> Result<()> {
     conn.execute(
         "UPDATE job_applications SET
             reminder_sent = 1,
+            last_reminded_at = CURRENT_TIMESTAMP,
+            reminded_status = status,
             updated_at = CURRENT_TIMESTAMP
          WHERE id = ?1",
         (application_id,),
@@ -700,6 +1833,30 @@ pub fn mark_application_reminder_sent(conn: &Connection, application_id: i64) ->
     Ok(())
 }
 
+/// Enregistre un échec d'envoi d'un rappel de candidature : le rappel repasse en attente
+/// (nouvel essai au prochain passage) jusqu'à `max_retries` échecs, puis il est marqué
+/// envoyé en échec. Retourne `true` si le rappel est abandonné.
+pub fn record_application_reminder_failure(
+    conn: &Connection,
+    application_id: i64,
+    max_retries: i64,
+) -> Result<bool> {
+    conn.execute(
+        "UPDATE job_applications SET
+            reminder_retry_count = reminder_retry_count + 1,
+            reminder_send_failed = (reminder_retry_count + 1 >= ?2),
+            reminder_sent = (reminder_retry_count + 1 >= ?2),
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        (application_id, max_retries),
+    )?;
+    conn.query_row(
+        "SELECT reminder_send_failed FROM job_applications WHERE id = ?1",
+        (application_id,),
+        |row| row.get(0),
+    )
+}
+
 /// Liste les rappels de candidatures en attente (date passée et non envoyés)
 pub fn get_pending_application_reminders(conn: &Connection) -> Result<Vec<JobApplication>> {
     let sql = format!(
@@ -717,6 +1874,49 @@ pub fn get_pending_application_reminders(conn: &Connection) -> Result<Vec<JobApp
     Ok(apps)
 }
 
+/// Liste les candidatures dont le dernier rappel est resté sans suite depuis `after_days` jours
+/// (statut inchangé, candidature toujours en cours, nombre de relances < `max_escalations`)
+pub fn get_unacknowledged_application_reminders(
+    conn: &Connection,
+    after_days: i64,
+    max_escalations: i64,
+) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE reminder_sent = 1
+         AND reminder_send_failed = 0
+         AND last_reminded_at IS NOT NULL
+         AND status = reminded_status
+         AND status NOT IN ('rejected', 'accepted')
+         AND COALESCE(reminder_escalation_count, 0) < ?2
+         AND datetime(last_reminded_at) <= datetime('now', '-' || ?1 || ' days')
+         ORDER BY last_reminded_at ASC",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map((after_days, max_escalations), map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
+
+/// Enregistre une relance (à appeler AVANT l'envoi). Retourne le numéro de la relance.
+pub fn record_reminder_escalation(conn: &Connection, application_id: i64) -> Result<i64> {
+    conn.execute(
+        "UPDATE job_applications SET
+            reminder_escalation_count = COALESCE(reminder_escalation_count, 0) + 1,
+            last_reminded_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        (application_id,),
+    )?;
+    conn.query_row(
+        "SELECT reminder_escalation_count FROM job_applications WHERE id = ?1",
+        (application_id,),
+        |row| row.get(0),
+    )
+}
+
 /// Liste les rappels à venir pour un utilisateur
 pub fn list_user_application_reminders(
     conn: &Connection,
@@ -737,6 +1937,24 @@ pub fn list_user_application_reminders(
     Ok(apps)
 }
 
+/// Entretiens à venir d'un utilisateur (/setinterview), du plus proche au plus lointain
+pub fn list_upcoming_interviews(conn: &Connection, user_id: i64) -> Result<Vec<JobApplication>> {
+    let sql = format!(
+        "{} WHERE user_id = ?1
+         AND interview_at IS NOT NULL
+         AND datetime(interview_at) >= datetime('now')
+         AND archived_at IS NULL
+         ORDER BY interview_at ASC",
+        JOB_APPLICATION_SELECT
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let apps: Vec<JobApplication> = stmt
+        .query_map((user_id,), map_job_application)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(apps)
+}
+
 // ============================================================================
 // STANDALONE REMINDER OPERATIONS
 // ============================================================================
@@ -791,6 +2009,11 @@ pub fn delete_reminder(conn: &Connection, reminder_id: i64, user_id: i64) -> Res
     Ok(rows > 0)
 }
 
+/// Supprime les rappels libres liés à une candidature (avant sa suppression)
+pub fn delete_reminders_for_application(conn: &Connection, application_id: i64) -> Result<usize> {
+    conn.execute("DELETE FROM reminders WHERE application_id = ?1", (application_id,))
+}
+
 /// Marque un rappel comme envoyé
 pub fn mark_reminder_sent(conn: &Connection, reminder_id: i64) -> Result<()> {
     conn.execute(
@@ -800,6 +2023,24 @@ pub fn mark_reminder_sent(conn: &Connection, reminder_id: i64) -> Result<()> {
     Ok(())
 }
 
+/// Enregistre un échec d'envoi d'un rappel libre (canal et DM) : même logique que
+/// `record_application_reminder_failure`. Retourne `true` si le rappel est abandonné.
+pub fn record_reminder_failure(conn: &Connection, reminder_id: i64, max_retries: i64) -> Result<bool> {
+    conn.execute(
+        "UPDATE reminders SET
+            retry_count = retry_count + 1,
+            send_failed = (retry_count + 1 >= ?2),
+            is_sent = (retry_count + 1 >= ?2)
+         WHERE id = ?1",
+        (reminder_id, max_retries),
+    )?;
+    conn.query_row(
+        "SELECT send_failed FROM reminders WHERE id = ?1",
+        (reminder_id,),
+        |row| row.get(0),
+    )
+}
+
 /// Récupère l'historique des statuts d'une candidature
 pub fn get_application_status_history(
     conn: &Connection,
@@ -840,4 +2081,804 @@ pub fn get_pending_reminders(conn: &Connection) -> Result<Vec<Reminder>> {
         .filter_map(|r| r.ok())
         .collect();
     Ok(reminders)
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// COMMAND USAGE OPERATIONS
+// ============================================================================
+
+/// Entrées conservées par utilisateur : les plus anciennes sont supprimées au fil de l'eau
+pub const MAX_COMMAND_USAGE_PER_USER: i64 = 200;
+
+/// Enregistre une commande exécutée et purge les entrées au-delà de MAX_COMMAND_USAGE_PER_USER
+pub fn record_command_usage(conn: &Connection, user_id: i64, command_name: &str, application_id: Option<i64>) -> Result<()> {
+    conn.execute(
+        "INSERT INTO command_usage (user_id, command_name, application_id) VALUES (?1, ?2, ?3)",
+        (user_id, command_name, application_id),
+    )?;
+    conn.execute(
+        "DELETE FROM command_usage
+         WHERE user_id = ?1 AND id NOT IN (
+            SELECT id FROM command_usage WHERE user_id = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+        (user_id, MAX_COMMAND_USAGE_PER_USER),
+    )?;
+    Ok(())
+}
+
+/// Dernières commandes d'un utilisateur, de la plus récente à la plus ancienne
+pub fn list_command_usage(conn: &Connection, user_id: i64, limit: i64) -> Result<Vec<CommandUsage>> {
+    let mut stmt = conn.prepare(
+        "SELECT command_name, application_id, created_at FROM command_usage
+         WHERE user_id = ?1
+         ORDER BY id DESC
+         LIMIT ?2",
+    )?;
+    let usage = stmt
+        .query_map((user_id, limit), |row| {
+            Ok(CommandUsage {
+                command_name: row.get(0)?,
+                application_id: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(usage)
+}
+
+// ============================================================================
+// GUILD COMMAND CONFIG OPERATIONS
+// ============================================================================
+
+/// Active ou désactive une commande sur un serveur
+pub fn set_command_enabled(conn: &Connection, guild_id: i64, command_name: &str, enabled: bool) -> Result<()> {
+    conn.execute(
+        "INSERT INTO guild_command_config (guild_id, command_name, enabled)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(guild_id, command_name) DO UPDATE SET
+            enabled = excluded.enabled,
+            updated_at = CURRENT_TIMESTAMP",
+        (guild_id, command_name, enabled),
+    )?;
+    Ok(())
+}
+
+/// Une commande est activée tant qu'aucune ligne ne la désactive
+pub fn is_command_enabled(conn: &Connection, guild_id: i64, command_name: &str) -> Result<bool> {
+    let enabled: Option<bool> = conn
+        .query_row(
+            "SELECT enabled FROM guild_command_config WHERE guild_id = ?1 AND command_name = ?2",
+            (guild_id, command_name),
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(enabled.unwrap_or(true))
+}
+
+/// Liste les commandes désactivées sur un serveur (ordre alphabétique)
+pub fn list_disabled_commands(conn: &Connection, guild_id: i64) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT command_name FROM guild_command_config
+         WHERE guild_id = ?1 AND enabled = 0
+         ORDER BY command_name ASC",
+    )?;
+    let names = stmt
+        .query_map((guild_id,), |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+    Ok(names)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init::create_tables_for_test;
+
+    fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        create_tables_for_test(&conn).unwrap();
+        upsert_user(&conn, 42, "tester").unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_settings_defaults_and_update() {
+        let conn = test_conn();
+
+        // Aucune ligne : valeurs par défaut
+        let mut settings = get_settings(&conn, 42).unwrap();
+        assert_eq!(settings.user_id, 42);
+        assert_eq!(settings.default_reminder_time, "09:00");
+        assert_eq!(settings.auto_reminder_days, 0);
+        assert!(!settings.private_by_default);
+
+        settings.auto_reminder_days = 7;
+        settings.notification_channel_id = Some(123);
+        settings.private_by_default = true;
+        update_settings(&conn, &settings).unwrap();
+
+        let saved = get_settings(&conn, 42).unwrap();
+        assert_eq!(saved.auto_reminder_days, 7);
+        assert_eq!(saved.notification_channel_id, Some(123));
+        assert!(saved.private_by_default);
+        assert_eq!(saved.locale, "fr");
+    }
+
+    #[test]
+    fn test_application_versions_chain() {
+        let conn = test_conn();
+        let first = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let second = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let third = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let other = create_application(&conn, 42, None, Some("Ops"), Some("Globex"), None, None, "desc").unwrap();
+        set_application_parent(&conn, second, first).unwrap();
+        set_application_parent(&conn, third, second).unwrap();
+        update_application_analysis(&conn, first, "", "[]", "[]", "[]", 40).unwrap();
+        update_application_analysis(&conn, third, "", "[]", "[]", "[]", 75).unwrap();
+
+        // Même chaîne quel que soit le point d'entrée
+        for id in [first, second, third] {
+            let ids: Vec<i64> = get_application_versions(&conn, id).unwrap().iter().map(|v| v.id).collect();
+            assert_eq!(ids, vec![first, second, third]);
+        }
+        let versions = get_application_versions(&conn, third).unwrap();
+        assert_eq!(versions[0].match_score, Some(40));
+        assert_eq!(versions[2].match_score, Some(75));
+
+        // Une candidature isolée est sa propre chaîne
+        let ids: Vec<i64> = get_application_versions(&conn, other).unwrap().iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![other]);
+    }
+
+    #[test]
+    fn test_delete_application_removes_linked_reminders() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        create_reminder(&conn, 42, Some(app_id), 1, "2000-01-01 09:00:00", "Relancer Acme").unwrap();
+        let standalone = create_reminder(&conn, 42, None, 1, "2000-01-01 09:00:00", "Autre").unwrap();
+
+        // Mauvais propriétaire : rien n'est supprimé
+        assert!(!delete_application(&conn, app_id, 7).unwrap());
+        assert_eq!(get_pending_reminders(&conn).unwrap().len(), 2);
+
+        assert!(delete_application(&conn, app_id, 42).unwrap());
+        let pending: Vec<i64> = get_pending_reminders(&conn).unwrap().iter().map(|r| r.id).collect();
+        assert_eq!(pending, vec![standalone]);
+        assert_eq!(get_application_owner(&conn, app_id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_json_column_accessors() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        let app_id = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_analysis(
+            &conn,
+            app_id,
+            "",
+            r#"["Rust", "SQL"]"#,
+            r#"[{"skill": "Rust", "cv_level": "expert", "required": "senior", "match": true}]"#,
+            "not json",
+            70,
+        )
+        .unwrap();
+
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.required_skills_parsed(), vec!["Rust".to_string(), "SQL".to_string()]);
+        let matched = app.matched_skills();
+        assert_eq!(matched.len(), 1);
+        assert!(matched[0].is_match);
+        assert!(app.missing_skills_parsed().is_empty());
+    }
+
+    #[test]
+    fn test_application_benefits() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert!(get_application(&conn, app_id).unwrap().unwrap().benefits_parsed().is_empty());
+
+        update_application_benefits(&conn, app_id, &["Télétravail".to_string(), "RTT".to_string()]).unwrap();
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.benefits_parsed(), vec!["Télétravail".to_string(), "RTT".to_string()]);
+        assert_eq!(list_applications(&conn, 42, None, 10).unwrap()[0].benefits_parsed().len(), 2);
+
+        assert!(app.language.is_none());
+        update_application_language(&conn, app_id, "en").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_application_salary_currency() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().salary_currency, "EUR");
+
+        update_application_currency(&conn, app_id, "USD").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().salary_currency, "USD");
+
+        update_application_salary(&conn, app_id, Some(90000), None, "Bon", Some(80000), Some(95000), Some(110000), "CHF")
+            .unwrap();
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.salary_currency, "CHF");
+        assert_eq!(app.salary_min, Some(90000));
+    }
+
+    #[test]
+    fn test_list_applications_filtered_by_score() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        for (title, score) in [("Dev", Some(85)), ("Ops", Some(60)), ("QA", None)] {
+            let app_id = create_application(&conn, 42, Some(cv_id), Some(title), Some("Acme"), None, None, "desc").unwrap();
+            if let Some(score) = score {
+                update_application_analysis(&conn, app_id, "", "[]", "[]", "[]", score).unwrap();
+            }
+        }
+
+        let titles = |apps: Vec<JobApplication>| -> Vec<String> {
+            apps.into_iter().filter_map(|a| a.job_title).collect()
+        };
+        assert_eq!(titles(list_applications_filtered(&conn, 42, None, Some(70), None, false, 10).unwrap()), vec!["Dev"]);
+        assert_eq!(titles(list_applications_filtered(&conn, 42, None, None, Some(70), false, 10).unwrap()), vec!["Ops"]);
+        assert_eq!(list_applications_filtered(&conn, 42, None, None, None, false, 10).unwrap().len(), 3);
+        assert!(list_applications_filtered(&conn, 42, Some("applied"), Some(0), None, false, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_create_application_reads_back_with_defaults() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), Some("Paris"), None, "desc").unwrap();
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!((app.user_id, app.job_title.as_deref(), app.location.as_deref()), (42, Some("Dev"), Some("Paris")));
+        assert_eq!(app.raw_job_description, "desc");
+        assert_eq!(app.salary_currency, "EUR");
+        assert_eq!(app.generated_cv_format, "pdf");
+        assert_eq!(app.status, "generated");
+        assert!(!app.reminder_sent && !app.applied_externally && !app.minimal_tracking && !app.anonymize_cv);
+        assert!(app.cover_letter.is_none() && app.thread_id.is_none() && app.reminder_date.is_none());
+        assert!(app.match_score.is_none() && app.salary_analysis.is_none() && app.archived_at.is_none());
+        assert!(!app.created_at.is_empty());
+        // Même mapping via JOB_APPLICATION_SELECT
+        assert_eq!(list_applications(&conn, 42, None, 10).unwrap()[0].id, app_id);
+    }
+
+    #[test]
+    fn test_set_application_minimal_tracking() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert!(!get_application(&conn, app_id).unwrap().unwrap().minimal_tracking);
+        set_application_minimal_tracking(&conn, app_id, true).unwrap();
+        assert!(get_application(&conn, app_id).unwrap().unwrap().minimal_tracking);
+        assert!(list_applications(&conn, 42, None, 10).unwrap()[0].minimal_tracking);
+    }
+
+    #[test]
+    fn test_update_application_seniority() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().seniority, None);
+        update_application_seniority(&conn, app_id, "senior").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().seniority.as_deref(), Some("senior"));
+        assert_eq!(list_applications(&conn, 42, None, 10).unwrap()[0].seniority.as_deref(), Some("senior"));
+    }
+
+    #[test]
+    fn test_set_application_anonymize_cv() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert!(!get_application(&conn, app_id).unwrap().unwrap().anonymize_cv);
+        set_application_anonymize_cv(&conn, app_id, true).unwrap();
+        assert!(get_application(&conn, app_id).unwrap().unwrap().anonymize_cv);
+        assert!(list_applications(&conn, 42, None, 10).unwrap()[0].anonymize_cv);
+    }
+
+    #[test]
+    fn test_update_application_analysis_options() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().analysis_options, AnalysisOptions::default());
+        let options = AnalysisOptions {
+            fit_level: 3,
+            output_language: Some("en".to_string()),
+            pdf_template: Some("modern".to_string()),
+            pdf_accent_color: Some("#2c3e50".to_string()),
+            skip_salary: true,
+            suggest_roles: true,
+            save_synthesis_pdf: true,
+        };
+        update_application_analysis_options(&conn, app_id, &options).unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().analysis_options, options);
+        assert_eq!(list_applications(&conn, 42, None, 10).unwrap()[0].analysis_options, options);
+    }
+
+    #[test]
+    fn test_set_application_interview() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().interview_at, None);
+        assert!(!set_application_interview(&conn, app_id, 7, "2030-03-15 14:00:00").unwrap());
+        assert!(set_application_interview(&conn, app_id, 42, "2030-03-15 14:00:00").unwrap());
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.interview_at.as_deref(), Some("2030-03-15 14:00:00"));
+        let listed = list_applications(&conn, 42, None, 10).unwrap();
+        assert_eq!(listed[0].interview_at, app.interview_at);
+
+        let past = create_application(&conn, 42, None, Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        set_application_interview(&conn, past, 42, "2000-01-01 10:00:00").unwrap();
+        let upcoming: Vec<i64> = list_upcoming_interviews(&conn, 42).unwrap().iter().map(|a| a.id).collect();
+        assert_eq!(upcoming, vec![app_id]);
+        assert!(list_upcoming_interviews(&conn, 7).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_archive_closed_applications() {
+        let conn = test_conn();
+        let closed = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let open = create_application(&conn, 42, None, Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_status(&conn, closed, 42, "rejected", None).unwrap();
+        conn.execute(
+            "UPDATE job_applications SET updated_at = datetime('now', '-40 days')",
+            [],
+        ).unwrap();
+
+        // Option désactivée par défaut
+        assert_eq!(archive_closed_applications(&conn).unwrap(), 0);
+
+        let settings = UserSettings { user_id: 42, auto_archive_days: 30, ..UserSettings::default() };
+        update_settings(&conn, &settings).unwrap();
+        assert_eq!(get_settings(&conn, 42).unwrap().auto_archive_days, 30);
+        assert_eq!(get_settings(&conn, 42).unwrap().salary_target(), None);
+        assert_eq!(get_settings(&conn, 42).unwrap().min_fit_warn, 40);
+        assert_eq!(archive_closed_applications(&conn).unwrap(), 1);
+        assert_eq!(archive_closed_applications(&conn).unwrap(), 0);
+
+        let visible = list_applications_filtered(&conn, 42, None, None, None, false, 10).unwrap();
+        assert_eq!(visible.iter().map(|a| a.id).collect::<Vec<_>>(), vec![open]);
+        assert_eq!(list_applications_filtered(&conn, 42, None, None, None, true, 10).unwrap().len(), 2);
+
+        // Rouvrir la candidature la désarchive
+        update_application_status(&conn, closed, 42, "interview", None).unwrap();
+        assert!(get_application(&conn, closed).unwrap().unwrap().archived_at.is_none());
+    }
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(escape_like(r"100%_a\b"), r"100\%\_a\\b");
+
+        let conn = test_conn();
+        for company in ["C_C++ Labs", "CxC++ Labs", "Remise 100%", "Remise 1000", r"Dept\IT", "DeptIT"] {
+            create_application(&conn, 42, None, Some("Dev"), Some(company), None, None, "desc").unwrap();
+        }
+        let sql = format!(
+            "SELECT company FROM job_applications WHERE company LIKE ?1 {} ORDER BY id",
+            LIKE_ESCAPE_CLAUSE
+        );
+        let search = |term: &str| -> Vec<String> {
+            let mut stmt = conn.prepare(&sql).unwrap();
+            stmt.query_map([like_contains_pattern(term)], |row| row.get(0))
+                .unwrap()
+                .map(|r| r.unwrap())
+                .collect()
+        };
+        assert_eq!(search("c_c++"), vec!["C_C++ Labs"]);
+        assert_eq!(search("100%"), vec!["Remise 100%"]);
+        assert_eq!(search(r"Dept\"), vec![r"Dept\IT"]);
+        assert_eq!(search("%").len(), 1);
+        assert_eq!(search("_").len(), 1);
+    }
+
+    #[test]
+    fn test_stale_interview_applications() {
+        let conn = test_conn();
+        let stale = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let applied = create_application(&conn, 42, None, Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_status(&conn, stale, 42, "interview", None).unwrap();
+        update_application_status(&conn, applied, 42, "applied", None).unwrap();
+        conn.execute("UPDATE job_applications SET updated_at = datetime('now', '-20 days')", []).unwrap();
+
+        // Relance désactivée par défaut
+        assert!(get_stale_interview_applications(&conn, 14).unwrap().is_empty());
+
+        let settings = UserSettings { user_id: 42, interview_nudges: true, ..UserSettings::default() };
+        update_settings(&conn, &settings).unwrap();
+        assert!(get_settings(&conn, 42).unwrap().interview_nudges);
+        let ids = |apps: Vec<JobApplication>| apps.iter().map(|a| a.id).collect::<Vec<_>>();
+        assert_eq!(ids(get_stale_interview_applications(&conn, 14).unwrap()), vec![stale]);
+        assert!(get_stale_interview_applications(&conn, 30).unwrap().is_empty());
+
+        // Une seule relance par période d'inactivité
+        mark_interview_nudged(&conn, stale).unwrap();
+        assert!(get_stale_interview_applications(&conn, 14).unwrap().is_empty());
+
+        // Nouvelle activité puis inactivité : nouvelle relance
+        update_application_notes(&conn, stale, "Relancé par mail").unwrap();
+        conn.execute(
+            "UPDATE job_applications SET updated_at = datetime('now', '-15 days'),
+                 interview_nudged_at = datetime('now', '-18 days') WHERE id = ?1",
+            [stale],
+        ).unwrap();
+        assert_eq!(ids(get_stale_interview_applications(&conn, 14).unwrap()), vec![stale]);
+    }
+
+    #[test]
+    fn test_list_rescorable_applications() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        let recent = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let rejected = create_application(&conn, 42, Some(cv_id), Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_status(&conn, rejected, 42, "rejected", None).unwrap();
+        let old = create_application(&conn, 42, Some(cv_id), Some("QA"), Some("Acme"), None, None, "desc").unwrap();
+        conn.execute(
+            "UPDATE job_applications SET created_at = datetime('now', '-60 days') WHERE id = ?1",
+            [old],
+        ).unwrap();
+
+        let apps = list_rescorable_applications(&conn, 42, 30, 10).unwrap();
+        assert_eq!(apps.iter().map(|a| a.id).collect::<Vec<_>>(), vec![recent]);
+        assert!(list_rescorable_applications(&conn, 7, 30, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backfill_missing_analysis() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        update_cv_extracted_data(&conn, cv_id, "Rust, SQL", "{}").unwrap();
+        let unlinked = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let scored = create_application(&conn, 42, Some(cv_id), Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_analysis(&conn, scored, "", "[]", "[]", "[]", 80).unwrap();
+        // Pas de texte de CV exploitable : ignorée
+        upsert_user(&conn, 7, "other").unwrap();
+        save_cv(&conn, 7, "cv.pdf", "cv.pdf", "/tmp/cv7.pdf", 10, None).unwrap();
+        create_application(&conn, 7, None, Some("QA"), Some("Beta"), None, None, "desc").unwrap();
+
+        let apps = list_applications_missing_analysis(&conn, 0, 10).unwrap();
+        assert_eq!(apps.iter().map(|a| a.id).collect::<Vec<_>>(), vec![unlinked]);
+        // Reprise après la dernière candidature traitée
+        assert!(list_applications_missing_analysis(&conn, unlinked, 10).unwrap().is_empty());
+        assert_eq!(count_applications_missing_analysis(&conn).unwrap(), 1);
+        assert_eq!(count_applications(&conn).unwrap(), 3);
+
+        let before = get_application(&conn, unlinked).unwrap().unwrap();
+        assert!(backfill_application_skills(&conn, unlinked, cv_id, "[\"Rust\"]", "[]", 65).unwrap());
+        let after = get_application(&conn, unlinked).unwrap().unwrap();
+        assert_eq!(after.match_score, Some(65));
+        assert_eq!(after.base_cv_id, Some(cv_id));
+        assert_eq!(after.updated_at, before.updated_at);
+
+        // Déjà rempli : rien n'est écrasé
+        assert!(!backfill_application_skills(&conn, unlinked, cv_id, "[]", "[]", 10).unwrap());
+        assert_eq!(count_applications_missing_analysis(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_streak_from_dates() {
+        let d = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let today = d("2025-03-10");
+        assert_eq!(streak_from_dates(&[], today), 0);
+        assert_eq!(streak_from_dates(&[d("2025-03-10"), d("2025-03-09"), d("2025-03-08"), d("2025-03-05")], today), 3);
+        // Rien aujourd'hui mais hier : la série tient encore
+        assert_eq!(streak_from_dates(&[d("2025-03-09"), d("2025-03-08")], today), 2);
+        assert_eq!(streak_from_dates(&[d("2025-03-07"), d("2025-03-06")], today), 0);
+    }
+
+    #[test]
+    fn test_application_streak() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        assert_eq!(get_application_streak(&conn, 42).unwrap(), 0);
+        for offset in ["-0 days", "-0 days", "-1 days", "-3 days"] {
+            let app_id = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+            conn.execute(
+                "UPDATE job_applications SET created_at = datetime('now', ?1) WHERE id = ?2",
+                (offset, app_id),
+            ).unwrap();
+        }
+        assert_eq!(get_application_streak(&conn, 42).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_application_without_cv() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+
+        // Sans CV la candidature doit rester lisible (base_cv_id NULL)
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.base_cv_id, None);
+        assert_eq!(list_applications(&conn, 42, None, 10).unwrap().len(), 1);
+
+        // Un ID de CV inexistant est refusé par la clé étrangère
+        assert!(create_application(&conn, 42, Some(9999), None, None, None, None, "desc").is_err());
+    }
+
+    #[test]
+    fn test_update_application_cv() {
+        let conn = test_conn();
+        upsert_user(&conn, 7, "other").unwrap();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+
+        assert!(!update_application_cv(&conn, app_id, 7, cv_id).unwrap());
+        assert!(update_application_cv(&conn, app_id, 42, cv_id).unwrap());
+        assert_eq!(get_application(&conn, app_id).unwrap().unwrap().base_cv_id, Some(cv_id));
+    }
+
+    #[test]
+    fn test_soft_delete_and_restore_cv() {
+        let conn = test_conn();
+        let old_id = save_cv(&conn, 42, "old.pdf", "old.pdf", "data/cvs/old.pdf", 10, None).unwrap();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "data/cvs/cv.pdf", 10, None).unwrap();
+
+        assert!(!soft_delete_cv(&conn, 7, cv_id, "data/cvs/trash/cv.pdf").unwrap());
+        assert!(soft_delete_cv(&conn, 42, cv_id, "data/cvs/trash/cv.pdf").unwrap());
+        assert!(get_active_cv(&conn, 42).unwrap().is_none());
+        assert_eq!(list_user_cvs(&conn, 42).unwrap().len(), 1);
+
+        let deleted = list_deleted_cvs(&conn, 42, 10).unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].file_path, "data/cvs/trash/cv.pdf");
+        assert!(deleted[0].deleted_at.is_some());
+
+        // Seuls les CVs à la corbeille de l'utilisateur peuvent être restaurés
+        assert!(!restore_cv(&conn, 42, old_id, "data/cvs/old.pdf").unwrap());
+        assert!(!restore_cv(&conn, 7, cv_id, "data/cvs/cv.pdf").unwrap());
+        assert!(restore_cv(&conn, 42, cv_id, "data/cvs/cv.pdf").unwrap());
+        let active = get_active_cv(&conn, 42).unwrap().unwrap();
+        assert_eq!(active.id, cv_id);
+        assert_eq!(active.file_path, "data/cvs/cv.pdf");
+        assert!(active.deleted_at.is_none());
+        assert!(list_deleted_cvs(&conn, 42, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_submitted_applications_in_stats() {
+        let conn = test_conn();
+        let analyzed = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let applied = create_application(&conn, 42, None, Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        let interview = create_application(&conn, 42, None, Some("QA"), Some("Acme"), None, None, "desc").unwrap();
+        assert!(!get_application(&conn, analyzed).unwrap().unwrap().applied_externally);
+
+        update_application_status(&conn, applied, 42, "applied", None).unwrap();
+        // Refus après envoi : la candidature reste comptée comme envoyée
+        update_application_status(&conn, applied, 42, "rejected", None).unwrap();
+        update_application_status(&conn, interview, 42, "interview", None).unwrap();
+        update_application_status(&conn, analyzed, 42, "rejected", None).unwrap();
+
+        assert!(get_application(&conn, applied).unwrap().unwrap().applied_externally);
+        let stats = get_user_stats(&conn, 42).unwrap();
+        assert_eq!(stats.total_applications, 3);
+        assert_eq!(stats.submitted_applications, 2);
+    }
+
+    #[test]
+    fn test_placeholder_company_is_stored_as_null() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("N/A"), Some("Non spécifié"), None, None, "desc").unwrap();
+        create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+
+        let app = get_application(&conn, app_id).unwrap().unwrap();
+        assert_eq!(app.company, None);
+        assert_eq!(app.job_title, None);
+        let stats = get_user_stats(&conn, 42).unwrap();
+        assert_eq!(stats.top_companies, vec![("Acme".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_interrupted_applications() {
+        let conn = test_conn();
+        let done = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        let interrupted = create_application(&conn, 42, None, Some("Ops"), Some("Acme"), None, None, "desc").unwrap();
+        let notified = create_application(&conn, 42, None, Some("QA"), Some("Acme"), None, None, "desc").unwrap();
+        // Candidature importée : jamais passée par le pipeline
+        create_application(&conn, 42, None, Some("PM"), Some("Acme"), None, None, "desc").unwrap();
+        for id in [done, interrupted, notified] {
+            start_application_pipeline(&conn, id, 1234).unwrap();
+        }
+        set_application_pipeline_stage(&conn, done, None).unwrap();
+        set_application_pipeline_stage(&conn, interrupted, Some(PIPELINE_SKILLS)).unwrap();
+        set_application_pipeline_stage(&conn, notified, Some(PIPELINE_STALLED)).unwrap();
+
+        let apps = list_interrupted_applications(&conn).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].id, interrupted);
+        assert_eq!(apps[0].pipeline_stage.as_deref(), Some(PIPELINE_SKILLS));
+        assert_eq!(apps[0].origin_channel_id, Some(1234));
+    }
+
+    #[test]
+    fn test_purge_user_data() {
+        let conn = test_conn();
+        upsert_user(&conn, 7, "other").unwrap();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        let app_id = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        update_application_status(&conn, app_id, 42, "applied", None).unwrap();
+        add_attachment(&conn, app_id, 42, "offer.pdf", "/tmp/offer.pdf", 10, None).unwrap();
+        create_reminder(&conn, 42, Some(app_id), 1, "2000-01-01 09:00:00", "Relancer").unwrap();
+        create_application(&conn, 7, None, Some("Ops"), Some("Globex"), None, None, "desc").unwrap();
+
+        let summary = purge_user_data(&conn, 42).unwrap();
+        assert_eq!(summary.cvs, 1);
+        assert_eq!(summary.applications, 1);
+        assert_eq!(summary.status_history, 1);
+        assert_eq!(summary.attachments, 1);
+        assert_eq!(summary.reminders, 1);
+        assert!(summary.file_paths.contains(&"/tmp/cv.pdf".to_string()));
+        assert!(summary.file_paths.contains(&"/tmp/offer.pdf".to_string()));
+
+        assert!(get_user(&conn, 42).unwrap().is_none());
+        // Les données des autres utilisateurs sont intactes
+        assert_eq!(get_user_stats(&conn, 7).unwrap().total_applications, 1);
+    }
+
+    #[test]
+    fn test_command_usage_log() {
+        let conn = test_conn();
+        record_command_usage(&conn, 42, "applyjob", None).unwrap();
+        record_command_usage(&conn, 42, "updatestatus", Some(12)).unwrap();
+        record_command_usage(&conn, 7, "status", None).unwrap();
+
+        let usage = list_command_usage(&conn, 42, 10).unwrap();
+        assert_eq!(usage.iter().map(|u| u.command_name.as_str()).collect::<Vec<_>>(), vec!["updatestatus", "applyjob"]);
+        assert_eq!(usage[0].application_id, Some(12));
+        assert_eq!(list_command_usage(&conn, 42, 1).unwrap().len(), 1);
+
+        // Seules les MAX_COMMAND_USAGE_PER_USER dernières entrées sont conservées
+        for _ in 0..MAX_COMMAND_USAGE_PER_USER {
+            record_command_usage(&conn, 42, "status", None).unwrap();
+        }
+        let usage = list_command_usage(&conn, 42, 1000).unwrap();
+        assert_eq!(usage.len() as i64, MAX_COMMAND_USAGE_PER_USER);
+        assert!(usage.iter().all(|u| u.command_name == "status"));
+
+        purge_user_data(&conn, 42).unwrap();
+        assert!(list_command_usage(&conn, 42, 10).unwrap().is_empty());
+        assert_eq!(list_command_usage(&conn, 7, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_export_user_data() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        let app_id = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        save_cover_letter(&conn, app_id, "Madame, Monsieur...").unwrap();
+        update_application_status(&conn, app_id, 42, "applied", None).unwrap();
+        create_reminder(&conn, 42, Some(app_id), 1, "2000-01-01 09:00:00", "Relancer").unwrap();
+
+        let export = export_user_data(&conn, 42).unwrap();
+        assert_eq!(export.user.as_ref().map(|u| u.username.as_str()), Some("tester"));
+        assert_eq!(export.cvs.len(), 1);
+        assert_eq!(export.applications.len(), 1);
+        assert_eq!(export.applications[0].cover_letter.as_deref(), Some("Madame, Monsieur..."));
+        assert_eq!(export.status_history.len(), 1);
+        assert_eq!(export.reminders.len(), 1);
+        assert!(serde_json::to_string(&export).is_ok());
+    }
+
+    #[test]
+    fn test_unacknowledged_reminder_escalation() {
+        let conn = test_conn();
+        let cv_id = save_cv(&conn, 42, "cv.pdf", "cv.pdf", "/tmp/cv.pdf", 10, None).unwrap();
+        let app_id = create_application(&conn, 42, Some(cv_id), Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        set_application_reminder(&conn, app_id, "2020-01-01 09:00:00", Some("Demander le package relocation")).unwrap();
+        let pending = get_pending_application_reminders(&conn).unwrap();
+        assert_eq!(pending[0].reminder_note.as_deref(), Some("Demander le package relocation"));
+        mark_application_reminder_sent(&conn, app_id).unwrap();
+
+        // Rappel trop récent : pas encore de relance
+        assert!(get_unacknowledged_application_reminders(&conn, 3, 2).unwrap().is_empty());
+
+        conn.execute(
+            "UPDATE job_applications SET last_reminded_at = datetime('now', '-4 days') WHERE id = ?1",
+            (app_id,),
+        ).unwrap();
+        let pending = get_unacknowledged_application_reminders(&conn, 3, 2).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(record_reminder_escalation(&conn, app_id).unwrap(), 1);
+
+        // Un changement de statut arrête les relances
+        conn.execute(
+            "UPDATE job_applications SET last_reminded_at = datetime('now', '-4 days') WHERE id = ?1",
+            (app_id,),
+        ).unwrap();
+        update_application_status(&conn, app_id, 42, "applied", None).unwrap();
+        assert!(get_unacknowledged_application_reminders(&conn, 3, 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_guild_command_toggle() {
+        let conn = test_conn();
+        assert!(is_command_enabled(&conn, 1, "applyjob").unwrap());
+
+        set_command_enabled(&conn, 1, "applyjob", false).unwrap();
+        set_command_enabled(&conn, 1, "status", false).unwrap();
+        assert!(!is_command_enabled(&conn, 1, "applyjob").unwrap());
+        // Configuration propre à chaque serveur
+        assert!(is_command_enabled(&conn, 2, "applyjob").unwrap());
+        assert_eq!(list_disabled_commands(&conn, 1).unwrap(), vec!["applyjob", "status"]);
+
+        set_command_enabled(&conn, 1, "applyjob", true).unwrap();
+        assert!(is_command_enabled(&conn, 1, "applyjob").unwrap());
+        assert_eq!(list_disabled_commands(&conn, 1).unwrap(), vec!["status"]);
+    }
+
+    #[test]
+    fn test_reminder_send_failures_are_capped() {
+        let conn = test_conn();
+        let app_id = create_application(&conn, 42, None, Some("Dev"), Some("Acme"), None, None, "desc").unwrap();
+        set_application_reminder(&conn, app_id, "2020-01-01 09:00:00", None).unwrap();
+
+        // Échecs sous le plafond : le rappel reste en attente
+        mark_application_reminder_sent(&conn, app_id).unwrap();
+        assert!(!record_application_reminder_failure(&conn, app_id, 2).unwrap());
+        assert_eq!(get_pending_application_reminders(&conn).unwrap().len(), 1);
+
+        // Plafond atteint : abandon, sans relance ultérieure
+        assert!(record_application_reminder_failure(&conn, app_id, 2).unwrap());
+        assert!(get_pending_application_reminders(&conn).unwrap().is_empty());
+        conn.execute(
+            "UPDATE job_applications SET last_reminded_at = datetime('now', '-4 days') WHERE id = ?1",
+            (app_id,),
+        ).unwrap();
+        assert!(get_unacknowledged_application_reminders(&conn, 3, 2).unwrap().is_empty());
+
+        // Un nouveau rappel repart de zéro
+        set_application_reminder(&conn, app_id, "2020-01-02 09:00:00", None).unwrap();
+        assert!(!record_application_reminder_failure(&conn, app_id, 2).unwrap());
+
+        let reminder_id = create_reminder(&conn, 42, None, 1, "2020-01-01 09:00:00", "Relancer").unwrap();
+        mark_reminder_sent(&conn, reminder_id).unwrap();
+        assert!(!record_reminder_failure(&conn, reminder_id, 2).unwrap());
+        assert_eq!(get_pending_reminders(&conn).unwrap().len(), 1);
+        assert!(record_reminder_failure(&conn, reminder_id, 2).unwrap());
+        assert!(get_pending_reminders(&conn).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_failure_restores_previous_database() {
+        use crate::services::backup::BackupError;
+        let dir = std::env::temp_dir().join(format!("reopen-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("bot.db");
+        let safety = dir.join("safety.db");
+        let conn = crate::db::init::open_database(&db_path).unwrap();
+        let db = crate::db::Database { conn: std::sync::Arc::new(tokio::sync::Mutex::new(conn)) };
+        db.upsert_user(42, "tester").await.unwrap();
+        db.backup_database(&safety.to_string_lossy()).await.unwrap();
+
+        // Fichier de remplacement illisible : la réouverture échoue, la sauvegarde de sécurité est remise en place
+        let result = db.reopen_at(
+            &db_path,
+            |path| -> Result<(), BackupError> {
+                std::fs::write(path, b"definitely not sqlite, just some bytes long enough")?;
+                Ok(())
+            },
+            |path| -> Result<(), BackupError> {
+                std::fs::copy(&safety, path)?;
+                Ok(())
+            },
+        ).await;
+        assert!(result.is_err());
+        assert_eq!(db.get_user(42).await.unwrap().unwrap().username, "tester");
+
+        // La base rouverte est bien le fichier, pas une base en mémoire
+        db.upsert_user(7, "other").await.unwrap();
+        drop(db);
+        let reopened = Connection::open(&db_path).unwrap();
+        assert!(get_user(&reopened, 7).unwrap().is_some());
+        drop(reopened);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backup_database_copies_data() {
+        let conn = test_conn();
+        let path = std::env::temp_dir().join(format!("backup-test-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_string_lossy().to_string();
+
+        backup_database(&conn, &path).unwrap();
+        let copy = Connection::open(&path).unwrap();
+        assert_eq!(get_user(&copy, 42).unwrap().unwrap().username, "tester");
+
+        // VACUUM INTO refuse d'écraser un fichier existant
+        assert!(backup_database(&conn, &path).is_err());
+        drop(copy);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
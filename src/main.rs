@@ -1,35 +1,57 @@
 mod commands;
+mod config;
 mod db;
 mod services;
 
 use commands::{
-    ApplyJobCommand, ApplicationHistoryCommand, ClearAllCvsCommand, CommandRegistry, DeleteCvCommand,
+    AnalyzeMessageCommand, ApplyJobCommand, ApplyJobFormCommand, ApplicationHistoryCommand, AttachCommand, AttachmentsCommand, BenefitsCommand, ClearAllCvsCommand, CloneApplicationCommand, CommandRegistry, CompareCvCommand,
+    CvProfileCommand, DeleteCvCommand, ExportUserCommand, BackfillCommand, RestoreBackupCommand, ToggleCommandCommand, RestoreCvCommand, TrashCommand, LinkCvCommand, handle_cv_rescore_component, handle_linkcv_component,
     GenerateCoverLetterCommand, GenerateMarketAnalysisCommand, GenerateResumeCommand,
-    GetCvCommand, HelpCommand, ListCvsCommand, ListMyCvsCommand, MyStatsCommand,
-    SendCvCommand, StatusCommand, SynthesizeOfferCommand, UpdateStatusCommand,
-    get_status_buttons, rebuild_tracking_embed_from_status,
+    GetCvCommand, GetCvUserMenuCommand, HelpCommand, ImportApplicationsCommand, InterviewsCommand, ListCvsCommand, ListMyCvsCommand, MyCommandsCommand, MyStatsCommand,
+    SendCvCommand, SetInterviewCommand, StatusCommand, SynthesizeOfferCommand, UpdateStatusCommand,
+    PendingAnalysesKey, PendingApplyJobsKey, handle_applyjob_cost_component,
+    handle_applyjob_preview_component, handle_retry_analysis_component,
+    interrupted_pipeline_message,
+    ApplyJobFormModal, ModalRegistry, OutcomeNotesModal, apply_status_update, build_outcome_notes_modal,
+    is_terminal_status,
+    PurgeMeCommand, handle_purgeme_component,
     // Reminder commands
-    SetReminderCommand, ListRemindersCommand, ClearReminderCommand,
-    CreateReminderCommand, DeleteReminderCommand,
+    SetReminderCommand, ListRemindersCommand, ClearReminderCommand, CalendarCommand,
+    CreateReminderCommand, DeleteReminderCommand, RemindMeCommand, handle_quick_reminder_component,
+    // Settings
+    SetTargetCommand, SettingsCommand, apply_settings_component, build_settings_components, build_settings_embed,
 };
+use config::Config;
 use db::Database;
 use services::ClaudeClient;
-use serenity::all::{ChannelId, GatewayIntents, GuildId, Interaction, UserId};
+use services::backup;
+use services::triggers::{self, MessageTrigger};
+use serenity::all::{
+    ActivityData, ChannelId, ConnectionStage, CreateMessage, GuildId, Interaction, Message, ResumedEvent,
+    ShardManager, ShardStageUpdateEvent, UserId,
+};
 use serenity::async_trait;
 use serenity::model::gateway::Ready;
 use serenity::prelude::*;
-use std::env;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tracing::{error, info, warn};
 
 /// Clé pour stocker le registre de commandes dans le TypeMap de Serenity
-struct CommandRegistryKey;
+pub struct CommandRegistryKey;
 
 impl TypeMapKey for CommandRegistryKey {
     type Value = Arc<CommandRegistry>;
 }
 
+/// Clé pour stocker le registre des modals dans le TypeMap de Serenity
+pub struct ModalRegistryKey;
+
+impl TypeMapKey for ModalRegistryKey {
+    type Value = Arc<ModalRegistry>;
+}
+
 /// Clé pour stocker le client Claude dans le TypeMap de Serenity
 pub struct ClaudeClientKey;
 
@@ -37,12 +59,62 @@ impl TypeMapKey for ClaudeClientKey {
     type Value = Arc<ClaudeClient>;
 }
 
+/// État de la connexion Gateway, partagé avec les tâches de fond
+#[derive(Default)]
+pub struct ConnectionState {
+    connected: AtomicBool,
+}
+
+impl ConnectionState {
+    fn set_connected(&self, connected: bool) {
+        let was_connected = self.connected.swap(connected, Ordering::SeqCst);
+        if was_connected != connected {
+            if connected {
+                info!("🔌 Gateway connected");
+            } else {
+                warn!("🔌 Gateway disconnected, background sends are paused");
+            }
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+}
+
+/// Clé pour stocker l'état de connexion dans le TypeMap de Serenity
+struct ConnectionStateKey;
+
+impl TypeMapKey for ConnectionStateKey {
+    type Value = Arc<ConnectionState>;
+}
+
+/// Met à jour l'état de connexion stocké dans le TypeMap
+async fn set_connection_state(ctx: &Context, connected: bool) {
+    let state = ctx.data.read().await.get::<ConnectionStateKey>().cloned();
+    if let Some(state) = state {
+        state.set_connected(connected);
+    }
+}
+
 struct Handler;
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("✅ {} is now online!", ready.user.name);
+        set_connection_state(&ctx, true).await;
+
+        // Activité affichée dès la connexion, rafraîchie ensuite par presence_task
+        let services = {
+            let data = ctx.data.read().await;
+            data.get::<Database>().cloned().zip(data.get::<Config>().cloned())
+        };
+        if let Some((db, config)) = services {
+            if let Some(activity) = current_activity(&db, &config).await {
+                ctx.set_activity(Some(activity));
+            }
+        }
 
         // Récupérer le registre depuis le TypeMap
         let registry = {
@@ -56,10 +128,12 @@ impl EventHandler for Handler {
         let commands = registry.build_commands();
 
         // Enregistrer les commandes (guild pour dev, global pour prod)
-        let guild_id = env::var("GUILD_ID")
-            .ok()
-            .and_then(|id| id.parse::<u64>().ok())
-            .map(GuildId::new);
+        let guild_id = {
+            let data = ctx.data.read().await;
+            data.get::<Config>()
+                .and_then(|config| config.guild_id)
+                .map(GuildId::new)
+        };
 
         if let Some(guild) = guild_id {
             match guild.set_commands(&ctx.http, commands).await {
@@ -78,6 +152,14 @@ impl EventHandler for Handler {
         }
     }
 
+    async fn resume(&self, ctx: Context, _: ResumedEvent) {
+        set_connection_state(&ctx, true).await;
+    }
+
+    async fn shard_stage_update(&self, ctx: Context, event: ShardStageUpdateEvent) {
+        set_connection_state(&ctx, event.new == ConnectionStage::Connected).await;
+    }
+
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         match interaction {
             Interaction::Command(cmd) => {
@@ -122,9 +204,72 @@ impl EventHandler for Handler {
                         .await;
                 }
             }
+            Interaction::Modal(modal) => {
+                let registry = {
+                    let data = ctx.data.read().await;
+                    data.get::<ModalRegistryKey>()
+                        .expect("ModalRegistry not found")
+                        .clone()
+                };
+
+                // Dispatcher le modal selon le préfixe de son custom_id
+                if let Err(e) = registry.dispatch(&ctx, &modal).await {
+                    error!("Modal interaction error: {}", e);
+                    let _ = modal
+                        .create_response(
+                            &ctx.http,
+                            serenity::all::CreateInteractionResponse::Message(
+                                serenity::all::CreateInteractionResponseMessage::new()
+                                    .content(format!("❌ Erreur: {}", e))
+                                    .ephemeral(true),
+                            ),
+                        )
+                        .await;
+                }
+            }
             _ => {}
         }
     }
+
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.author.bot {
+            return;
+        }
+
+        let config = {
+            let data = ctx.data.read().await;
+            data.get::<Config>().cloned()
+        };
+        let Some(config) = config else { return };
+        if !config.message_triggers_enabled {
+            return;
+        }
+        if let Some(watch_channel) = config.watch_channel_id {
+            if msg.channel_id.get() != watch_channel {
+                return;
+            }
+        }
+
+        let attachment_names: Vec<&str> = msg.attachments.iter().map(|a| a.filename.as_str()).collect();
+        let hint = match triggers::detect_trigger(&msg.content, &attachment_names) {
+            Some(MessageTrigger::JobPosting) => {
+                "📋 Ce message ressemble à une offre d'emploi. Clic droit → Applications → **Analyze job posting** (ou `/applyjob`) pour obtenir la synthèse, le matching et un CV adapté."
+            }
+            Some(MessageTrigger::CvFile) => {
+                "📄 Un CV ? Utilisez `/sendcv` pour l'enregistrer comme CV de base, puis `/applyjob` sur vos offres."
+            }
+            None => return,
+        };
+
+        info!("Message trigger in channel {} from user {}", msg.channel_id, msg.author.id);
+        if let Err(e) = msg
+            .channel_id
+            .send_message(&ctx.http, CreateMessage::new().content(hint).reference_message(&msg))
+            .await
+        {
+            warn!("Failed to reply to message trigger: {}", e);
+        }
+    }
 }
 
 /// Gère les interactions avec les composants (boutons)
@@ -172,169 +317,547 @@ async fn handle_component_interaction(
         return Ok(());
     }
 
+    // Hub /settings : menus déroulants et boutons de préférences
+    if custom_id.starts_with("settings_") {
+        let db = {
+            let data = ctx.data.read().await;
+            data.get::<Database>()
+                .ok_or("Database not found")?
+                .clone()
+        };
+        let user_id = component.user.id.get() as i64;
+
+        let value = match &component.data.kind {
+            serenity::all::ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
+            _ => None,
+        };
+
+        db.upsert_user(user_id, &component.user.name).await?;
+        apply_settings_component(&db, user_id, custom_id, value, component.channel_id.get() as i64).await?;
+
+        let settings = db.get_settings(user_id).await?;
+        component
+            .create_response(
+                &ctx.http,
+                serenity::all::CreateInteractionResponse::UpdateMessage(
+                    serenity::all::CreateInteractionResponseMessage::new()
+                        .embed(build_settings_embed(&settings))
+                        .components(build_settings_components(&settings)),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // /linkcv : relance du matching avec le CV associé
+    if custom_id.starts_with("linkcv_rematch_") {
+        handle_linkcv_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // /sendcv : re-scoring des candidatures récentes avec le nouveau CV
+    if custom_id.starts_with("cvrescore_") {
+        handle_cv_rescore_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // Confirmation /purgeme
+    if custom_id == "purgeme_confirm" || custom_id == "purgeme_cancel" {
+        handle_purgeme_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // Rappel de relance depuis l'embed de suivi : quickreminder_{id}
+    if custom_id.starts_with("quickreminder_") {
+        handle_quick_reminder_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // Offre très longue : applyjobcost_run_{id} / applyjobcost_cancel_{id}
+    // CV illisible : applyjobnocv_retry_{id} / applyjobnocv_run_{id} / applyjobnocv_cancel_{id}
+    if custom_id.starts_with("applyjobcost_") || custom_id.starts_with("applyjobnocv_") {
+        handle_applyjob_cost_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // Aperçu /applyjob : applyjob_proceed_{id} / applyjob_cancel_{id}
+    if custom_id.starts_with("applyjob_") {
+        handle_applyjob_preview_component(ctx, component).await?;
+        return Ok(());
+    }
+
+    // Analyse /applyjob échouée : retryanalysis_{id}
+    if custom_id.starts_with("retryanalysis_") {
+        handle_retry_analysis_component(ctx, component).await?;
+        return Ok(());
+    }
+
     // Format: status_{application_id}_{new_status}
     if custom_id.starts_with("status_") {
         let parts: Vec<&str> = custom_id.split('_').collect();
         if parts.len() >= 3 {
             let application_id: i64 = parts[1].parse()?;
             let new_status = parts[2];
-            let user_id = component.user.id.get() as i64;
 
-            info!(
-                "Status update: user {} changing application {} to {}",
-                user_id, application_id, new_status
-            );
+            // Acceptée / refusée : demander d'abord les notes de clôture via un modal
+            if is_terminal_status(new_status) {
+                component
+                    .create_response(
+                        &ctx.http,
+                        serenity::all::CreateInteractionResponse::Modal(
+                            build_outcome_notes_modal(application_id, new_status),
+                        ),
+                    )
+                    .await?;
+                return Ok(());
+            }
 
-            // Récupérer la DB
-            let db = {
-                let data = ctx.data.read().await;
-                data.get::<Database>()
-                    .ok_or("Database not found")?
-                    .clone()
-            };
+            let response = apply_status_update(ctx, component.user.id, application_id, new_status, None).await?;
+            component
+                .create_response(&ctx.http, serenity::all::CreateInteractionResponse::UpdateMessage(response))
+                .await?;
+        }
+    }
 
-            // Mettre à jour le statut en DB
-            let updated = db.update_application_status(application_id, user_id, new_status, None).await?;
+    Ok(())
+}
 
-            if !updated {
-                return Err("Cette candidature ne vous appartient pas ou n'existe pas.".into());
-            }
+/// Tache de fond d'archivage des candidatures terminées (option auto_archive_days de /settings)
+async fn archive_sweep_task(db: Database) {
+    info!("Starting archive sweep background task");
 
-            // Récupérer l'application mise à jour pour reconstruire l'embed
-            let app = db
-                .get_application(application_id).await?
-                .ok_or("Application not found after update")?;
+    loop {
+        match db.archive_closed_applications().await {
+            Ok(0) => {}
+            Ok(count) => info!("Auto-archived {} closed application(s)", count),
+            Err(e) => error!("Failed to auto-archive closed applications: {}", e),
+        }
 
-            // Reconstruire l'embed avec le nouveau statut
-            let thread_id = app.thread_id.map(|t| t as u64);
-            let embed = rebuild_tracking_embed_from_status(
-                app.company.as_deref().unwrap_or("N/A"),
+        // Check every hour
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+    }
+}
+
+/// Tache de fond de relance des candidatures restées au stade entretien sans activité
+/// (option « Relance des entretiens » de /settings) : un message privé par période d'inactivité
+async fn interview_nudge_task(
+    http: Arc<serenity::http::Http>,
+    db: Database,
+    config: Arc<Config>,
+    connection: Arc<ConnectionState>,
+) {
+    let after_days = config.interview_nudge_days;
+    info!("Starting interview nudge background task ({} days)", after_days);
+
+    loop {
+        // Check every hour
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        if !connection.is_connected() {
+            continue;
+        }
+
+        let apps = match db.get_stale_interview_applications(after_days).await {
+            Ok(apps) => apps,
+            Err(e) => {
+                error!("Failed to list stale interview applications: {}", e);
+                continue;
+            }
+        };
+        for app in apps {
+            // Enregistrer la relance AVANT l'envoi pour éviter le double envoi
+            if let Err(e) = db.mark_interview_nudged(app.id).await {
+                error!("Failed to record interview nudge (aborting send): {}", e);
+                continue;
+            }
+            info!("Sending interview nudge for application {} to user {}", app.id, app.user_id);
+
+            let message = format!(
+                "**Entretien sans nouvelles**\n\n\
+                Candidature **#{}** - {} chez {}\n\
+                Au stade entretien sans activité depuis au moins {} jours.\n\n\
+                Pensez à relancer le recruteur (remerciements, demande de retour) puis à mettre à jour \
+                le statut avec les boutons de suivi de la candidature.\n\
+                _Désactivez ces relances avec `/settings`._",
+                app.id,
                 app.job_title.as_deref().unwrap_or("N/A"),
-                app.location.as_deref().unwrap_or("N/A"),
-                app.match_score.unwrap_or(0) as u32,
-                app.generated_cv_path.is_some(),
-                thread_id,
-                application_id,
-                new_status,
+                app.company.as_deref().unwrap_or("N/A"),
+                after_days
             );
+            let user_id = UserId::new(app.user_id as u64);
+            match user_id.create_dm_channel(&http).await {
+                Ok(dm_channel) => {
+                    if let Err(e) = dm_channel.say(&http, &message).await {
+                        error!("Failed to send interview nudge DM (already recorded): {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to create DM channel for user {}: {}", app.user_id, e),
+            }
+        }
+    }
+}
 
-            // Reconstruire les boutons
-            let buttons = get_status_buttons(application_id, new_status);
+/// Balayage unique au démarrage : les analyses /applyjob interrompues par un redémarrage
+/// (interaction différée perdue) sont signalées en message privé avec le bouton « Réessayer »
+async fn interrupted_pipeline_sweep(http: Arc<serenity::http::Http>, db: Database) {
+    let apps = match db.list_interrupted_applications().await {
+        Ok(apps) => apps,
+        Err(e) => {
+            error!("Failed to list interrupted applications: {}", e);
+            return;
+        }
+    };
+    if !apps.is_empty() {
+        info!("Found {} application(s) interrupted by a restart", apps.len());
+    }
 
-            // Mettre à jour le message avec le nouvel embed et les nouveaux boutons
-            component
-                .create_response(
-                    &ctx.http,
-                    serenity::all::CreateInteractionResponse::UpdateMessage(
-                        serenity::all::CreateInteractionResponseMessage::new()
-                            .embed(embed)
-                            .components(buttons),
-                    ),
-                )
-                .await?;
+    for app in apps {
+        // Marquer AVANT l'envoi pour ne pas avertir deux fois après un nouveau redémarrage
+        if let Err(e) = db.set_application_pipeline_stage(app.id, Some(db::PIPELINE_STALLED)).await {
+            error!("Failed to mark application {} as stalled (aborting notice): {}", app.id, e);
+            continue;
+        }
+        let user_id = UserId::new(app.user_id as u64);
+        match user_id.create_dm_channel(&http).await {
+            Ok(dm_channel) => {
+                if let Err(e) = dm_channel.send_message(&http, interrupted_pipeline_message(&app)).await {
+                    error!("Failed to send interrupted analysis notice for application {}: {}", app.id, e);
+                }
+            }
+            Err(e) => error!("Failed to create DM channel for user {}: {}", app.user_id, e),
+        }
+    }
+}
 
-            info!(
-                "Successfully updated application {} to status {}",
-                application_id, new_status
-            );
+/// Activité « Regarde … » avec le nombre de candidatures suivies (None si BOT_ACTIVITY=none)
+async fn current_activity(db: &Database, config: &Config) -> Option<ActivityData> {
+    config.bot_activity.as_ref()?;
+    let applications = db.count_applications().await.unwrap_or_else(|e| {
+        warn!("Failed to count applications for the bot activity: {}", e);
+        0
+    });
+    config.activity_text(applications).map(ActivityData::watching)
+}
+
+/// Tache de fond de rafraîchissement de l'activité du bot (statistiques en direct)
+async fn presence_task(shard_manager: Arc<ShardManager>, db: Database, config: Arc<Config>) {
+    info!("Starting presence refresh background task");
+
+    loop {
+        // Check every 10 minutes
+        tokio::time::sleep(Duration::from_secs(600)).await;
+
+        let activity = current_activity(&db, &config).await;
+        for runner in shard_manager.runners.lock().await.values() {
+            runner.runner_tx.set_activity(activity.clone());
         }
     }
+}
 
-    Ok(())
+/// Tache de fond de sauvegarde de la base (BACKUP_INTERVAL_HOURS > 0)
+async fn backup_task(db: Database, config: Arc<Config>) {
+    let interval = Duration::from_secs(config.backup_interval_hours as u64 * 3600);
+    let dir = std::path::PathBuf::from(&config.backup_dir);
+    info!(
+        "Starting database backup task (every {}h, keeping {} in {:?})",
+        config.backup_interval_hours, config.backup_keep, dir
+    );
+
+    loop {
+        match backup::run_backup(&db, &dir, config.backup_keep as usize).await {
+            Ok(path) => info!("Database backed up to {:?}", path),
+            Err(e) => error!("Failed to back up database: {}", e),
+        }
+
+        tokio::time::sleep(interval).await;
+    }
 }
 
 /// Tache de fond pour verifier et envoyer les rappels automatiques
-async fn reminder_check_task(http: Arc<serenity::http::Http>, db: Database) {
+async fn reminder_check_task(
+    http: Arc<serenity::http::Http>,
+    db: Database,
+    config: Arc<Config>,
+    connection: Arc<ConnectionState>,
+) {
     info!("Starting reminder check background task");
 
+    // Relances des rappels restés sans suite (statut inchangé)
+    let escalation_days = config.reminder_escalation_days;
+    let max_escalations = config.reminder_max_escalations;
+    // Plafond d'envois par passage : un arriéré (bot hors ligne) est écoulé progressivement
+    let max_per_tick = config.reminder_max_per_tick as usize;
+    let dry_run = config.reminder_dry_run;
+    let max_retries = config.reminder_max_retries;
+    if dry_run {
+        warn!("REMINDER_DRY_RUN enabled: reminders are logged only (not sent, not marked sent)");
+    }
+
     loop {
         // Check every 5 minutes
         tokio::time::sleep(Duration::from_secs(300)).await;
 
+        // Gateway coupée : différer les envois (les rappels restent en attente en DB)
+        if !connection.is_connected() {
+            info!("Gateway disconnected, deferring reminder checks");
+            while !connection.is_connected() {
+                tokio::time::sleep(Duration::from_secs(15)).await;
+            }
+            info!("Gateway back online, resuming reminder checks");
+        }
+
+        let pending_apps = db.get_pending_application_reminders().await.unwrap_or_else(|e| {
+            error!("Failed to get pending application reminders: {}", e);
+            Vec::new()
+        });
+        let pending_escalations = db
+            .get_unacknowledged_application_reminders(escalation_days, max_escalations)
+            .await
+            .unwrap_or_else(|e| {
+                error!("Failed to get unacknowledged application reminders: {}", e);
+                Vec::new()
+            });
+        let pending_reminders = db.get_pending_reminders().await.unwrap_or_else(|e| {
+            error!("Failed to get pending reminders: {}", e);
+            Vec::new()
+        });
+
+        let total = pending_apps.len() + pending_escalations.len() + pending_reminders.len();
+        if total > 0 {
+            info!(
+                "Reminder check: {} pending ({} application, {} escalation, {} standalone), cap {} per tick",
+                total, pending_apps.len(), pending_escalations.len(), pending_reminders.len(), max_per_tick
+            );
+        }
+        if total > max_per_tick {
+            // Les rappels non traités restent is_sent = 0 et repassent au prochain tick
+            warn!("Reminder backlog: {} reminder(s) deferred to next ticks", total - max_per_tick);
+        }
+        let mut budget = max_per_tick;
+
         // Check application reminders
-        match db.get_pending_application_reminders().await {
-            Ok(apps) => {
-                for app in apps {
-                    info!("Sending reminder for application {} to user {}", app.id, app.user_id);
-
-                    // Try to DM the user
-                    let user_id = UserId::new(app.user_id as u64);
-                    match user_id.create_dm_channel(&http).await {
-                        Ok(dm_channel) => {
-                            let message = format!(
-                                "**Rappel de suivi de candidature**\n\n\
-                                Candidature **#{}** - {} chez {}\n\
-                                Statut actuel: `{}`\n\n\
-                                N'oubliez pas de faire le suivi de cette candidature!\n\
-                                Utilisez `/status` pour voir vos candidatures.",
-                                app.id,
-                                app.job_title.as_deref().unwrap_or("N/A"),
-                                app.company.as_deref().unwrap_or("N/A"),
-                                app.status
-                            );
-
-                            // Marquer comme envoyé AVANT l'envoi pour éviter le double envoi
-                            // en cas de redémarrage du bot entre l'envoi et le marquage
-                            if let Err(e) = db.mark_application_reminder_sent(app.id).await {
-                                error!("Failed to mark reminder as sent (aborting send): {}", e);
-                            } else if let Err(e) = dm_channel.say(&http, &message).await {
-                                error!("Failed to send reminder DM (already marked sent): {}", e);
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to create DM channel for user {}: {}", app.user_id, e);
+        let apps: Vec<_> = pending_apps.into_iter().take(budget).collect();
+        budget -= apps.len();
+        for app in apps {
+            if dry_run {
+                info!(
+                    "[dry run] Would send reminder for application {} ({} chez {}, status {}) to user {}",
+                    app.id,
+                    app.job_title.as_deref().unwrap_or("N/A"),
+                    app.company.as_deref().unwrap_or("N/A"),
+                    app.status,
+                    app.user_id
+                );
+                continue;
+            }
+            info!("Sending reminder for application {} to user {}", app.id, app.user_id);
+
+            // Try to DM the user
+            let user_id = UserId::new(app.user_id as u64);
+            match user_id.create_dm_channel(&http).await {
+                Ok(dm_channel) => {
+                    let message = format!(
+                        "**Rappel de suivi de candidature**\n\n\
+                        Candidature **#{}** - {} chez {}\n\
+                        Statut actuel: `{}`\n\n\
+                        {}\
+                        Utilisez `/status` pour voir vos candidatures.",
+                        app.id,
+                        app.job_title.as_deref().unwrap_or("N/A"),
+                        app.company.as_deref().unwrap_or("N/A"),
+                        app.status,
+                        match app.reminder_note.as_deref() {
+                            Some(note) => format!("📝 {}\n", note),
+                            None => "N'oubliez pas de faire le suivi de cette candidature!\n".to_string(),
                         }
+                    );
+
+                    // Marquer comme envoyé AVANT l'envoi pour éviter le double envoi
+                    // en cas de redémarrage du bot entre l'envoi et le marquage
+                    if let Err(e) = db.mark_application_reminder_sent(app.id).await {
+                        error!("Failed to mark reminder as sent (aborting send): {}", e);
+                    } else if let Err(e) = dm_channel.say(&http, &message).await {
+                        error!("Failed to send reminder DM for application {}: {}", app.id, e);
+                        record_application_reminder_failure(&http, &db, &app, max_retries).await;
                     }
                 }
+                Err(e) => {
+                    error!("Failed to create DM channel for user {}: {}", app.user_id, e);
+                    record_application_reminder_failure(&http, &db, &app, max_retries).await;
+                }
             }
-            Err(e) => {
-                error!("Failed to get pending application reminders: {}", e);
+        }
+
+        // Escalate unacknowledged application reminders
+        let apps: Vec<_> = pending_escalations.into_iter().take(budget).collect();
+        budget -= apps.len();
+        for app in apps {
+            if dry_run {
+                info!(
+                    "[dry run] Would send escalated reminder for application {} (status {}) to user {}",
+                    app.id, app.status, app.user_id
+                );
+                continue;
+            }
+            // Enregistrer la relance AVANT l'envoi pour éviter le double envoi
+            let escalation = match db.record_reminder_escalation(app.id).await {
+                Ok(n) => n,
+                Err(e) => {
+                    error!("Failed to record reminder escalation (aborting send): {}", e);
+                    continue;
+                }
+            };
+            info!(
+                "Sending escalated reminder #{} for application {} to user {}",
+                escalation, app.id, app.user_id
+            );
+
+            let last_notice = if escalation >= max_escalations {
+                "\n\n_Dernière relance pour cette candidature._"
+            } else {
+                ""
+            };
+            let message = format!(
+                "**Relance n°{} — candidature sans nouvelles**\n\n\
+                Candidature **#{}** - {} chez {}\n\
+                Toujours au statut `{}` depuis le dernier rappel.\n\n\
+                Pensez à relancer le recruteur ou à mettre à jour le statut avec `/updatestatus`.{}",
+                escalation,
+                app.id,
+                app.job_title.as_deref().unwrap_or("N/A"),
+                app.company.as_deref().unwrap_or("N/A"),
+                app.status,
+                last_notice
+            );
+
+            let user_id = UserId::new(app.user_id as u64);
+            match user_id.create_dm_channel(&http).await {
+                Ok(dm_channel) => {
+                    if let Err(e) = dm_channel.say(&http, &message).await {
+                        error!("Failed to send escalated reminder DM (already recorded): {}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to create DM channel for user {}: {}", app.user_id, e);
+                }
             }
         }
 
         // Check standalone reminders
-        match db.get_pending_reminders().await {
-            Ok(reminders) => {
-                for reminder in reminders {
-                    info!("Sending standalone reminder {} to user {}", reminder.id, reminder.user_id);
+        for reminder in pending_reminders.into_iter().take(budget) {
+            if dry_run {
+                info!(
+                    "[dry run] Would send standalone reminder {} to user {} in channel {} (due {}): {}",
+                    reminder.id, reminder.user_id, reminder.channel_id, reminder.reminder_date, reminder.message
+                );
+                continue;
+            }
+            info!("Sending standalone reminder {} to user {}", reminder.id, reminder.user_id);
 
-                    // Send to the specified channel
-                    let channel_id = ChannelId::new(reminder.channel_id as u64);
-                    let user_mention = format!("<@{}>", reminder.user_id);
+            // Send to the specified channel
+            let channel_id = ChannelId::new(reminder.channel_id as u64);
+            let user_mention = format!("<@{}>", reminder.user_id);
 
-                    let message = format!(
-                        "{} **Rappel**\n\n{}",
-                        user_mention,
-                        reminder.message
-                    );
+            let message = format!(
+                "{} **Rappel**\n\n{}",
+                user_mention,
+                reminder.message
+            );
 
-                    // Marquer comme envoyé AVANT l'envoi pour éviter le double envoi
-                    if let Err(e) = db.mark_reminder_sent(reminder.id).await {
-                        error!("Failed to mark standalone reminder as sent (aborting send): {}", e);
-                        continue;
-                    }
+            // Marquer comme envoyé AVANT l'envoi pour éviter le double envoi
+            if let Err(e) = db.mark_reminder_sent(reminder.id).await {
+                error!("Failed to mark standalone reminder as sent (aborting send): {}", e);
+                continue;
+            }
 
-                    if let Err(e) = channel_id.say(&http, &message).await {
-                        error!("Failed to send reminder to channel (already marked sent): {}", e);
-                        // Try DM as fallback
-                        let user_id = UserId::new(reminder.user_id as u64);
-                        if let Ok(dm_channel) = user_id.create_dm_channel(&http).await {
-                            if let Err(e) = dm_channel.say(&http, &format!("**Rappel**\n\n{}", reminder.message)).await {
-                                error!("Failed to send reminder DM fallback: {}", e);
-                            }
-                        } else {
-                            error!("Failed to create DM channel for reminder fallback (user {})", reminder.user_id);
+            if let Err(e) = channel_id.say(&http, &message).await {
+                error!("Failed to send reminder to channel (already marked sent): {}", e);
+                // Try DM as fallback
+                let user_id = UserId::new(reminder.user_id as u64);
+                let delivered = match user_id.create_dm_channel(&http).await {
+                    Ok(dm_channel) => match dm_channel.say(&http, &format!("**Rappel**\n\n{}", reminder.message)).await {
+                        Ok(_) => true,
+                        Err(e) => {
+                            error!("Failed to send reminder DM fallback: {}", e);
+                            false
                         }
+                    },
+                    Err(_) => {
+                        error!("Failed to create DM channel for reminder fallback (user {})", reminder.user_id);
+                        false
+                    }
+                };
+                if !delivered {
+                    match db.record_reminder_failure(reminder.id, max_retries).await {
+                        Ok(true) => {
+                            warn!("Giving up on standalone reminder {} after {} failed sends", reminder.id, max_retries);
+                            notify_reminder_give_up(&http, &db, reminder.user_id, &reminder.message).await;
+                        }
+                        Ok(false) => {}
+                        Err(e) => error!("Failed to record standalone reminder failure: {}", e),
                     }
                 }
             }
-            Err(e) => {
-                error!("Failed to get pending reminders: {}", e);
-            }
         }
     }
 }
 
+/// Enregistre l'échec d'envoi d'un rappel de candidature (nouvel essai au prochain passage)
+/// et prévient l'utilisateur si le rappel est abandonné
+async fn record_application_reminder_failure(
+    http: &Arc<serenity::http::Http>,
+    db: &Database,
+    app: &db::JobApplication,
+    max_retries: i64,
+) {
+    match db.record_application_reminder_failure(app.id, max_retries).await {
+        Ok(true) => {
+            warn!("Giving up on reminder for application {} after {} failed sends", app.id, max_retries);
+            let text = format!(
+                "Candidature **#{}** - {} chez {}",
+                app.id,
+                app.job_title.as_deref().unwrap_or("N/A"),
+                app.company.as_deref().unwrap_or("N/A")
+            );
+            notify_reminder_give_up(http, db, app.user_id, &text).await;
+        }
+        Ok(false) => {}
+        Err(e) => error!("Failed to record reminder failure for application {}: {}", app.id, e),
+    }
+}
+
+/// Prévient l'utilisateur qu'un rappel n'a pas pu être remis, dans son canal de
+/// notification (/settings) s'il en a défini un : sans canal, le DM a déjà échoué
+async fn notify_reminder_give_up(http: &Arc<serenity::http::Http>, db: &Database, user_id: i64, text: &str) {
+    let channel_id = match db.get_settings(user_id).await {
+        Ok(settings) => settings.notification_channel_id,
+        Err(e) => {
+            error!("Failed to load settings for user {}: {}", user_id, e);
+            None
+        }
+    };
+    let Some(channel_id) = channel_id else {
+        warn!("No notification channel for user {}, reminder failure not reported", user_id);
+        return;
+    };
+    let message = format!(
+        "<@{}> **Rappel non remis**\n\n{}\n\n\
+        Ce rappel n'a pas pu vous être envoyé en message privé (DMs fermés ?) et a été abandonné.",
+        user_id, text
+    );
+    if let Err(e) = ChannelId::new(channel_id as u64).say(http, &message).await {
+        error!("Failed to notify user {} of abandoned reminder: {}", user_id, e);
+    }
+}
+
+/// Handlers des modals (formulaires Discord), par préfixe de custom_id
+fn build_modal_registry() -> ModalRegistry {
+    let mut registry = ModalRegistry::new();
+    registry
+        .register(ApplyJobFormModal)
+        .register(OutcomeNotesModal);
+    registry
+}
+
 /// Initialise le registre avec toutes les commandes
 fn build_registry() -> CommandRegistry {
     let mut registry = CommandRegistry::new();
@@ -344,21 +867,39 @@ fn build_registry() -> CommandRegistry {
     registry
         .register(SendCvCommand::new())
         .register(DeleteCvCommand::new())
-        .register(ListMyCvsCommand::new());
+        .register(TrashCommand::new())
+        .register(RestoreCvCommand::new())
+        .register(ListMyCvsCommand::new())
+        .register(LinkCvCommand::new())
+        .register(CvProfileCommand::new());
 
     // Job Application Pipeline (main workflow)
     registry
         .register(ApplyJobCommand::new())
+        .register(ApplyJobFormCommand::new())
+        .register(CloneApplicationCommand::new())
         .register(StatusCommand::new())
         .register(UpdateStatusCommand::new())
         .register(MyStatsCommand::new())
-        .register(ApplicationHistoryCommand::new());
+        .register(MyCommandsCommand::new())
+        .register(BenefitsCommand::new())
+        .register(ApplicationHistoryCommand::new())
+        .register(InterviewsCommand::new())
+        .register(SetInterviewCommand::new())
+        .register(ImportApplicationsCommand::new())
+        .register(CompareCvCommand::new())
+        .register(AttachCommand::new())
+        .register(AttachmentsCommand::new());
 
     // === ADMIN COMMANDS ===
     registry
         .register(ListCvsCommand::new())
         .register(GetCvCommand::new())
-        .register(ClearAllCvsCommand::new());
+        .register(ClearAllCvsCommand::new())
+        .register(ExportUserCommand::new())
+        .register(RestoreBackupCommand::new())
+        .register(BackfillCommand::new())
+        .register(ToggleCommandCommand::new());
 
     // === LEGACY/STANDALONE AI COMMANDS ===
     // (kept for direct access, but /applyjob combines them)
@@ -374,12 +915,25 @@ fn build_registry() -> CommandRegistry {
         .register(ListRemindersCommand::new())
         .register(ClearReminderCommand::new())
         .register(CreateReminderCommand::new())
-        .register(DeleteReminderCommand::new());
+        .register(RemindMeCommand::new())
+        .register(DeleteReminderCommand::new())
+        .register(CalendarCommand::new());
+
+    // === SETTINGS & PRIVACY ===
+    registry
+        .register(SettingsCommand::new())
+        .register(SetTargetCommand::new())
+        .register(PurgeMeCommand::new());
 
     // Help command (created last to include all commands)
     let help_info = registry.help_info();
     registry.register(HelpCommand::new(help_info));
 
+    // === CONTEXT MENU (clic droit → Applications, hors /help) ===
+    registry
+        .register(AnalyzeMessageCommand::new())
+        .register(GetCvUserMenuCommand::new());
+
     registry
 }
 
@@ -391,11 +945,23 @@ async fn main() {
     // Charger les variables d'environnement
     dotenv::dotenv().ok();
 
+    // Valider la configuration avant toute connexion (toutes les erreurs d'un coup)
+    let config = match Config::from_env() {
+        Ok(config) => Arc::new(config),
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Nettoyer les écritures interrompues par un arrêt brutal
+    services::storage::remove_stale_temp_files(std::path::Path::new("data"));
+
     // Initialiser la base de données
     let database = Database::new().await.expect("Failed to initialize database");
 
     // Initialiser le client Claude (HTTP)
-    let claude_client = Arc::new(ClaudeClient::from_env());
+    let claude_client = Arc::new(ClaudeClient::new(&config.claude_api_url, config.claude_timeouts(), config.log_full_bodies));
 
     // Vérifier la connexion au serveur Claude
     match claude_client.health_check().await {
@@ -404,26 +970,32 @@ async fn main() {
         Err(e) => warn!("⚠️ Claude connection failed (will retry on demand): {}", e),
     }
 
-    let token = env::var("DISCORD_BOT_TOKEN").expect("Expected DISCORD_BOT_TOKEN in .env");
-
     // Construire le registre de commandes
     let registry = Arc::new(build_registry());
 
     // Créer le client Discord
-    let mut client = Client::builder(&token, GatewayIntents::empty())
+    let mut client = Client::builder(&config.discord_token, config.gateway_intents())
         .event_handler(Handler)
         .await
         .expect("Failed to create client");
 
     // Clone for background task
     let db_for_task = database.clone();
+    let config_for_task = config.clone();
+    let connection = Arc::new(ConnectionState::default());
+    let connection_for_task = connection.clone();
 
     // Injecter les services dans le TypeMap
     {
         let mut data = client.data.write().await;
         data.insert::<CommandRegistryKey>(registry);
+        data.insert::<ModalRegistryKey>(Arc::new(build_modal_registry()));
         data.insert::<Database>(database);
         data.insert::<ClaudeClientKey>(claude_client);
+        data.insert::<Config>(config);
+        data.insert::<ConnectionStateKey>(connection);
+        data.insert::<PendingAnalysesKey>(Default::default());
+        data.insert::<PendingApplyJobsKey>(Default::default());
     }
 
     info!("🚀 Starting bot...");
@@ -431,11 +1003,33 @@ async fn main() {
     // Get HTTP client for background task
     let http = client.http.clone();
 
+    // Spawn archive sweep background task
+    tokio::spawn(archive_sweep_task(db_for_task.clone()));
+
+    // Spawn database backup background task (disabled by default)
+    if config_for_task.backup_interval_hours > 0 {
+        tokio::spawn(backup_task(db_for_task.clone(), config_for_task.clone()));
+    }
+
+    // Spawn presence refresh background task (disabled with BOT_ACTIVITY=none)
+    if config_for_task.bot_activity.is_some() {
+        tokio::spawn(presence_task(client.shard_manager.clone(), db_for_task.clone(), config_for_task.clone()));
+    }
+
+    // Spawn interview nudge background task (opt-in per user)
+    tokio::spawn(interview_nudge_task(
+        http.clone(),
+        db_for_task.clone(),
+        config_for_task.clone(),
+        connection_for_task.clone(),
+    ));
+
     // Spawn reminder check background task
     tokio::spawn(async move {
         // Wait a bit for the bot to fully connect
         tokio::time::sleep(Duration::from_secs(10)).await;
-        reminder_check_task(http, db_for_task).await;
+        interrupted_pipeline_sweep(http.clone(), db_for_task.clone()).await;
+        reminder_check_task(http, db_for_task, config_for_task, connection_for_task).await;
     });
 
     if let Err(e) = client.start().await {
@@ -0,0 +1,179 @@
+// Anonymisation du CV avant envoi au serveur Claude (option `anonymize` de /applyjob) :
+// nom, emails et téléphones sont remplacés par des marqueurs, puis restaurés localement
+// dans le CV généré.
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+/// Marqueur du nom du candidat
+const NAME_PLACEHOLDER: &str = "[NOM]";
+
+/// Texte anonymisé et correspondances marqueur → valeur d'origine
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    pub text: String,
+    replacements: Vec<(String, String)>,
+}
+
+impl Redaction {
+    /// Texte transmis tel quel (anonymisation désactivée)
+    pub fn none(text: String) -> Self {
+        Self { text, replacements: Vec::new() }
+    }
+
+    /// Nombre de valeurs distinctes masquées
+    pub fn count(&self) -> usize {
+        self.replacements.len()
+    }
+
+    /// Nom du candidat masqué (introuvable dans le texte sinon : transmis en clair)
+    pub fn masks_name(&self) -> bool {
+        self.replacements.iter().any(|(placeholder, _)| placeholder == NAME_PLACEHOLDER)
+    }
+
+    /// Réinsère les valeurs d'origine dans un texte produit à partir du texte anonymisé
+    pub fn restore(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |acc, (placeholder, original)| acc.replace(placeholder, original))
+    }
+
+    fn placeholder_for(&mut self, kind: &str, original: &str) -> String {
+        if let Some((placeholder, _)) = self.replacements.iter().find(|(_, o)| o == original) {
+            return placeholder.clone();
+        }
+        let index = self.replacements.iter().filter(|(p, _)| p.starts_with(&format!("[{}_", kind))).count() + 1;
+        let placeholder = format!("[{}_{}]", kind, index);
+        self.replacements.push((placeholder.clone(), original.to_string()));
+        placeholder
+    }
+}
+
+/// `text` anonymisé si `enabled` (option `anonymize` enregistrée sur la candidature), sinon tel quel
+pub fn anonymize_if(enabled: bool, text: &str, name: Option<&str>) -> Redaction {
+    if enabled {
+        anonymize(text, name)
+    } else {
+        Redaction::none(text.to_string())
+    }
+}
+
+/// Masque le nom (`name`, tel quel ou « Nom Prénom » ; à défaut la première ligne du texte
+/// si elle ressemble à un nom), les emails et les numéros de téléphone
+pub fn anonymize(text: &str, name: Option<&str>) -> Redaction {
+    let mut redaction = Redaction::default();
+    let name = name.map(str::to_string).or_else(|| name_from_first_line(text));
+    let text = email_regex()
+        .replace_all(text, |caps: &Captures| redaction.placeholder_for("EMAIL", &caps[0]))
+        .into_owned();
+    let mut text = phone_regex()
+        .replace_all(&text, |caps: &Captures| redaction.placeholder_for("TÉLÉPHONE", &caps[0]))
+        .into_owned();
+
+    if let Some(name) = name.as_deref().map(str::trim).filter(|n| n.chars().count() >= 3) {
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let mut variants = vec![words.clone()];
+        if words.len() == 2 {
+            variants.push(vec![words[1], words[0]]);
+        }
+        let mut found = false;
+        for variant in &variants {
+            let escaped: Vec<String> = variant.iter().map(|w| regex::escape(w)).collect();
+            let Ok(pattern) = Regex::new(&format!(r"(?i)\b{}\b", escaped.join(r"\s+"))) else {
+                continue;
+            };
+            if pattern.is_match(&text) {
+                text = pattern.replace_all(&text, NAME_PLACEHOLDER).into_owned();
+                found = true;
+            }
+        }
+        if found {
+            redaction.replacements.push((NAME_PLACEHOLDER.to_string(), name.to_string()));
+        }
+    }
+
+    redaction.text = text;
+    redaction
+}
+
+/// Première ligne non vide du CV quand elle ressemble à un nom (« Jean Dupont », « DUPONT Jean ») :
+/// deux à quatre mots alphabétiques, hors intitulés comme « Curriculum Vitae »
+fn name_from_first_line(text: &str) -> Option<String> {
+    const NOT_A_NAME: &[&str] = &["curriculum", "vitae", "cv", "resume", "résumé", "profil", "profile"];
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let looks_like_name = (2..=4).contains(&words.len())
+        && line.chars().count() <= 40
+        && words.iter().all(|w| {
+            w.chars().next().is_some_and(char::is_uppercase)
+                && w.chars().all(|c| c.is_alphabetic() || c == '-' || c == '\'')
+                && !NOT_A_NAME.contains(&w.to_lowercase().as_str())
+        });
+    looks_like_name.then(|| words.join(" "))
+}
+
+fn email_regex() -> &'static Regex {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    EMAIL.get_or_init(|| {
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b")
+            .expect("Invalid email regex")
+    })
+}
+
+/// `+` ou `0` en tête, 9 à 15 chiffres, séparateurs isolés entre les chiffres
+/// (« 06 12 34 56 78 », « +33 (0)6 12 34 56 78 », « 06.12.34.56.78 »)
+fn phone_regex() -> &'static Regex {
+    static PHONE: OnceLock<Regex> = OnceLock::new();
+    PHONE.get_or_init(|| {
+        let separator = r"(?:[ .\-\u{a0}]\(?|\(|\)[ .\-\u{a0}]?)?";
+        Regex::new(&format!(r"(?:\+(?:{sep}\d){{9,15}}|\b0(?:{sep}\d){{8,14}})\b", sep = separator))
+            .expect("Invalid phone regex")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize_and_restore() {
+        let cv = "Jean Dupont\nDÉVELOPPEUR RUST\njean.dupont+cv@mail.example.fr | 06 12 34 56 78\n\
+                  Contact : +33 (0)6 12 34 56 78, jean.dupont+cv@mail.example.fr.\n\
+                  DUPONT Jean — Acme (2019 - 2023), 01.2019 - 06.2020, 3 ans";
+        let redaction = anonymize(cv, Some("Jean Dupont"));
+        assert_eq!(
+            redaction.text,
+            "[NOM]\nDÉVELOPPEUR RUST\n[EMAIL_1] | [TÉLÉPHONE_1]\n\
+             Contact : [TÉLÉPHONE_2], [EMAIL_1].\n\
+             [NOM] — Acme (2019 - 2023), 01.2019 - 06.2020, 3 ans"
+        );
+        assert_eq!(redaction.count(), 4);
+        assert!(redaction.masks_name());
+
+        let generated = "# [NOM]\n[EMAIL_1] · [TÉLÉPHONE_2]";
+        assert_eq!(
+            redaction.restore(generated),
+            "# Jean Dupont\njean.dupont+cv@mail.example.fr · +33 (0)6 12 34 56 78"
+        );
+    }
+
+    #[test]
+    fn test_anonymize_ignores_non_pii() {
+        let text = "Léa, Python@3.12, user@localhost, 0 à 2 ans, +15% de CA, réf. 0123-45, Jeanne Dupontel";
+        let redaction = anonymize(text, Some("Jean Dupont"));
+        assert_eq!(redaction.text, text);
+        assert_eq!(redaction.count(), 0);
+        assert_eq!(Redaction::none("x".to_string()).restore("[NOM]"), "[NOM]");
+    }
+
+    #[test]
+    fn test_anonymize_falls_back_to_first_line_name() {
+        let redaction = anonymize("\n  Jean  Dupont\nDéveloppeur Rust chez Acme\nJean Dupont, 3 ans", None);
+        assert_eq!(redaction.text, "\n  [NOM]\nDéveloppeur Rust chez Acme\n[NOM], 3 ans");
+        assert_eq!(redaction.restore("# [NOM]"), "# Jean Dupont");
+
+        // Première ligne qui n'est pas un nom : rien n'est masqué
+        let redaction = anonymize("CURRICULUM VITAE\nJean Dupont", None);
+        assert_eq!(redaction.text, "CURRICULUM VITAE\nJean Dupont");
+        assert!(!redaction.masks_name());
+    }
+}
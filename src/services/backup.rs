@@ -0,0 +1,184 @@
+// Sauvegardes périodiques de la base SQLite (BACKUP_INTERVAL_HOURS).
+// Chaque sauvegarde est une copie cohérente faite par `VACUUM INTO` ; seules les
+// BACKUP_KEEP plus récentes sont conservées dans BACKUP_DIR.
+// /restorebackup remplace la base active par l'une d'elles.
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::db::Database;
+
+const BACKUP_PREFIX: &str = "bot-";
+const BACKUP_SUFFIX: &str = ".db";
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Unknown backup: {0}")]
+    UnknownBackup(String),
+    #[error("Backup failed the integrity check: {0}")]
+    Corrupted(String),
+}
+
+/// Fichier de sauvegarde disponible
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Nom horodaté d'une sauvegarde : l'ordre alphabétique suit l'ordre chronologique
+pub fn backup_file_name(now: DateTime<Utc>) -> String {
+    format!("{}{}{}", BACKUP_PREFIX, now.format("%Y%m%d-%H%M%S"), BACKUP_SUFFIX)
+}
+
+fn is_backup_file(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX) && name.ends_with(BACKUP_SUFFIX)
+}
+
+/// Nom saisi par un admin : uniquement un fichier de sauvegarde, sans chemin
+fn is_restorable_name(name: &str) -> bool {
+    is_backup_file(name) && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+/// Phrase à saisir pour confirmer la restauration de `name`
+pub fn restore_confirmation_phrase(name: &str) -> String {
+    format!("RESTORE {}", name)
+}
+
+/// Sauvegardes à supprimer pour n'en garder que les `keep` plus récentes
+pub fn backups_to_prune(names: &[String], keep: usize) -> Vec<String> {
+    let mut backups: Vec<&String> = names.iter().filter(|n| is_backup_file(n)).collect();
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    backups.into_iter().take(excess).cloned().collect()
+}
+
+/// Crée une sauvegarde dans `dir` puis supprime les plus anciennes au-delà de `keep`
+pub async fn run_backup(db: &Database, dir: &Path, keep: usize) -> Result<PathBuf, BackupError> {
+    tokio::fs::create_dir_all(dir).await?;
+
+    let path = dir.join(backup_file_name(Utc::now()));
+    db.backup_database(&path.to_string_lossy()).await?;
+
+    let mut names = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        names.push(entry.file_name().to_string_lossy().to_string());
+    }
+
+    for name in backups_to_prune(&names, keep) {
+        let old = dir.join(&name);
+        match tokio::fs::remove_file(&old).await {
+            Ok(()) => info!("Removed old backup {:?}", old),
+            Err(e) => warn!("Failed to remove old backup {:?}: {}", old, e),
+        }
+    }
+
+    Ok(path)
+}
+
+/// Sauvegardes disponibles, de la plus récente à la plus ancienne
+pub async fn list_backups(dir: &Path) -> Result<Vec<BackupFile>, BackupError> {
+    let mut backups = Vec::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(backups),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_backup_file(&name) {
+            let size = entry.metadata().await?.len();
+            backups.push(BackupFile { name, size });
+        }
+    }
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+/// Remplace la base active par la sauvegarde `name` de `dir`. La base courante est
+/// d'abord sauvegardée (nom retourné) pour pouvoir annuler la restauration.
+pub async fn restore_backup(db: &Database, dir: &Path, name: &str) -> Result<String, BackupError> {
+    let snapshot = dir.join(name);
+    if !is_restorable_name(name) || !tokio::fs::try_exists(&snapshot).await? {
+        return Err(BackupError::UnknownBackup(name.to_string()));
+    }
+    check_integrity(&snapshot)?;
+
+    let safety_name = backup_file_name(Utc::now());
+    db.backup_database(&dir.join(&safety_name).to_string_lossy()).await?;
+
+    // En cas d'échec (sauvegarde trop ancienne pour les migrations...), la sauvegarde de
+    // sécurité est remise en place : le bot ne continue jamais sur une base vide
+    let replace_with = |source: &Path, db_path: &Path| -> Result<(), BackupError> {
+        let temp_path = db_path.with_extension("restore.tmp");
+        std::fs::copy(source, &temp_path)?;
+        std::fs::rename(&temp_path, db_path)?;
+        Ok(())
+    };
+    let safety_snapshot = dir.join(&safety_name);
+    db.reopen(
+        |db_path| replace_with(&snapshot, db_path),
+        |db_path| replace_with(&safety_snapshot, db_path),
+    )
+    .await?;
+
+    info!("Database restored from backup {:?} (previous state saved as {})", snapshot, safety_name);
+    Ok(safety_name)
+}
+
+/// Refuse une sauvegarde illisible avant de fermer la base active
+fn check_integrity(path: &Path) -> Result<(), BackupError> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(BackupError::Corrupted(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_backups_to_prune_keeps_most_recent() {
+        let first = backup_file_name(Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap());
+        let second = backup_file_name(Utc.with_ymd_and_hms(2024, 3, 1, 21, 0, 0).unwrap());
+        let third = backup_file_name(Utc.with_ymd_and_hms(2024, 3, 2, 9, 0, 0).unwrap());
+        assert_eq!(first, "bot-20240301-090000.db");
+
+        // Ordre de lecture du dossier quelconque, fichiers étrangers ignorés
+        let names = vec![third.clone(), "notes.txt".to_string(), first.clone(), second.clone()];
+        assert_eq!(backups_to_prune(&names, 2), vec![first.clone()]);
+        assert_eq!(backups_to_prune(&names, 1), vec![first, second]);
+        assert!(backups_to_prune(&names, 5).is_empty());
+    }
+
+    #[test]
+    fn test_restore_rejects_paths_and_corrupted_files() {
+        assert!(is_restorable_name("bot-20240301-090000.db"));
+        assert!(!is_restorable_name("../bot.db"));
+        assert!(!is_restorable_name("bot-../../etc/passwd.db"));
+        assert!(!is_restorable_name("notes.txt"));
+
+        let dir = std::env::temp_dir().join(format!("backup-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let garbage = dir.join("bot-20240301-090000.db");
+        std::fs::write(&garbage, b"definitely not sqlite, just some bytes long enough").unwrap();
+        assert!(check_integrity(&garbage).is_err());
+
+        let valid = dir.join("bot-20240302-090000.db");
+        Connection::open(&valid).unwrap().execute_batch("CREATE TABLE t (id INTEGER);").unwrap();
+        assert!(check_integrity(&valid).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
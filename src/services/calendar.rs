@@ -0,0 +1,127 @@
+// Export iCalendar (RFC 5545) des entretiens et rappels, importable dans Google Calendar,
+// Outlook ou Apple Calendar. Les dates stockées en base sont en UTC.
+use chrono::{Duration, NaiveDateTime};
+
+/// Longueur maximale d'une ligne avant repli (octets, hors CRLF)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Événement à exporter
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Identifiant stable : réimporter le fichier met à jour l'événement au lieu de le dupliquer
+    pub uid: String,
+    /// Début en UTC
+    pub start: NaiveDateTime,
+    pub duration: Duration,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Calendrier complet, lignes terminées par CRLF. `stamp` (UTC) renseigne DTSTAMP
+pub fn build_ics(events: &[CalendarEvent], stamp: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Automate-CV-discordBot//Calendrier des candidatures//FR".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", escape_text(&event.uid)));
+        lines.push(format!("DTSTAMP:{}", format_utc(stamp)));
+        lines.push(format!("DTSTART:{}", format_utc(event.start)));
+        lines.push(format!("DTEND:{}", format_utc(event.start + event.duration)));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(ref description) = event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(ref url) = event.url {
+            lines.push(format!("URL:{}", url.trim()));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect::<Vec<_>>().join("")
+}
+
+fn format_utc(dt: NaiveDateTime) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Échappement des valeurs TEXT : `\`, `;`, `,` et retours à la ligne
+fn escape_text(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Replie une ligne à 75 octets (suite préfixée d'une espace) sans couper un caractère UTF-8
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut current = 0;
+    for c in line.chars() {
+        // Les lignes de continuation commencent par une espace, comptée dans la limite
+        let limit = if folded.is_empty() { MAX_LINE_OCTETS } else { MAX_LINE_OCTETS - 1 };
+        if current + c.len_utf8() > limit {
+            folded.push_str("\r\n ");
+            current = 0;
+        }
+        folded.push(c);
+        current += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_build_ics() {
+        let event = CalendarEvent {
+            uid: "interview-12@automate-cv".to_string(),
+            start: at("2030-03-15 14:00:00"),
+            duration: Duration::hours(1),
+            summary: "Entretien : Dev, Rust; Acme".to_string(),
+            description: Some("Ligne 1\nC:\\docs".to_string()),
+            url: None,
+        };
+        let ics = build_ics(&[event], at("2030-03-01 08:00:00"));
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20300301T080000Z\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20300315T140000Z\r\nDTEND:20300315T150000Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Entretien : Dev\\, Rust\\; Acme\r\n"));
+        assert!(ics.contains("\r\nDESCRIPTION:Ligne 1\\nC:\\\\docs\r\n"));
+        assert!(!ics.contains("URL:"));
+    }
+
+    #[test]
+    fn test_fold_line_respects_octets_and_chars() {
+        let line = format!("SUMMARY:{}", "é".repeat(100));
+        let folded = fold_line(&line);
+        for part in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(part.len() <= MAX_LINE_OCTETS);
+        }
+        let unfolded = folded.trim_end_matches("\r\n").replace("\r\n ", "");
+        assert_eq!(unfolded, line);
+        assert_eq!(fold_line("END:VEVENT"), "END:VEVENT\r\n");
+    }
+}
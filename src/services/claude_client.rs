@@ -1,149 +1,286 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use thiserror::Error;
 use tracing::{debug, info, error, warn};
 
+/// Erreurs du serveur Claude, classées selon la réaction attendue :
+/// relancer (`BackendUnavailable`, `RateLimited`), corriger l'entrée (`Api`) ou réessayer
+/// plus tard une réponse mal formée (`ParseError`, `Timeout`).
 #[derive(Error, Debug)]
 pub enum ClaudeError {
-    #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
-    #[error("JSON error: {0}")]
-    Json(#[from] serde_json::Error),
+    /// Erreur renvoyée par le serveur pour cette requête (HTTP 4xx, champ `error`)
     #[error("API error: {0}")]
     Api(String),
-    #[error("Connection error: {0}")]
-    Connection(String),
+    /// Réponse reçue mais inexploitable (JSON invalide, texte brut au lieu de JSON)
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    /// Serveur injoignable ou en panne (connexion refusée, HTTP 5xx)
+    #[error("Backend unavailable: {0}")]
+    BackendUnavailable(String),
+    /// Pas de réponse dans le délai imparti (côté bot ou côté CLI, HTTP 504)
+    #[error("Timeout: {0}")]
+    Timeout(String),
+    /// Trop de requêtes (HTTP 429) ; délai suggéré par l'en-tête Retry-After
+    #[error("Rate limited (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+    /// Autre erreur de transport
+    #[error("HTTP error: {0}")]
+    Http(reqwest::Error),
+}
+
+impl From<reqwest::Error> for ClaudeError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            ClaudeError::Timeout(e.to_string())
+        } else if e.is_connect() {
+            ClaudeError::BackendUnavailable(e.to_string())
+        } else if e.is_decode() {
+            ClaudeError::ParseError(e.to_string())
+        } else {
+            ClaudeError::Http(e)
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClaudeError {
+    fn from(e: serde_json::Error) -> Self {
+        ClaudeError::ParseError(e.to_string())
+    }
+}
+
+/// Message de `ClaudeError::ParseError` quand /prompt ne renvoie aucun texte
+const EMPTY_RESPONSE: &str = "empty response";
+
+/// Attente maximale acceptée sur un Retry-After avant de relancer automatiquement
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+impl ClaudeError {
+    /// Erreur correspondant à une réponse HTTP en échec (consomme le corps pour le message)
+    async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let body = response.text().await.unwrap_or_default();
+        Self::from_status(status, retry_after, &body)
+    }
+
+    fn from_status(status: reqwest::StatusCode, retry_after: Option<Duration>, body: &str) -> Self {
+        // Le serveur renvoie {"error": "..."} : garder le message plutôt que le JSON brut
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+            .unwrap_or_else(|| body.to_string());
+        let message = format!("HTTP {}: {}", status, message);
+        match status.as_u16() {
+            429 => ClaudeError::RateLimited { retry_after },
+            408 | 504 => ClaudeError::Timeout(message),
+            500..=599 => ClaudeError::BackendUnavailable(message),
+            _ => ClaudeError::Api(message),
+        }
+    }
+
+    /// Réponse vide de Claude : l'appel peut simplement être relancé
+    pub fn is_empty_response(&self) -> bool {
+        matches!(self, ClaudeError::ParseError(msg) if msg == EMPTY_RESPONSE)
+    }
+
+    /// Relance automatique utile : le serveur est momentanément indisponible ou saturé.
+    /// Un délai dépassé n'est pas relancé (chaque tentative attendrait tout le délai).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ClaudeError::BackendUnavailable(_) => true,
+            ClaudeError::RateLimited { retry_after } => retry_after.is_none_or(|d| d <= MAX_RETRY_AFTER),
+            _ => false,
+        }
+    }
+
+    /// Conseil affiché à l'utilisateur selon la nature de l'erreur
+    pub fn user_hint(&self) -> &'static str {
+        match self {
+            ClaudeError::BackendUnavailable(_) | ClaudeError::Http(_) => {
+                "Le serveur Claude est indisponible : réessayez dans quelques instants."
+            }
+            ClaudeError::Timeout(_) => "Le serveur Claude a mis trop de temps à répondre : réessayez plus tard.",
+            ClaudeError::RateLimited { .. } => "Trop de requêtes en cours : patientez une minute avant de relancer.",
+            ClaudeError::ParseError(_) => "La réponse de Claude était inexploitable : relancer suffit en général.",
+            ClaudeError::Api(_) => "Vérifiez le contenu envoyé (offre, CV) avant de relancer.",
+        }
+    }
+}
+
+/// Délais d'attente des requêtes, par type d'appel
+#[derive(Debug, Clone, Copy)]
+pub struct ClaudeTimeouts {
+    /// Appels d'analyse (synthèse, compétences, salaire, extraction PDF)
+    pub synthesis: Duration,
+    /// Appels de génération (CV personnalisé, PDF, prompts libres)
+    pub generation: Duration,
+}
+
+impl Default for ClaudeTimeouts {
+    fn default() -> Self {
+        Self {
+            synthesis: Duration::from_secs(120),
+            generation: Duration::from_secs(300),
+        }
+    }
 }
 
 /// HTTP Client for Claude Code server
 pub struct ClaudeClient {
     base_url: String,
     client: reqwest::Client,
+    timeouts: ClaudeTimeouts,
+    /// Passe à false dès que /analyze répond 404 (serveur sans endpoint combiné)
+    analyze_supported: AtomicBool,
+    /// LOG_FULL_BODIES : corps complets dans les logs debug (CV et offres compris)
+    log_full_bodies: bool,
 }
 
 impl ClaudeClient {
-    /// Create a new client
-    pub fn new(base_url: &str) -> Self {
+    /// Create a new client (the timeout is set per request, see `ClaudeTimeouts`)
+    pub fn new(base_url: &str, timeouts: ClaudeTimeouts, log_full_bodies: bool) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
             client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
+                .connect_timeout(Duration::from_secs(10))
                 .build()
                 .expect("Failed to create HTTP client"),
+            timeouts,
+            analyze_supported: AtomicBool::new(true),
+            log_full_bodies,
+        }
+    }
+
+    /// Corps JSON tel qu'écrit dans les logs : textes longs masqués sauf avec LOG_FULL_BODIES
+    fn loggable(&self, body: &serde_json::Value) -> serde_json::Value {
+        if self.log_full_bodies {
+            body.clone()
+        } else {
+            redact_body(body)
+        }
+    }
+
+    /// Corps JSON d'une réponse (erreur typée si le statut HTTP est en échec)
+    async fn read_json(&self, response: reqwest::Response) -> Result<serde_json::Value, ClaudeError> {
+        if !response.status().is_success() {
+            return Err(ClaudeError::from_response(response).await);
         }
+        let data: serde_json::Value = response.json().await?;
+        debug!("Response body: {}", self.loggable(&data));
+        Ok(data)
+    }
+
+    /// POST JSON sur `path` (avec relances) et renvoie le corps JSON brut de la réponse
+    async fn post_value(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, ClaudeError> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.post_with_retry(&url, body, timeout).await?;
+        self.read_json(response).await
     }
 
-    /// Create client from environment variables
-    pub fn from_env() -> Self {
-        let base_url = std::env::var("CLAUDE_API_URL")
-            .unwrap_or_else(|_| "http://claudecode:8080".to_string());
-        Self::new(&base_url)
+    /// POST JSON sur `path` et désérialise la réponse après les vérifications communes
+    /// (statut HTTP, champ `error`, `success: false`, `raw_response`), voir `parse_envelope`
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<T, ClaudeError> {
+        let data = self.post_value(path, body, timeout).await?;
+        parse_envelope(data, path)
     }
 
     /// Check if the server is healthy
     pub async fn health_check(&self) -> Result<bool, ClaudeError> {
         let url = format!("{}/health", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).timeout(self.timeouts.synthesis).send().await?;
         Ok(response.status().is_success())
     }
 
-    /// HTTP POST with exponential backoff retry (3 attempts: 0s, 1s, 2s delays)
+    /// HTTP POST with exponential backoff retry (3 attempts: 0s, 1s, 2s delays).
+    /// `timeout` applies to each attempt.
     async fn post_with_retry(
         &self,
         url: &str,
         body: &serde_json::Value,
+        timeout: Duration,
     ) -> Result<reqwest::Response, ClaudeError> {
         let delays_secs = [0u64, 1, 2];
-        let mut last_err = None;
+        let mut last_err: Option<ClaudeError> = None;
+        debug!("POST {} body: {}", url, self.loggable(body));
 
         for (attempt, &delay) in delays_secs.iter().enumerate() {
             if delay > 0 {
-                warn!("Retry attempt {} for {} (waiting {}s)", attempt, url, delay);
-                tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+                // Un 429 indique lui-même combien attendre
+                let wait = match &last_err {
+                    Some(ClaudeError::RateLimited { retry_after: Some(d) }) => *d,
+                    _ => Duration::from_secs(delay),
+                };
+                warn!("Retry attempt {} for {} (waiting {:?})", attempt, url, wait);
+                tokio::time::sleep(wait).await;
             }
 
-            match self.client.post(url).json(body).send().await {
-                Ok(resp) if resp.status().is_server_error() => {
-                    let status = resp.status();
-                    let text = resp.text().await.unwrap_or_default();
-                    last_err = Some(ClaudeError::Api(format!("HTTP {}: {}", status, text)));
+            let err = match self.client.post(url).json(body).timeout(timeout).send().await {
+                Ok(resp) if resp.status().is_server_error() || resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    ClaudeError::from_response(resp).await
                 }
                 Ok(resp) => return Ok(resp),
-                Err(e) => {
-                    last_err = Some(ClaudeError::Http(e));
-                }
+                Err(e) => ClaudeError::from(e),
+            };
+            if !err.is_transient() {
+                return Err(err);
             }
+            last_err = Some(err);
         }
 
-        Err(last_err.unwrap_or_else(|| ClaudeError::Api("All retry attempts failed".to_string())))
+        Err(last_err.unwrap_or_else(|| ClaudeError::BackendUnavailable("All retry attempts failed".to_string())))
     }
 
     /// Send a generic prompt to Claude
     pub async fn prompt(&self, prompt: &str) -> Result<String, ClaudeError> {
-        let url = format!("{}/prompt", self.base_url);
-
-        debug!("Sending prompt to {}", url);
-
-        let response = self.post_with_retry(&url, &json!({ "prompt": prompt })).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
-        }
-
-        let data: serde_json::Value = response.json().await?;
+        debug!("Sending prompt");
 
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            return Err(ClaudeError::Api(error.to_string()));
-        }
-
-        if let Some(response) = data.get("response").and_then(|r| r.as_str()) {
-            return Ok(response.to_string());
-        }
-
-        Ok(serde_json::to_string_pretty(&data)?)
+        let data = self.post_value("/prompt", &json!({ "prompt": prompt }), self.timeouts.generation).await?;
+        prompt_response_text(&data)
     }
 
     /// Synthesize a job offer
-    pub async fn synthesize_job_offer(&self, job_description: &str) -> Result<JobSynthesis, ClaudeError> {
-        let url = format!("{}/synthesize", self.base_url);
-
+    pub async fn synthesize_job_offer(&self, job_description: &str, language: Option<&str>) -> Result<JobSynthesis, ClaudeError> {
         info!("Synthesizing job offer");
 
-        let response = self.post_with_retry(&url, &json!({ "job_description": job_description })).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
+        let mut payload = json!({ "job_description": job_description });
+        if let Some(language) = language {
+            payload["language"] = json!(language);
         }
 
-        let data: serde_json::Value = response.json().await?;
-
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            return Err(ClaudeError::Api(error.to_string()));
-        }
-
-        // Handle raw_response (fallback)
-        if data.get("raw_response").is_some() {
-            error!("Got raw response instead of structured data");
-            return Err(ClaudeError::Api("Failed to parse job synthesis".to_string()));
-        }
-
-        let synthesis: JobSynthesis = serde_json::from_value(data)?;
-        Ok(synthesis)
+        self.post_json("/synthesize", &payload, self.timeouts.synthesis).await
     }
 
-    /// Match skills between CV and job
+    /// Match skills between CV and job.
+    /// `declared_skills` comes from the structured CV profile (empty = extracted from the CV text).
+    /// `seniority` (junior, mid, senior, lead) is the level the candidate targets.
+    #[allow(clippy::too_many_arguments)]
     pub async fn match_skills(
         &self,
         job_description: &str,
         cv_content: &str,
         experience_notes: Option<&str>,
+        declared_skills: &[String],
+        language: Option<&str>,
+        seniority: Option<&str>,
     ) -> Result<SkillsMatch, ClaudeError> {
-        let url = format!("{}/match-skills", self.base_url);
-
         info!("Matching skills");
 
         let mut payload = json!({
@@ -154,101 +291,181 @@ impl ClaudeClient {
         if let Some(notes) = experience_notes {
             payload["experience_notes"] = json!(notes);
         }
-
-        let response = self.post_with_retry(&url, &payload).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
+        if !declared_skills.is_empty() {
+            payload["cv_skills"] = json!(declared_skills);
         }
-
-        let data: serde_json::Value = response.json().await?;
-
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            return Err(ClaudeError::Api(error.to_string()));
+        if let Some(language) = language {
+            payload["language"] = json!(language);
         }
-
-        if data.get("raw_response").is_some() {
-            error!("Got raw response instead of structured data");
-            return Err(ClaudeError::Api("Failed to parse skills match".to_string()));
+        if let Some(seniority) = seniority {
+            payload["seniority"] = json!(seniority);
         }
 
-        let skills: SkillsMatch = serde_json::from_value(data)?;
-        Ok(skills)
+        self.post_json("/match-skills", &payload, self.timeouts.synthesis).await
     }
 
-    /// Analyze salary for a job
+    /// Analyze salary for a job. `currency` (ISO code) overrides the currency of the amounts,
+    /// otherwise the server picks it from the job location (EUR by default).
     pub async fn analyze_salary(
         &self,
         job_description: &str,
         location: Option<&str>,
+        language: Option<&str>,
+        currency: Option<&str>,
     ) -> Result<SalaryAnalysis, ClaudeError> {
-        let url = format!("{}/salary-analysis", self.base_url);
-
         info!("Analyzing salary");
 
-        let response = self.post_with_retry(&url, &json!({
+        let mut payload = json!({
             "job_description": job_description,
             "location": location.unwrap_or("France")
-        })).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
+        });
+        if let Some(language) = language {
+            payload["language"] = json!(language);
+        }
+        if let Some(currency) = currency {
+            payload["currency"] = json!(currency);
         }
 
-        let data: serde_json::Value = response.json().await?;
+        self.post_json("/salary-analysis", &payload, self.timeouts.synthesis).await
+    }
 
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            return Err(ClaudeError::Api(error.to_string()));
-        }
+    /// Synthesis + skills match + salary analysis in a single call to /analyze.
+    /// Falls back to the three separate endpoints when the server has no /analyze (404).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn analyze_all(
+        &self,
+        job_description: &str,
+        cv_content: &str,
+        experience_notes: Option<&str>,
+        declared_skills: &[String],
+        language: Option<&str>,
+        currency: Option<&str>,
+        seniority: Option<&str>,
+    ) -> Result<FullAnalysis, ClaudeError> {
+        if self.analyze_supported.load(Ordering::Relaxed) {
+            let url = format!("{}/analyze", self.base_url);
+
+            info!("Analyzing job offer (combined)");
+
+            let mut payload = json!({
+                "job_description": job_description,
+                "cv_content": cv_content
+            });
+            if let Some(notes) = experience_notes {
+                payload["experience_notes"] = json!(notes);
+            }
+            if !declared_skills.is_empty() {
+                payload["cv_skills"] = json!(declared_skills);
+            }
+            if let Some(language) = language {
+                payload["language"] = json!(language);
+            }
+            if let Some(currency) = currency {
+                payload["currency"] = json!(currency);
+            }
+            if let Some(seniority) = seniority {
+                payload["seniority"] = json!(seniority);
+            }
 
-        if data.get("raw_response").is_some() {
-            error!("Got raw response instead of structured data");
-            return Err(ClaudeError::Api("Failed to parse salary analysis".to_string()));
+            let response = self.post_with_retry(&url, &payload, self.timeouts.synthesis).await?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                warn!("Server has no /analyze endpoint, using separate calls");
+                self.analyze_supported.store(false, Ordering::Relaxed);
+            } else {
+                let data = self.read_json(response).await?;
+                return parse_envelope(data, "/analyze");
+            }
         }
 
-        let salary: SalaryAnalysis = serde_json::from_value(data)?;
-        Ok(salary)
+        let synthesis = self.synthesize_job_offer(job_description, language).await?;
+        let skills_match = self
+            .match_skills(job_description, cv_content, experience_notes, declared_skills, language, seniority)
+            .await?;
+        let salary_analysis = self.analyze_salary(job_description, Some(&synthesis.location), language, currency).await?;
+        Ok(FullAnalysis {
+            synthesis,
+            skills_match,
+            salary_analysis,
+        })
+    }
+
+    /// Extract a structured profile (name, email, skills...) from the CV text
+    pub async fn parse_cv_profile(&self, cv_text: &str) -> Result<CvProfile, ClaudeError> {
+        info!("Parsing CV profile");
+
+        let prompt = format!(
+            "Extrais le profil de ce CV et retourne UNIQUEMENT un JSON valide:\n\
+             {{\n\
+                 \"name\": \"Prénom Nom ou null\",\n\
+                 \"email\": \"email ou null\",\n\
+                 \"skills\": [\"compétence1\", \"compétence2\"],\n\
+                 \"experience_years\": 5,\n\
+                 \"education\": [\"Diplôme - École (année)\"]\n\
+             }}\n\n\
+             CV:\n{}",
+            cv_text
+        );
+        let response = self.prompt(&prompt).await?;
+
+        parse_cv_profile_response(&response)
+            .ok_or_else(|| ClaudeError::ParseError("Failed to parse CV profile".to_string()))
+    }
+
+    /// Suggest related job titles and search keywords from the synthesis and the user's skills
+    pub async fn suggest_roles(
+        &self,
+        synthesis: &JobSynthesis,
+        profile_skills: &[String],
+        language: &str,
+    ) -> Result<RoleSuggestions, ClaudeError> {
+        info!("Suggesting related roles");
+
+        let prompt = format!(
+            "À partir de cette offre et des compétences du candidat, propose des intitulés de postes \
+             proches et des mots-clés à utiliser sur les sites d'emploi. Retourne UNIQUEMENT un JSON valide:\n\
+             {{\n\
+                 \"titles\": [\"Intitulé 1\", \"Intitulé 2\"],\n\
+                 \"keywords\": [\"mot-clé 1\", \"mot-clé 2\"]\n\
+             }}\n\
+             {} intitulés et {} mots-clés au maximum, rédigés dans la langue de code ISO \"{}\".\n\n\
+             Poste: {}\n\
+             Exigences clés: {}\n\
+             Compétences du candidat: {}",
+            MAX_ROLE_SUGGESTIONS,
+            MAX_ROLE_SUGGESTIONS,
+            language,
+            synthesis.title,
+            synthesis.key_requirements.join(", "),
+            if profile_skills.is_empty() { "non renseignées".to_string() } else { profile_skills.join(", ") }
+        );
+        let response = self.prompt(&prompt).await?;
+
+        parse_role_suggestions(&response)
+            .ok_or_else(|| ClaudeError::ParseError("Failed to parse role suggestions".to_string()))
     }
 
     /// Extract text from a PDF file
     pub async fn extract_pdf(&self, pdf_base64: &str) -> Result<String, ClaudeError> {
-        let url = format!("{}/extract-pdf", self.base_url);
-
         info!("Extracting PDF text");
 
-        let response = self.post_with_retry(&url, &json!({ "pdf_base64": pdf_base64 })).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
-        }
+        let body = json!({ "pdf_base64": pdf_base64 });
+        let extracted: ExtractedText = self.post_json("/extract-pdf", &body, self.timeouts.synthesis).await?;
+        Ok(extracted.text)
+    }
 
-        let data: serde_json::Value = response.json().await?;
+    /// Transcrit le texte d'une capture d'écran d'offre (OCR par Claude)
+    pub async fn extract_image_text(&self, image_base64: &str, mime_type: &str) -> Result<String, ClaudeError> {
+        info!("Extracting job posting text from image ({})", mime_type);
 
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            if !error.is_empty() {
-                return Err(ClaudeError::Api(error.to_string()));
-            }
-        }
+        let body = json!({ "image_base64": image_base64, "mime_type": mime_type });
+        let extracted: ExtractedText = self.post_json("/extract-image", &body, self.timeouts.synthesis).await?;
 
-        let success = data.get("success").and_then(|s| s.as_bool()).unwrap_or(false);
-        if !success {
-            let error_msg = data.get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("Unknown error");
-            return Err(ClaudeError::Api(error_msg.to_string()));
+        let text = extracted.text.trim().to_string();
+        if text.is_empty() {
+            return Err(ClaudeError::ParseError("No text extracted from image".to_string()));
         }
 
-        let text = data.get("text")
-            .and_then(|t| t.as_str())
-            .unwrap_or("")
-            .to_string();
-
         Ok(text)
     }
 
@@ -282,33 +499,14 @@ impl ClaudeClient {
             payload["experience_notes"] = json!(notes);
         }
 
-        let response = self.post_with_retry(&url, &payload).await?;
+        let response = self.post_with_retry(&url, &payload, self.timeouts.generation).await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
-        }
-
-        let data: serde_json::Value = response.json().await?;
-
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            return Err(ClaudeError::Api(error.to_string()));
+            return Err(ClaudeError::from_response(response).await);
         }
 
-        // Handle raw_response fallback from server
-        if let Some(raw) = data.get("raw_response").and_then(|r| r.as_str()) {
-            info!("Got raw response, using fallback");
-            return Ok(GeneratedCv {
-                latex_content: String::new(),
-                cv_text: raw.to_string(),
-                adaptations: vec!["CV généré (format brut)".to_string()],
-                summary: "Le CV a été généré mais le parsing a échoué.".to_string(),
-            });
-        }
-
-        let cv: GeneratedCv = serde_json::from_value(data)?;
-        Ok(cv)
+        let body = response.text().await?;
+        parse_generated_cv(&body)
     }
 
     /// Count the number of pages in a PDF from raw bytes.
@@ -340,10 +538,9 @@ impl ClaudeClient {
         job_title: &str,
         company: &str,
         single_page: bool,
+        style: &PdfStyle,
     ) -> Result<Vec<u8>, ClaudeError> {
-        let url = format!("{}/generate-pdf", self.base_url);
-
-        info!("Generating PDF (single_page={})", single_page);
+        info!("Generating PDF (single_page={}, template={:?})", single_page, style.template);
 
         let mut payload = json!({
             "cv_content": cv_content,
@@ -355,44 +552,93 @@ impl ClaudeClient {
         if single_page {
             payload["single_page"] = json!(true);
         }
-
-        let response = self.post_with_retry(&url, &payload).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClaudeError::Api(format!("HTTP {}: {}", status, body)));
+        if let Some(ref template) = style.template {
+            payload["template"] = json!(template);
+        }
+        if let Some(ref accent_color) = style.accent_color {
+            payload["accent_color"] = json!(accent_color);
         }
 
-        let data: serde_json::Value = response.json().await?;
+        self.post_pdf(&payload).await
+    }
+
+    /// Generate an archival PDF of a job synthesis through the CV PDF endpoint:
+    /// the offer is laid out as CV sections whose headings are overridden by `section_titles`
+    pub async fn generate_synthesis_pdf(&self, synthesis: &JobSynthesis, style: &PdfStyle) -> Result<Vec<u8>, ClaudeError> {
+        info!("Generating synthesis PDF for {} at {}", synthesis.title, synthesis.company);
 
-        if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
-            if !error.is_empty() {
-                return Err(ClaudeError::Api(error.to_string()));
+        let header = if synthesis.location.trim().is_empty() {
+            synthesis.company.clone()
+        } else {
+            format!("{} — {}", synthesis.company, synthesis.location)
+        };
+        let mut payload = json!({
+            "cv_content": synthesis_pdf_content(synthesis),
+            "name": synthesis.title,
+            "job_title": header,
+            "company": synthesis.company,
+            "section_titles": {
+                "profil": "Résumé de l'offre",
+                "competences": "Informations clés",
+                "experience": "Détail du poste"
             }
+        });
+        if let Some(ref template) = style.template {
+            payload["template"] = json!(template);
         }
-
-        let success = data.get("success").and_then(|s| s.as_bool()).unwrap_or(false);
-        if !success {
-            let error_msg = data.get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("PDF generation failed");
-            return Err(ClaudeError::Api(error_msg.to_string()));
+        if let Some(ref accent_color) = style.accent_color {
+            payload["accent_color"] = json!(accent_color);
         }
 
-        let pdf_base64 = data.get("pdf_base64")
-            .and_then(|p| p.as_str())
-            .ok_or_else(|| ClaudeError::Api("Missing pdf_base64 in response".to_string()))?;
+        self.post_pdf(&payload).await
+    }
+
+    async fn post_pdf(&self, payload: &serde_json::Value) -> Result<Vec<u8>, ClaudeError> {
+        let generated: GeneratedPdf = self.post_json("/generate-pdf", payload, self.timeouts.generation).await?;
 
         use base64::{engine::general_purpose::STANDARD, Engine};
-        let pdf_bytes = STANDARD.decode(pdf_base64)
-            .map_err(|e| ClaudeError::Api(format!("Failed to decode PDF: {}", e)))?;
+        let pdf_bytes = STANDARD.decode(&generated.pdf_base64)
+            .map_err(|e| ClaudeError::ParseError(format!("Failed to decode PDF: {}", e)))?;
 
         info!("PDF generated: {} bytes", pdf_bytes.len());
         Ok(pdf_bytes)
     }
 }
 
+/// Synthesis rendered in the `[SECTION]` format parsed by `/generate-pdf`:
+/// facts as `Label: value` rows, requirements / responsibilities / benefits as bullet entries
+fn synthesis_pdf_content(synthesis: &JobSynthesis) -> String {
+    // `|` separates columns and line breaks split entries in the server parser
+    let clean = |text: &str| text.replace('|', "/").split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut content = format!("[PROFIL]\n{}\n[COMPETENCES]\n", clean(&synthesis.summary));
+    let salary = synthesis.salary_range.as_deref().unwrap_or_default();
+    for (label, value) in [
+        ("Entreprise", synthesis.company.as_str()),
+        ("Lieu", synthesis.location.as_str()),
+        ("Contrat", synthesis.contract_type.as_str()),
+        ("Salaire", salary),
+    ] {
+        if !value.trim().is_empty() {
+            content.push_str(&format!("{}: {}\n", label, clean(value)));
+        }
+    }
+    content.push_str("[EXPERIENCE]\n");
+    for (heading, items) in [
+        ("Exigences clés", &synthesis.key_requirements),
+        ("Responsabilités", &synthesis.responsibilities),
+        ("Avantages", &synthesis.benefits),
+    ] {
+        if !items.is_empty() {
+            content.push_str(&format!("| {}\n", heading));
+            for item in items {
+                content.push_str(&format!("- {}\n", clean(item)));
+            }
+        }
+    }
+    content
+}
+
 // ============================================================================
 // Data structures
 // ============================================================================
@@ -462,10 +708,68 @@ pub struct SalaryAnalysis {
     pub negotiation_tips: Vec<String>,
 }
 
+/// Réponse de /analyze : les trois analyses de /applyjob en un seul appel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullAnalysis {
+    pub synthesis: JobSynthesis,
+    pub skills_match: SkillsMatch,
+    pub salary_analysis: SalaryAnalysis,
+}
+
+/// Devise des analyses salariales sans précision (offres en France)
+pub const DEFAULT_CURRENCY: &str = "EUR";
+
 fn default_currency() -> String {
-    "EUR".to_string()
+    DEFAULT_CURRENCY.to_string()
+}
+
+/// Style visuel transmis tel quel au serveur pour le rendu PDF (None = rendu par défaut)
+#[derive(Debug, Clone, Default)]
+pub struct PdfStyle {
+    /// "classic", "modern" ou "compact"
+    pub template: Option<String>,
+    /// Couleur d'accent au format "#rrggbb"
+    pub accent_color: Option<String>,
 }
 
+/// Profil structuré extrait du CV (colonne `base_cvs.parsed_data`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CvProfile {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    #[serde(default)]
+    pub experience_years: Option<f32>,
+    #[serde(default)]
+    pub education: Vec<String>,
+}
+
+impl CvProfile {
+    /// Aucun champ renseigné (ex: `parsed_data = "{}"` des CVs envoyés avant l'extraction du profil)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.email.is_none()
+            && self.skills.is_empty()
+            && self.experience_years.is_none()
+            && self.education.is_empty()
+    }
+}
+
+/// Intitulés de postes proches et mots-clés de recherche (option `suggest_roles` de /applyjob)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleSuggestions {
+    #[serde(default)]
+    pub titles: Vec<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+/// Nombre maximum d'intitulés et de mots-clés suggérés
+const MAX_ROLE_SUGGESTIONS: usize = 6;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratedCv {
     #[serde(default)]
@@ -479,6 +783,16 @@ pub struct GeneratedCv {
 }
 
 impl GeneratedCv {
+    /// Build a CV from unstructured text when the server response could not be parsed
+    pub fn from_raw(raw: &str) -> Self {
+        Self {
+            latex_content: String::new(),
+            cv_text: raw.trim().to_string(),
+            adaptations: vec!["CV généré (format brut)".to_string()],
+            summary: "Le CV a été généré mais le parsing a échoué.".to_string(),
+        }
+    }
+
     /// Returns the CV content (prefers cv_text, falls back to latex_content)
     pub fn get_content(&self) -> &str {
         if !self.cv_text.is_empty() {
@@ -488,3 +802,360 @@ impl GeneratedCv {
         }
     }
 }
+
+/// Parse the /generate-cv response body.
+/// Falls back to the raw text when the server returns `raw_response`, non-JSON output,
+/// or JSON that does not match `GeneratedCv`, so CV generation never fails on formatting alone.
+fn parse_generated_cv(body: &str) -> Result<GeneratedCv, ClaudeError> {
+    let data: serde_json::Value = match serde_json::from_str(body) {
+        Ok(data) => data,
+        Err(_) if !body.trim().is_empty() => {
+            warn!("Non-JSON response from /generate-cv, using raw text fallback");
+            return Ok(GeneratedCv::from_raw(body));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
+        return Err(ClaudeError::Api(error.to_string()));
+    }
+
+    // Handle raw_response fallback from server
+    if let Some(raw) = data.get("raw_response").and_then(|r| r.as_str()) {
+        info!("Got raw response, using fallback");
+        return Ok(GeneratedCv::from_raw(raw));
+    }
+
+    match serde_json::from_value::<GeneratedCv>(data) {
+        Ok(cv) if !cv.get_content().trim().is_empty() => Ok(cv),
+        Ok(_) => Err(ClaudeError::ParseError("Generated CV is empty".to_string())),
+        Err(e) => {
+            warn!("Malformed /generate-cv JSON ({}), using raw text fallback", e);
+            Ok(GeneratedCv::from_raw(body))
+        }
+    }
+}
+
+/// Extrait le JSON du profil d'une réponse texte libre (souvent entourée de ```json ... ```)
+fn parse_cv_profile_response(response: &str) -> Option<CvProfile> {
+    let profile: CvProfile = serde_json::from_str(extract_json_object(response)?).ok()?;
+    (!profile.is_empty()).then_some(profile)
+}
+
+/// Suggestions nettoyées (entrées vides et doublons retirés, MAX_ROLE_SUGGESTIONS par liste) ;
+/// None si rien d'exploitable
+fn parse_role_suggestions(response: &str) -> Option<RoleSuggestions> {
+    let raw: RoleSuggestions = serde_json::from_str(extract_json_object(response)?).ok()?;
+    let clean = |items: Vec<String>| {
+        let mut kept: Vec<String> = Vec::new();
+        for item in items.into_iter().map(|i| i.trim().to_string()).filter(|i| !i.is_empty()) {
+            if kept.len() < MAX_ROLE_SUGGESTIONS && !kept.iter().any(|k| k.eq_ignore_ascii_case(&item)) {
+                kept.push(item);
+            }
+        }
+        kept
+    };
+    let suggestions = RoleSuggestions { titles: clean(raw.titles), keywords: clean(raw.keywords) };
+    (!suggestions.titles.is_empty() || !suggestions.keywords.is_empty()).then_some(suggestions)
+}
+
+/// Premier objet JSON valide d'une réponse texte (bloc ```json, texte autour...).
+/// Le comptage des accolades ignore celles contenues dans les chaînes.
+fn extract_json_object(response: &str) -> Option<&str> {
+    for (start, _) in response.match_indices('{') {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for (offset, c) in response[start..].char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let candidate = &response[start..=start + offset];
+                        if serde_json::from_str::<serde_json::Value>(candidate).is_ok() {
+                            return Some(candidate);
+                        }
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Texte extrait par /extract-pdf et /extract-image
+#[derive(Debug, Deserialize)]
+struct ExtractedText {
+    #[serde(default)]
+    text: String,
+}
+
+/// PDF produit par /generate-pdf
+#[derive(Debug, Deserialize)]
+struct GeneratedPdf {
+    pdf_base64: String,
+}
+
+/// Vérifications communes aux réponses du serveur avant désérialisation :
+/// champ `error` non vide, `success: false` et `raw_response` (Claude n'a pas produit de JSON)
+fn parse_envelope<T: DeserializeOwned>(data: serde_json::Value, path: &str) -> Result<T, ClaudeError> {
+    if let Some(error) = data.get("error").and_then(|e| e.as_str()).filter(|e| !e.is_empty()) {
+        return Err(ClaudeError::Api(error.to_string()));
+    }
+
+    if data.get("success").and_then(|s| s.as_bool()) == Some(false) {
+        return Err(ClaudeError::Api(format!("{} failed without error message", path)));
+    }
+
+    if data.get("raw_response").is_some() {
+        error!("Got raw response instead of structured data from {}", path);
+        return Err(ClaudeError::ParseError(format!("Unstructured response from {}", path)));
+    }
+
+    Ok(serde_json::from_value(data)?)
+}
+
+/// Longueur au-delà de laquelle un texte est masqué dans les logs (offre, CV, prompt...)
+const MAX_LOGGED_FIELD_CHARS: usize = 80;
+
+/// Copie d'un corps JSON pour les logs : les textes longs sont remplacés par leur taille,
+/// pour ne pas écrire les CV et les offres (données personnelles) dans les logs
+fn redact_body(value: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(text) => {
+            let len = text.chars().count();
+            if len > MAX_LOGGED_FIELD_CHARS {
+                Value::String(format!("<{} caractères masqués>", len))
+            } else {
+                value.clone()
+            }
+        }
+        Value::Array(items) => Value::Array(items.iter().map(redact_body).collect()),
+        Value::Object(fields) => Value::Object(
+            fields.iter().map(|(key, field)| (key.clone(), redact_body(field))).collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Texte de la réponse de /prompt ; une réponse absente ou vide est une erreur
+/// (sinon le JSON brut finirait affiché dans un embed)
+fn prompt_response_text(data: &serde_json::Value) -> Result<String, ClaudeError> {
+    if let Some(error) = data.get("error").and_then(|e| e.as_str()) {
+        return Err(ClaudeError::Api(error.to_string()));
+    }
+
+    match data.get("response").and_then(|r| r.as_str()) {
+        Some(response) if !response.trim().is_empty() => Ok(response.to_string()),
+        _ => {
+            warn!("Claude /prompt returned no text: {}", redact_body(data));
+            Err(ClaudeError::ParseError(EMPTY_RESPONSE.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthesis_pdf_content() {
+        let synthesis = JobSynthesis {
+            title: "Data Engineer".to_string(),
+            company: "Acme".to_string(),
+            location: "Paris".to_string(),
+            contract_type: String::new(),
+            key_requirements: vec!["Python | SQL".to_string()],
+            responsibilities: vec!["Pipelines\nbatch".to_string()],
+            benefits: vec![],
+            salary_range: Some("50-60k€".to_string()),
+            summary: "Équipe data.".to_string(),
+        };
+        assert_eq!(
+            synthesis_pdf_content(&synthesis),
+            "[PROFIL]\nÉquipe data.\n[COMPETENCES]\nEntreprise: Acme\nLieu: Paris\nSalaire: 50-60k€\n\
+             [EXPERIENCE]\n| Exigences clés\n- Python / SQL\n| Responsabilités\n- Pipelines batch\n"
+        );
+    }
+
+    #[test]
+    fn test_redact_body_masks_long_text() {
+        let cv = "x".repeat(500);
+        let body = json!({
+            "job_description": cv,
+            "language": "fr",
+            "declared_skills": ["Rust", "y".repeat(81)],
+            "fit_level": 2,
+        });
+        assert_eq!(
+            redact_body(&body),
+            json!({
+                "job_description": "<500 caractères masqués>",
+                "language": "fr",
+                "declared_skills": ["Rust", "<81 caractères masqués>"],
+                "fit_level": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_from_status() {
+        use reqwest::StatusCode;
+
+        let err = ClaudeError::from_status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(5)), "");
+        assert!(matches!(err, ClaudeError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(5)));
+        assert!(err.is_transient());
+        let err = ClaudeError::from_status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(600)), "");
+        assert!(!err.is_transient());
+
+        let err = ClaudeError::from_status(StatusCode::GATEWAY_TIMEOUT, None, r#"{"error": "Claude CLI timed out"}"#);
+        assert!(matches!(&err, ClaudeError::Timeout(msg) if msg.ends_with("Claude CLI timed out")));
+        assert!(!err.is_transient());
+
+        let err = ClaudeError::from_status(StatusCode::BAD_GATEWAY, None, "down");
+        assert!(matches!(err, ClaudeError::BackendUnavailable(_)));
+        assert!(err.is_transient());
+
+        let err = ClaudeError::from_status(StatusCode::BAD_REQUEST, None, r#"{"error": "Missing prompt"}"#);
+        assert!(matches!(&err, ClaudeError::Api(msg) if msg == "HTTP 400 Bad Request: Missing prompt"));
+        assert!(!err.is_transient());
+    }
+
+    #[test]
+    fn test_prompt_response_text() {
+        assert_eq!(prompt_response_text(&json!({"response": "Bonjour"})).unwrap(), "Bonjour");
+        assert!(prompt_response_text(&json!({"response": "  "})).unwrap_err().is_empty_response());
+        assert!(prompt_response_text(&json!({"result": "x"})).unwrap_err().is_empty_response());
+
+        let err = prompt_response_text(&json!({"error": "timeout"})).unwrap_err();
+        assert!(!err.is_empty_response());
+    }
+
+    #[test]
+    fn test_parse_generated_cv_structured() {
+        let cv = parse_generated_cv(r#"{"cv_text": "Jean Dupont", "summary": "Adapté", "adaptations": ["a"]}"#).unwrap();
+        assert_eq!(cv.get_content(), "Jean Dupont");
+        assert_eq!(cv.summary, "Adapté");
+    }
+
+    #[test]
+    fn test_parse_generated_cv_fallbacks() {
+        let cv = parse_generated_cv(r#"{"raw_response": "Jean Dupont\nDéveloppeur"}"#).unwrap();
+        assert_eq!(cv.get_content(), "Jean Dupont\nDéveloppeur");
+
+        let cv = parse_generated_cv("Voici votre CV :\nJean Dupont").unwrap();
+        assert!(cv.get_content().starts_with("Voici votre CV"));
+        assert!(!cv.summary.is_empty());
+
+        let cv = parse_generated_cv(r#"{"cv_text": 42}"#).unwrap();
+        assert_eq!(cv.get_content(), r#"{"cv_text": 42}"#);
+
+        assert!(parse_generated_cv(r#"{"error": "overloaded"}"#).is_err());
+        assert!(parse_generated_cv("").is_err());
+    }
+
+    #[test]
+    fn test_extract_json_object() {
+        assert_eq!(extract_json_object(r#"{"a": 1}"#), Some(r#"{"a": 1}"#));
+        assert_eq!(
+            extract_json_object("```json\n{\"a\": {\"b\": 2}}\n```"),
+            Some(r#"{"a": {"b": 2}}"#)
+        );
+        // Texte autour, y compris des accolades qui ne sont pas du JSON
+        assert_eq!(
+            extract_json_object(r#"Voici le {résultat} : {"a": 1} (fin})"#),
+            Some(r#"{"a": 1}"#)
+        );
+        // Accolades et guillemets échappés dans une chaîne
+        assert_eq!(
+            extract_json_object(r#"{"s": "fin } et \"{\" ici", "n": 1} suite }"#),
+            Some(r#"{"s": "fin } et \"{\" ici", "n": 1}"#)
+        );
+        assert_eq!(extract_json_object("aucun JSON ici"), None);
+        assert_eq!(extract_json_object("{ incomplet"), None);
+    }
+
+    #[test]
+    fn test_parse_cv_profile_response() {
+        let profile = parse_cv_profile_response(
+            "Voici le profil :\n```json\n{\"name\": \"Jean Dupont\", \"email\": null, \"skills\": [\"Rust\"], \"experience_years\": 5}\n```",
+        )
+        .unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Jean Dupont"));
+        assert_eq!(profile.skills, vec!["Rust".to_string()]);
+        assert_eq!(profile.experience_years, Some(5.0));
+        assert!(profile.education.is_empty());
+
+        assert!(parse_cv_profile_response("{}").is_none());
+        assert!(parse_cv_profile_response("pas de JSON").is_none());
+    }
+
+    #[test]
+    fn test_parse_role_suggestions() {
+        let suggestions = parse_role_suggestions(
+            "Voici: {\"titles\": [\"Ingénieur backend\", \" \", \"ingénieur BACKEND\"], \"keywords\": [\"Rust\", \"API REST\"]}",
+        )
+        .unwrap();
+        assert_eq!(suggestions.titles, vec!["Ingénieur backend"]);
+        assert_eq!(suggestions.keywords, vec!["Rust", "API REST"]);
+
+        let many: Vec<String> = (0..10).map(|i| format!("\"k{}\"", i)).collect();
+        let suggestions = parse_role_suggestions(&format!("{{\"keywords\": [{}]}}", many.join(","))).unwrap();
+        assert_eq!(suggestions.keywords.len(), MAX_ROLE_SUGGESTIONS);
+
+        assert!(parse_role_suggestions("{\"titles\": [], \"keywords\": []}").is_none());
+        assert!(parse_role_suggestions("pas de JSON").is_none());
+    }
+
+    #[test]
+    fn test_parse_full_analysis() {
+        let analysis: FullAnalysis = parse_envelope(json!({
+            "synthesis": {
+                "title": "Développeur Rust",
+                "company": "Acme",
+                "location": "Paris",
+                "contract_type": "CDI",
+                "key_requirements": ["Rust"],
+                "summary": "Backend"
+            },
+            "skills_match": {"match_score": 80, "matched_skills": [{"skill": "Rust", "match": true}]},
+            "salary_analysis": {"offered_min": null, "offered_max": null, "market_median": 55000}
+        }), "/analyze")
+        .unwrap();
+        assert_eq!(analysis.synthesis.company, "Acme");
+        assert_eq!(analysis.skills_match.match_score, 80);
+        assert_eq!(analysis.salary_analysis.currency, "EUR");
+
+        assert!(parse_envelope::<FullAnalysis>(json!({"raw_response": "..."}), "/analyze").is_err());
+        assert!(parse_envelope::<FullAnalysis>(json!({"synthesis": {}}), "/analyze").is_err());
+    }
+
+    #[test]
+    fn test_parse_envelope_errors() {
+        let err = parse_envelope::<ExtractedText>(json!({"error": "PDF illisible"}), "/extract-pdf").unwrap_err();
+        assert!(matches!(err, ClaudeError::Api(msg) if msg == "PDF illisible"));
+        let err = parse_envelope::<ExtractedText>(json!({"success": false}), "/extract-pdf").unwrap_err();
+        assert!(matches!(err, ClaudeError::Api(_)));
+        let err = parse_envelope::<JobSynthesis>(json!({"raw_response": "texte"}), "/synthesize").unwrap_err();
+        assert!(matches!(err, ClaudeError::ParseError(_)));
+
+        // Un champ error vide n'est pas une erreur
+        let extracted: ExtractedText = parse_envelope(json!({"success": true, "error": "", "text": "Offre"}), "/extract-pdf").unwrap();
+        assert_eq!(extracted.text, "Offre");
+        assert!(parse_envelope::<GeneratedPdf>(json!({"success": true}), "/generate-pdf").is_err());
+    }
+}
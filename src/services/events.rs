@@ -0,0 +1,85 @@
+// Webhook sortant : publie les événements de candidature vers un service externe
+// (Notion, Zapier, dashboards personnels...). Activé si EVENT_WEBHOOK_URL est défini (voir Config).
+use serde::Serialize;
+use std::sync::OnceLock;
+use tracing::{debug, warn};
+
+/// Événement publié lors de la création ou du changement de statut d'une candidature
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationEvent {
+    /// "application.created" ou "application.status_changed"
+    pub event: &'static str,
+    pub user_id: i64,
+    pub application_id: i64,
+    pub company: Option<String>,
+    pub old_status: Option<String>,
+    pub new_status: String,
+    pub timestamp: String,
+}
+
+impl ApplicationEvent {
+    pub fn created(user_id: i64, application_id: i64, company: Option<&str>, status: &str) -> Self {
+        Self {
+            event: "application.created",
+            user_id,
+            application_id,
+            company: company.map(|c| c.to_string()),
+            old_status: None,
+            new_status: status.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn status_changed(
+        user_id: i64,
+        application_id: i64,
+        company: Option<&str>,
+        old_status: Option<&str>,
+        new_status: &str,
+    ) -> Self {
+        Self {
+            event: "application.status_changed",
+            user_id,
+            application_id,
+            company: company.map(|c| c.to_string()),
+            old_status: old_status.map(|s| s.to_string()),
+            new_status: new_status.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+fn webhook_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client")
+    })
+}
+
+/// Envoie l'événement en tâche de fond vers `webhook_url` (EVENT_WEBHOOK_URL).
+/// Ne fait rien si aucun webhook n'est configuré. Les erreurs sont loggées, jamais propagées.
+pub fn emit(webhook_url: Option<&str>, event: ApplicationEvent) {
+    let Some(url) = webhook_url.map(|u| u.to_string()) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        match webhook_client().post(&url).json(&event).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                debug!("Webhook event {} sent for application {}", event.event, event.application_id);
+            }
+            Ok(resp) => {
+                warn!(
+                    "Webhook rejected event {} for application {}: HTTP {}",
+                    event.event, event.application_id, resp.status()
+                );
+            }
+            Err(e) => {
+                warn!("Failed to send webhook event {}: {}", event.event, e);
+            }
+        }
+    });
+}
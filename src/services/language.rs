@@ -0,0 +1,51 @@
+// Détection de la langue d'une offre (whatlang), pour que l'analyse et le CV
+// soient rédigés dans la langue de la candidature.
+use whatlang::Lang;
+
+/// Code de l'option `language` de /applyjob correspondant à la langue du texte.
+/// None si la détection est peu fiable (texte trop court, mélange de langues)
+/// ou si la langue n'est pas prise en charge.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let info = whatlang::detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    match info.lang() {
+        Lang::Fra => Some("fr"),
+        Lang::Eng => Some("en"),
+        Lang::Spa => Some("es"),
+        Lang::Deu => Some("de"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language(
+                "Nous recherchons un développeur backend expérimenté pour rejoindre notre équipe \
+                 à Lyon. Vous travaillerez sur des services en Rust et participerez aux choix d'architecture."
+            ),
+            Some("fr")
+        );
+        assert_eq!(
+            detect_language(
+                "We are looking for an experienced backend developer to join our team in London. \
+                 You will work on Rust services and take part in architecture decisions."
+            ),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language(
+                "Wir suchen einen erfahrenen Backend-Entwickler für unser Team in Berlin. \
+                 Sie arbeiten an Diensten in Rust und gestalten die Architektur mit."
+            ),
+            Some("de")
+        );
+        assert_eq!(detect_language("Rust"), None);
+    }
+}
@@ -0,0 +1,246 @@
+// Comparaison approximative des noms d'entreprise et intitulés de poste
+// (détection des candidatures en double, regroupements des statistiques)
+use std::collections::HashMap;
+use strsim::jaro_winkler;
+
+/// Seuil de similarité par défaut (Jaro-Winkler, 0.0 à 1.0), surchargé par DUPLICATE_SIMILARITY_THRESHOLD
+pub const DEFAULT_DUPLICATE_THRESHOLD: f64 = 0.9;
+
+/// Formes juridiques ignorées en fin de nom d'entreprise
+const LEGAL_SUFFIXES: &[&str] = &[
+    "corporation", "corp", "incorporated", "inc", "company", "co", "limited", "ltd", "llc",
+    "plc", "gmbh", "ag", "sa", "sas", "sasu", "sarl", "bv", "group", "groupe",
+];
+
+/// Filiales géographiques regroupées avec la maison mère dans les statistiques ("Google Cloud France").
+/// Retirées seulement si au moins deux mots restent : "Air France" et "Air Canada" ne sont pas des filiales de "Air".
+const REGION_SUFFIXES: &[&str] = &[
+    "france", "paris", "lyon", "europe", "emea", "international", "uk", "us", "usa", "germany",
+    "deutschland", "spain", "espana", "italia", "italy", "belgium", "belgique", "suisse",
+    "switzerland", "canada", "luxembourg", "nederland", "netherlands",
+];
+
+/// Mentions de genre fréquentes dans les intitulés d'offres
+const GENDER_MARKERS: &[&str] = &["h/f", "f/h", "m/f", "f/m", "m/w/d", "w/m/d", "h/f/x"];
+
+/// Libellés de remplacement renvoyés par Claude (ou saisis dans un import) à la place
+/// d'une entreprise ou d'un intitulé, sous leur forme simplifiée
+const PLACEHOLDER_LABELS: &[&str] = &[
+    "", "n a", "na", "nc", "n c", "nd", "n d", "none", "null", "nil", "unknown", "inconnu", "inconnue",
+    "non specifie", "non specifiee", "non precise", "non precisee", "non communique", "non communiquee",
+    "non renseigne", "non renseignee", "non indique", "non indiquee", "not specified", "not available",
+    "unspecified", "undisclosed", "confidential", "confidentiel", "confidentielle",
+];
+
+/// Retire les accents courants ("é" -> "e") pour tolérer les saisies sans accents
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        _ => c,
+    }
+}
+
+/// Minuscules sans accents, ponctuation remplacée par des espaces, espaces fusionnés.
+/// Les points sont supprimés pour regrouper les sigles ("S.A." -> "sa").
+fn simplify(input: &str) -> Vec<String> {
+    input
+        .to_lowercase()
+        .replace('.', "")
+        .chars()
+        .map(fold_accent)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Entreprise ou intitulé exploitable : None pour un libellé de remplacement
+/// ("Non spécifié", "N/A", "Unknown"...), sinon la valeur sans espaces superflus
+pub fn meaningful_label(value: &str) -> Option<&str> {
+    if PLACEHOLDER_LABELS.contains(&simplify(value).join(" ").as_str()) {
+        None
+    } else {
+        Some(value.trim())
+    }
+}
+
+/// Normalise un nom d'entreprise ("ACME Corporation" -> "acme")
+pub fn normalize_company(name: &str) -> String {
+    let mut tokens = simplify(name);
+    while tokens.len() > 1 && tokens.last().is_some_and(|t| LEGAL_SUFFIXES.contains(&t.as_str())) {
+        tokens.pop();
+    }
+    tokens.join(" ")
+}
+
+/// Normalise un intitulé de poste ("Développeur Rust (H/F)" -> "developpeur rust")
+pub fn normalize_title(title: &str) -> String {
+    let mut lowered = title.to_lowercase();
+    for marker in GENDER_MARKERS {
+        lowered = lowered.replace(marker, " ");
+    }
+    simplify(&lowered).join(" ")
+}
+
+/// Clé de regroupement d'une entreprise pour les statistiques :
+/// forme juridique et filiale géographique retirées ("Google Cloud France SAS" -> "google cloud")
+pub fn company_group_key(name: &str) -> String {
+    let mut tokens = simplify(name);
+    loop {
+        let strip = tokens.last().is_some_and(|t| {
+            (tokens.len() > 1 && LEGAL_SUFFIXES.contains(&t.as_str()))
+                || (tokens.len() > 2 && REGION_SUFFIXES.contains(&t.as_str()))
+        });
+        if !strip {
+            break;
+        }
+        tokens.pop();
+    }
+    tokens.join(" ")
+}
+
+/// Compte les libellés par clé normalisée, du plus fréquent au moins fréquent.
+/// Chaque groupe est affiché sous son libellé d'origine le plus utilisé.
+pub fn aggregate_by_key<'a, I, F>(labels: I, key: F) -> Vec<(String, i32)>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: Fn(&str) -> String,
+{
+    // clé -> (total, ordre d'apparition, occurrences par libellé d'origine)
+    let mut groups: HashMap<String, (i32, usize, HashMap<String, i32>)> = HashMap::new();
+    for label in labels {
+        let trimmed = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        let group_key = key(&trimmed);
+        if group_key.is_empty() {
+            continue;
+        }
+        let next_order = groups.len();
+        let group = groups.entry(group_key).or_insert_with(|| (0, next_order, HashMap::new()));
+        group.0 += 1;
+        *group.2.entry(trimmed).or_insert(0) += 1;
+    }
+
+    let mut aggregated: Vec<(String, i32, usize)> = groups
+        .into_values()
+        .map(|(count, order, variants)| {
+            let label = variants
+                .into_iter()
+                .max_by(|(a_label, a_count), (b_label, b_count)| a_count.cmp(b_count).then(b_label.cmp(a_label)))
+                .map(|(label, _)| label)
+                .unwrap_or_default();
+            (label, count, order)
+        })
+        .collect();
+    aggregated.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+    aggregated.into_iter().map(|(label, count, _)| (label, count)).collect()
+}
+
+/// Similarité entre deux chaînes déjà normalisées (0.0 si l'une est vide)
+fn similarity(a: &str, b: &str) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    jaro_winkler(a, b)
+}
+
+/// Indique si deux candidatures (entreprise + intitulé) désignent probablement la même offre
+pub fn is_probable_duplicate(
+    company_a: &str,
+    title_a: &str,
+    company_b: &str,
+    title_b: &str,
+    threshold: f64,
+) -> bool {
+    similarity(&normalize_company(company_a), &normalize_company(company_b)) >= threshold
+        && similarity(&normalize_title(title_a), &normalize_title(title_b)) >= threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_company_group_key_merges_regional_entities() {
+        assert_eq!(company_group_key("Google Cloud France"), "google cloud");
+        assert_eq!(company_group_key("GOOGLE  Cloud France SAS"), "google cloud");
+        assert_eq!(company_group_key("Google Cloud"), "google cloud");
+        // Au moins un mot est conservé
+        assert_eq!(company_group_key("France"), "france");
+    }
+
+    #[test]
+    fn test_company_group_key_keeps_single_word_brands() {
+        assert_eq!(company_group_key("Air France"), "air france");
+        assert_eq!(company_group_key("Air Canada"), "air canada");
+        assert_eq!(company_group_key("Air France SA"), "air france");
+        assert_ne!(company_group_key("Air France"), company_group_key("Air Canada"));
+    }
+
+    #[test]
+    fn test_meaningful_label_rejects_placeholders() {
+        for placeholder in [
+            "Non spécifié", "non specifie", "Non spécifiée", "N/A", "n.a.", "NA", "N.C.", "Unknown",
+            "Inconnue", "Not specified", "Confidentiel", "-", "  ", "?", "null",
+        ] {
+            assert_eq!(meaningful_label(placeholder), None, "{:?}", placeholder);
+        }
+        assert_eq!(meaningful_label("  Acme Corp "), Some("Acme Corp"));
+        assert_eq!(meaningful_label("NAF Consulting"), Some("NAF Consulting"));
+        assert_eq!(meaningful_label("Non-profit Alliance"), Some("Non-profit Alliance"));
+    }
+
+    #[test]
+    fn test_aggregate_by_key() {
+        let names = ["Google Cloud", "Google Cloud France", "Acme", "google cloud", "Acme Corp", "Globex", "Google Cloud"];
+        let top = aggregate_by_key(names, company_group_key);
+        assert_eq!(
+            top,
+            vec![
+                ("Google Cloud".to_string(), 4),
+                ("Acme".to_string(), 2),
+                ("Globex".to_string(), 1),
+            ]
+        );
+        assert!(aggregate_by_key(["", "  "], company_group_key).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_company_strips_legal_suffixes() {
+        assert_eq!(normalize_company("ACME Corporation"), "acme");
+        assert_eq!(normalize_company("Acme Corp."), "acme");
+        assert_eq!(normalize_company("Société Générale S.A."), "societe generale");
+        // Ne jamais vider complètement le nom
+        assert_eq!(normalize_company("Group"), "group");
+    }
+
+    #[test]
+    fn test_normalize_title_strips_gender_markers() {
+        assert_eq!(normalize_title("Développeur Rust (H/F)"), "developpeur rust");
+        assert_eq!(normalize_title("Backend  Engineer - m/w/d"), "backend engineer");
+    }
+
+    #[test]
+    fn test_near_duplicates_are_detected() {
+        let t = DEFAULT_DUPLICATE_THRESHOLD;
+        assert!(is_probable_duplicate("Acme Corp", "Backend Engineer", "ACME Corporation", "Backend Engineer", t));
+        assert!(is_probable_duplicate("Datadog", "Senior Software Engineer", "Datadog Inc.", "Senior Software Engineer (H/F)", t));
+        assert!(is_probable_duplicate("Ubisoft", "Développeur C++", "UBISOFT SA", "Developpeur C++", t));
+        assert!(is_probable_duplicate("Doctolib", "Backend Engineer", "Doctolib", "Backend Engineer II", t));
+    }
+
+    #[test]
+    fn test_different_offers_are_not_duplicates() {
+        let t = DEFAULT_DUPLICATE_THRESHOLD;
+        assert!(!is_probable_duplicate("Acme Corp", "Backend Engineer", "Globex", "Backend Engineer", t));
+        assert!(!is_probable_duplicate("Acme Corp", "Backend Engineer", "Acme Corp", "Product Designer", t));
+        assert!(!is_probable_duplicate("", "Backend Engineer", "", "Backend Engineer", t));
+    }
+}
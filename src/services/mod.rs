@@ -1,4 +1,12 @@
+pub mod anonymize;
+pub mod backup;
+pub mod calendar;
 pub mod claude_client;
+pub mod events;
+pub mod language;
+pub mod matching;
+pub mod storage;
+pub mod triggers;
 
-pub use claude_client::ClaudeClient;
-pub use claude_client::{JobSynthesis, SkillsMatch, SalaryAnalysis};
+pub use claude_client::{ClaudeClient, ClaudeTimeouts};
+pub use claude_client::{CvProfile, JobSynthesis, PdfStyle, RoleSuggestions, SkillsMatch, SalaryAnalysis};
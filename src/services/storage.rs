@@ -0,0 +1,92 @@
+// Écritures de fichiers sûres : un crash pendant l'écriture ne laisse jamais
+// un fichier partiel au chemin final (lu ensuite par l'extraction de texte).
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Écrit `bytes` dans un fichier temporaire du même dossier puis le renomme
+/// atomiquement vers `path`. Le fichier temporaire est supprimé en cas d'échec.
+pub async fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let temp_path = temp_path_for(path);
+
+    let result = async {
+        let mut file = tokio::fs::File::create(&temp_path).await?;
+        tokio::io::AsyncWriteExt::write_all(&mut file, bytes).await?;
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&temp_path, path).await
+    }
+    .await;
+
+    if result.is_err() {
+        if let Err(e) = tokio::fs::remove_file(&temp_path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove temp file {:?}: {}", temp_path, e);
+            }
+        }
+    }
+    result
+}
+
+/// Supprime les fichiers temporaires laissés par un arrêt brutal (au démarrage)
+pub fn remove_stale_temp_files(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_stale_temp_files(&path);
+        } else if is_temp_file(&path) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => info!("Removed stale temp file {:?}", path),
+                Err(e) => warn!("Failed to remove stale temp file {:?}: {}", path, e),
+            }
+        }
+    }
+}
+
+fn temp_path_for(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}{}", file_name, Uuid::new_v4(), TEMP_SUFFIX))
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy())
+        .map(|n| n.starts_with('.') && n.ends_with(TEMP_SUFFIX))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_atomic_and_cleanup() {
+        let dir = std::env::temp_dir().join(format!("storage-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let target = dir.join("cv.pdf");
+        write_atomic(&target, b"%PDF-1.4").await.unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"%PDF-1.4");
+
+        // Échec (dossier inexistant) : rien au chemin final
+        let missing = dir.join("missing").join("cv.pdf");
+        assert!(write_atomic(&missing, b"data").await.is_err());
+        assert!(!missing.exists());
+
+        let stale = temp_path_for(&target);
+        std::fs::write(&stale, b"partial").unwrap();
+        remove_stale_temp_files(&dir);
+        assert!(!stale.exists());
+        assert!(target.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
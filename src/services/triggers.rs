@@ -0,0 +1,78 @@
+// Détection des messages qui peuvent déclencher le bot sans commande slash
+// (offre d'emploi collée, CV déposé). Activé par MESSAGE_TRIGGERS_ENABLED.
+
+/// Longueur minimale d'un message pour être considéré comme une offre d'emploi
+pub const MIN_JOB_POSTING_LENGTH: usize = 400;
+/// Nombre minimal de mots-clés d'offre présents dans le message
+const MIN_JOB_KEYWORDS: usize = 2;
+
+const JOB_KEYWORDS: &[&str] = &[
+    "poste", "mission", "profil", "cdi", "cdd", "alternance", "stage", "salaire", "rémunération",
+    "compétences", "expérience", "recherchons", "rejoindre", "candidature", "télétravail",
+    "job", "position", "responsibilities", "requirements", "salary", "experience", "we are looking",
+    "join our team", "remote", "skills",
+];
+
+const CV_EXTENSIONS: &[&str] = &["pdf", "doc", "docx"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageTrigger {
+    /// Texte long ressemblant à une offre d'emploi
+    JobPosting,
+    /// Fichier CV (PDF, DOC, DOCX) déposé dans le canal
+    CvFile,
+}
+
+/// Détermine si un message doit déclencher une proposition du bot.
+/// Un fichier CV a priorité sur le texte du message.
+pub fn detect_trigger(content: &str, attachment_names: &[&str]) -> Option<MessageTrigger> {
+    let has_cv_file = attachment_names.iter().any(|name| {
+        name.rsplit_once('.')
+            .map(|(_, ext)| CV_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            .unwrap_or(false)
+    });
+    if has_cv_file {
+        return Some(MessageTrigger::CvFile);
+    }
+
+    if looks_like_job_posting(content) {
+        return Some(MessageTrigger::JobPosting);
+    }
+
+    None
+}
+
+fn looks_like_job_posting(content: &str) -> bool {
+    if content.chars().count() < MIN_JOB_POSTING_LENGTH {
+        return false;
+    }
+    let lower = content.to_lowercase();
+    JOB_KEYWORDS.iter().filter(|kw| lower.contains(*kw)).count() >= MIN_JOB_KEYWORDS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_cv_file() {
+        assert_eq!(detect_trigger("mon cv", &["CV_Dupont.PDF"]), Some(MessageTrigger::CvFile));
+        assert_eq!(detect_trigger("", &["photo.png"]), None);
+    }
+
+    #[test]
+    fn test_detect_job_posting() {
+        let posting = format!(
+            "Nous recherchons un développeur Rust en CDI. Missions : {}. Profil : 3 ans d'expérience.",
+            "concevoir des services backend ".repeat(15)
+        );
+        assert_eq!(detect_trigger(&posting, &[]), Some(MessageTrigger::JobPosting));
+
+        // Long mais sans vocabulaire d'offre
+        let chat = "bonjour à tous ".repeat(40);
+        assert_eq!(detect_trigger(&chat, &[]), None);
+
+        // Mots-clés mais trop court
+        assert_eq!(detect_trigger("poste en CDI, salaire ?", &[]), None);
+    }
+}